@@ -3,18 +3,238 @@ use super::*;
 #[derive(Debug)]
 pub enum Action {
   Variable(Variable),
+  /// `let a, b = foo()` gives every item of the tuple its own variable
+  Destructure(ActionDestructure),
   Return(Option<Box<Action>>),
   Assigment(ActionAssigment),
   FunctionCall(ActionFunctionCall),
   VarRef(String),
   StaticString(String_),
-  StaticNumber(Number),
-  Break,
+  StaticNumber(NumberLiteral),
+  StaticBoolean(bool),
+  /// `null`, the absence of a value
+  StaticNull,
+  /// `break` or `break foo` to make the loop result in foo
+  Break(Option<Box<Action>>),
   Continue,
+  /// `defer foo()` runs foo when the function returns
+  Defer(Box<Action>),
   For(ActionFor),
+  /// `for let i = 0; i < 10; i += 1 {}`
+  ForClassic(ActionForClassic),
   While(ActionWhile),
-  Loop(Actions),
-  NOOP,
+  /// `do {} while foo`, the body runs at least once
+  DoWhile(ActionWhile),
+  /// `try {} catch err {}`
+  Try(ActionTry),
+  Loop(Block),
+  /// `if foo {}`, when used as a value the bodies only contain the value like `if foo { 1 } else { 2 }`
+  If(ActionIf),
+  BinaryOp(ActionBinaryOp),
+  Comparison(ActionComparison),
+  Logical(ActionLogical),
+  UnaryOp(ActionUnaryOp),
+  Cast(ActionCast),
+  /// `_ = foo` explicitly ignores the value of foo
+  Discard(Box<Action>),
+  StructInit(ActionStructInit),
+  /// `[foo, bar, 1]`
+  ArrayInit(Vec<Action>),
+  /// `{"foo": bar, baz: 1}` as a list of keys and values
+  MapInit(Vec<(Action, Action)>),
+  /// A function without a name, like `|x| x + 1` or `fn(x int) { return x + 1 }`
+  Closure(Function),
+  /// `foo.bar`
+  FieldAccess(ActionFieldAccess),
+  /// `foo.bar()`
+  MethodCall(ActionMethodCall),
+  /// `foo.bar = baz`
+  FieldAssigment(ActionFieldAssigment),
+  /// `foo[bar]`
+  Index(ActionIndex),
+  NullCoalesce(ActionNullCoalesce),
+  /// `foo!` asserts that foo is not null
+  NullAssert(Box<Action>),
+  Match(ActionMatch),
+  Range(ActionRange),
+  /// A named function declared inside of another function like `fn foo() { fn bar() {} }`
+  FunctionDecl(Function),
+  /// `(foo, 1)`
+  TupleInit(Vec<Action>),
+  /// `foo.0`
+  TupleIndex(ActionTupleIndex),
+}
+
+impl Action {
+  /// Returns true if the value can be computed without running the program,
+  /// like `1 + 2`, `[1, 2]` or a reference to another constant
+  pub fn is_constant(&self) -> bool {
+    let mut constant = true;
+    self.visit(&mut |action| {
+      constant &= matches!(
+        action,
+        Self::VarRef(_)
+          | Self::StaticString(_)
+          | Self::StaticNumber(_)
+          | Self::StaticBoolean(_)
+          | Self::StaticNull
+          | Self::BinaryOp(_)
+          | Self::Comparison(_)
+          | Self::Logical(_)
+          | Self::UnaryOp(_)
+          | Self::Cast(_)
+          | Self::StructInit(_)
+          | Self::ArrayInit(_)
+          | Self::MapInit(_)
+          | Self::TupleInit(_)
+          | Self::Range(_)
+          | Self::NullCoalesce(_)
+          | Self::NullAssert(_)
+          | Self::FieldAccess(_)
+          | Self::Index(_)
+          | Self::TupleIndex(_)
+      );
+    });
+    constant
+  }
+  /// Calls f for this action and every action nested inside of it
+  /// Block can be nested very deep (like long member chains) so this doesn't use recursion
+  pub fn visit<'a>(&'a self, f: &mut dyn FnMut(&'a Action)) {
+    self.visit_spanned(Span::default(), &mut |action, _| f(action));
+  }
+  /// Same as visit but also gives f the span of the action,
+  /// actions without a span of their own get the span of the closest parent that has one
+  pub fn visit_spanned<'a>(&'a self, span: Span, f: &mut dyn FnMut(&'a Action, Span)) {
+    let mut stack = vec![(self, span)];
+    while let Some((action, span)) = stack.pop() {
+      f(action, span);
+      // Reverse the children so they are visited in the order they are written
+      let first_child = stack.len();
+      action.push_children(span, &mut stack);
+      stack[first_child..].reverse();
+    }
+  }
+  /// Pushes the actions directly nested inside of this action together with their span
+  fn push_children<'a>(&'a self, span: Span, stack: &mut Vec<(&'a Action, Span)>) {
+    // Pushes the actions of a block with the span of every action
+    let push_block = |stack: &mut Vec<(&'a Action, Span)>, block: &'a Block| {
+      for (i, action) in block.actions.iter().enumerate() {
+        stack.push((action, block.action_span(i)));
+      }
+    };
+    match self {
+      Self::Variable(meta) => stack.push((&meta.action, meta.action_span)),
+      Self::Destructure(meta) => stack.push((&meta.action, span)),
+      Self::Return(Some(action)) | Self::Break(Some(action)) => stack.push((action, span)),
+      Self::Assigment(meta) => stack.push((&meta.action, span)),
+      Self::FunctionCall(meta) => {
+        for (i, argument) in meta.arguments.iter().enumerate() {
+          stack.push((argument, meta.argument_span(i).unwrap_or(span)));
+        }
+      }
+      Self::For(meta) => {
+        stack.push((&meta.list, span));
+        push_block(stack, &meta.body);
+      }
+      Self::ForClassic(meta) => {
+        stack.push((&meta.init.action, meta.init.action_span));
+        stack.push((&meta.condition, span));
+        stack.push((&meta.update, span));
+        push_block(stack, &meta.body);
+      }
+      Self::While(meta) | Self::DoWhile(meta) => {
+        stack.push((&meta.true_value, span));
+        push_block(stack, &meta.body);
+      }
+      Self::Loop(body) => push_block(stack, body),
+      Self::Try(meta) => {
+        push_block(stack, &meta.body);
+        push_block(stack, &meta.catch_body);
+      }
+      Self::If(meta) => {
+        stack.push((&meta.condition, span));
+        push_block(stack, &meta.body);
+        for (condition, body) in &meta.else_ifs {
+          stack.push((condition, span));
+          push_block(stack, body);
+        }
+        if let Some(body) = &meta.else_body {
+          push_block(stack, body);
+        }
+      }
+      Self::BinaryOp(meta) => {
+        stack.push((&meta.left, span));
+        stack.push((&meta.right, span));
+      }
+      Self::Comparison(meta) => {
+        stack.push((&meta.left, span));
+        stack.push((&meta.right, span));
+      }
+      Self::Logical(meta) => {
+        stack.push((&meta.left, span));
+        stack.push((&meta.right, span));
+      }
+      Self::UnaryOp(meta) => stack.push((&meta.action, span)),
+      Self::Cast(meta) => stack.push((&meta.value, span)),
+      Self::Discard(action) | Self::Defer(action) => stack.push((action, span)),
+      Self::StructInit(meta) => {
+        for (_, value) in &meta.fields {
+          stack.push((value, span));
+        }
+      }
+      Self::ArrayInit(items) | Self::TupleInit(items) => {
+        for item in items {
+          stack.push((item, span));
+        }
+      }
+      Self::MapInit(entries) => {
+        for (key, value) in entries {
+          stack.push((key, span));
+          stack.push((value, span));
+        }
+      }
+      Self::Closure(meta) | Self::FunctionDecl(meta) => push_block(stack, &meta.body),
+      Self::Range(meta) => {
+        stack.push((&meta.start, span));
+        stack.push((&meta.end, span));
+      }
+      Self::Match(meta) => {
+        stack.push((&meta.subject, span));
+        for arm in &meta.arms {
+          push_block(stack, &arm.body);
+        }
+      }
+      Self::FieldAccess(meta) => stack.push((&meta.target, span)),
+      Self::MethodCall(meta) => {
+        stack.push((&meta.target, span));
+        for argument in &meta.arguments {
+          stack.push((argument, span));
+        }
+      }
+      Self::FieldAssigment(meta) => {
+        stack.push((&meta.target, span));
+        stack.push((&meta.action, span));
+      }
+      Self::Index(meta) => {
+        stack.push((&meta.target, span));
+        stack.push((&meta.index, span));
+      }
+      Self::NullCoalesce(meta) => {
+        stack.push((&meta.left, span));
+        stack.push((&meta.right, span));
+      }
+      Self::NullAssert(action) => stack.push((action, span)),
+      Self::TupleIndex(meta) => stack.push((&meta.target, span)),
+      Self::Return(None)
+      | Self::VarRef(_)
+      | Self::StaticString(_)
+      | Self::StaticNumber(_)
+      | Self::StaticBoolean(_)
+      | Self::StaticNull
+      | Self::Break(None)
+      | Self::Continue => {}
+    }
+  }
 }
 
 #[derive(Debug)]
@@ -23,9 +243,9 @@ pub struct ActionAssigment {
   pub action: Box<Action>,
 }
 
-impl Into<Action> for ActionAssigment {
-  fn into(self) -> Action {
-    Action::Assigment(self)
+impl From<ActionAssigment> for Action {
+  fn from(value: ActionAssigment) -> Self {
+    Action::Assigment(value)
   }
 }
 
@@ -33,11 +253,91 @@ impl Into<Action> for ActionAssigment {
 pub struct ActionFunctionCall {
   pub name: String,
   pub arguments: Vec<Action>,
+  /// The span of every argument, so errors about a argument can point at the argument itself
+  pub argument_spans: Vec<Span>,
+}
+
+impl ActionFunctionCall {
+  /// The span of the argument at the index, None if the arguments were changed after parsing
+  pub fn argument_span(&self, index: usize) -> Option<Span> {
+    if self.argument_spans.len() == self.arguments.len() {
+      self.argument_spans.get(index).copied()
+    } else {
+      None
+    }
+  }
+}
+
+impl From<ActionFunctionCall> for Action {
+  fn from(value: ActionFunctionCall) -> Self {
+    Action::FunctionCall(value)
+  }
+}
+
+#[derive(Debug)]
+pub struct ActionFieldAccess {
+  pub target: Box<Action>,
+  pub field: String,
+  /// `foo?.bar` results in null when foo is null instead of accessing the field
+  pub optional: bool,
+}
+
+impl From<ActionFieldAccess> for Action {
+  fn from(value: ActionFieldAccess) -> Self {
+    Action::FieldAccess(value)
+  }
+}
+
+#[derive(Debug)]
+pub struct ActionMethodCall {
+  pub target: Box<Action>,
+  pub name: String,
+  pub arguments: Vec<Action>,
+  /// `foo?.bar()` results in null when foo is null instead of calling the method
+  pub optional: bool,
+}
+
+impl From<ActionMethodCall> for Action {
+  fn from(value: ActionMethodCall) -> Self {
+    Action::MethodCall(value)
+  }
 }
 
-impl Into<Action> for ActionFunctionCall {
-  fn into(self) -> Action {
-    Action::FunctionCall(self)
+#[derive(Debug)]
+pub struct ActionFieldAssigment {
+  /// The value that contains the field, `foo` in `foo.bar = baz`
+  pub target: Box<Action>,
+  pub field: String,
+  pub action: Box<Action>,
+}
+
+impl From<ActionFieldAssigment> for Action {
+  fn from(value: ActionFieldAssigment) -> Self {
+    Action::FieldAssigment(value)
+  }
+}
+
+#[derive(Debug)]
+pub struct ActionIndex {
+  pub target: Box<Action>,
+  pub index: Box<Action>,
+}
+
+impl From<ActionIndex> for Action {
+  fn from(value: ActionIndex) -> Self {
+    Action::Index(value)
+  }
+}
+
+#[derive(Debug)]
+pub struct ActionTupleIndex {
+  pub target: Box<Action>,
+  pub index: usize,
+}
+
+impl From<ActionTupleIndex> for Action {
+  fn from(value: ActionTupleIndex) -> Self {
+    Action::TupleIndex(value)
   }
 }
 
@@ -52,21 +352,25 @@ pub enum ParseActionState {
   Assigment(ParseActionStateAssigment),       // foo = bar
   FunctionCall(ParseActionStateFunctionCall), // foo(bar)
   VarRef(String),                             // foo
-  Break,
+  Break(Option<Action>),                      // break foo
   Continue,
   For(ActionFor),
+  ForClassic(ActionForClassic),
   While(ActionWhile),
-  Loop(Actions),
+  DoWhile(ActionWhile),
+  Loop(Block),
+  If(ActionIf),
 }
 
 pub struct ParseActionStateFunctionCall {
   name: String,
   arguments: Vec<Action>,
+  argument_spans: Vec<Span>,
 }
 
-impl Into<ParseActionState> for ParseActionStateFunctionCall {
-  fn into(self) -> ParseActionState {
-    ParseActionState::FunctionCall(self)
+impl From<ParseActionStateFunctionCall> for ParseActionState {
+  fn from(value: ParseActionStateFunctionCall) -> Self {
+    ParseActionState::FunctionCall(value)
   }
 }
 
@@ -75,23 +379,23 @@ pub struct ParseActionStateAssigment {
   action: Option<Action>,
 }
 
-impl Into<ParseActionState> for ParseActionStateAssigment {
-  fn into(self) -> ParseActionState {
-    ParseActionState::Assigment(self)
+impl From<ParseActionStateAssigment> for ParseActionState {
+  fn from(value: ParseActionStateAssigment) -> Self {
+    ParseActionState::Assigment(value)
   }
 }
 
 pub struct ParseActionStateReturn {
-  action: Option<Action>, // The value to return
+  actions: Vec<Action>, // The values to return, `return a, b` returns the tuple `(a, b)`
 }
 
-impl Into<ParseActionState> for ParseActionStateReturn {
-  fn into(self) -> ParseActionState {
-    ParseActionState::Return(self)
+impl From<ParseActionStateReturn> for ParseActionState {
+  fn from(value: ParseActionStateReturn) -> Self {
+    ParseActionState::Return(value)
   }
 }
 
-#[derive(PartialEq)]
+#[derive(PartialEq, Clone, Copy)]
 pub enum ActionToExpect {
   /// A line in a function body
   ActionInBody,
@@ -110,10 +414,13 @@ enum DetectedAction {
   Assignment,
   /// 3. functions `foo()`
   Function,
-  // 4. inline strings `"foo"`
-  // 5. inline numbers `1`
-  // 6. inline arrays `[foo, bar]`
-  // 7. inline structs `foo{bar: baz}`
+  /// 4. inline structs `foo{bar: baz}`
+  StructInit,
+  /// 5. `foo += bar`
+  CompoundAssignment(Operator),
+  // 5. inline strings `"foo"`
+  // 6. inline numbers `1`
+  // 7. inline arrays `[foo, bar]`
 }
 
 enum LoopType {
@@ -122,11 +429,11 @@ enum LoopType {
   Loop,
 }
 
-impl Into<LoopType> for Keywords {
-  fn into(self) -> LoopType {
-    match self {
-      Self::For => LoopType::For,
-      Self::While => LoopType::While,
+impl From<Keywords> for LoopType {
+  fn from(value: Keywords) -> Self {
+    match value {
+      Keywords::For => LoopType::For,
+      Keywords::While => LoopType::While,
       _ => LoopType::Loop,
     }
   }
@@ -134,26 +441,76 @@ impl Into<LoopType> for Keywords {
 
 #[derive(Debug)]
 pub struct ActionWhile {
-  actions: Actions,
-  true_value: Box<Action>,
+  pub body: Block,
+  pub true_value: Box<Action>,
+}
+
+impl From<ActionWhile> for Action {
+  fn from(value: ActionWhile) -> Self {
+    Action::While(value)
+  }
 }
 
-impl Into<Action> for ActionWhile {
-  fn into(self) -> Action {
-    Action::While(self)
+/// `try {} catch err {}`, the catch body runs when something inside of the try body fails
+#[derive(Debug)]
+pub struct ActionTry {
+  pub body: Block,
+  /// The `err` of `catch err {}`, the error is not given a name with `catch {}`
+  pub error_name: Option<String>,
+  pub catch_body: Block,
+}
+
+impl From<ActionTry> for Action {
+  fn from(value: ActionTry) -> Self {
+    Action::Try(value)
   }
 }
 
 #[derive(Debug)]
 pub struct ActionFor {
-  actions: Actions,
-  list: Box<Action>,
-  item_name: String,
+  pub body: Block,
+  pub list: Box<Action>,
+  pub item_name: String,
+  /// The `i` of `for i, item in items`
+  pub index_name: Option<String>,
+}
+
+impl From<ActionFor> for Action {
+  fn from(value: ActionFor) -> Self {
+    Action::For(value)
+  }
+}
+
+/// A C like for loop, `for let i = 0; i < 10; i += 1 {}`
+#[derive(Debug)]
+pub struct ActionForClassic {
+  /// The `let i = 0` part that runs before the loop
+  pub init: Variable,
+  /// The loop continues while this is true
+  pub condition: Box<Action>,
+  /// The `i += 1` part that runs after every iteration
+  pub update: Box<Action>,
+  pub body: Block,
 }
 
-impl Into<Action> for ActionFor {
-  fn into(self) -> Action {
-    Action::For(self)
+impl From<ActionForClassic> for Action {
+  fn from(value: ActionForClassic) -> Self {
+    Action::ForClassic(value)
+  }
+}
+
+#[derive(Debug)]
+pub struct ActionIf {
+  pub condition: Box<Action>,
+  pub body: Block,
+  /// The `else if` conditions with their bodies
+  pub else_ifs: Vec<(Action, Block)>,
+  pub else_body: Option<Block>,
+}
+
+impl From<ActionIf> for Action {
+  fn from(value: ActionIf) -> Self {
+    Action::If(value)
   }
 }
 
@@ -162,60 +519,380 @@ impl<'a> ParseAction<'a> {
     p: &'a mut Parser,
     go_back_one: bool,
     action_to_expect: ActionToExpect,
-  ) -> Result<Action, ParsingError> {
+  ) -> Result<Action, Box<ParsingError>> {
     if go_back_one {
       p.index -= 1;
     }
+    let start = p.index;
+    let mut res = ParseAction::start_operand(p, action_to_expect)?;
+    match action_to_expect {
+      ActionToExpect::Assignment(_) => {
+        res = ParseAction::parse_operators(p, res, start, 0, action_to_expect)?;
+      }
+      ActionToExpect::ActionInBody => match res {
+        // Only actions that do something are allowed as a line in a body
+        Action::VarRef(_)
+        | Action::StaticString(_)
+        | Action::StaticNumber(_)
+        | Action::StaticBoolean(_)
+        | Action::StaticNull
+        | Action::UnaryOp(_)
+        | Action::Cast(_)
+        | Action::ArrayInit(_)
+        | Action::Closure(_)
+        | Action::FieldAccess(_)
+        | Action::Index(_)
+        | Action::TupleInit(_)
+        | Action::TupleIndex(_)
+        | Action::NullAssert(_) => {
+          return p.error(ParsingErrorType::Custom(
+            "Unused value, use `_ = value` to discard it",
+          ))
+        }
+        _ => {}
+      },
+    }
+    p.trace_consumed("action", start);
+    Ok(res)
+  }
+  /// Parses a single action without the operators that might come after it
+  fn start_operand(
+    p: &'a mut Parser,
+    action_to_expect: ActionToExpect,
+  ) -> Result<Action, Box<ParsingError>> {
+    p.use_memory(core::mem::size_of::<Action>())?;
     let mut s = Self {
       action_to_expect,
       p,
       res: None,
     };
     s.detect()?;
-    if let Some(res) = s.res {
-      Ok(res)
-    } else {
-      s.p.error(ParsingErrorType::UnexpectedResult)
+    match s.res.take() {
+      Some(res) => s.finish_operand(res),
+      None => s.p.error(ParsingErrorType::UnexpectedResult),
     }
   }
-  fn commit_state(&mut self, state: impl Into<ParseActionState>) -> Result<(), ParsingError> {
-    self.res = Some(match state.into() {
-      ParseActionState::Return(meta) => {
-        let mut return_action: Option<Box<Action>> = None;
-        if let Some(action) = meta.action {
-          return_action = Some(Box::new(action));
+  /// Parses the postfixes and casts after the value of a operand
+  fn finish_operand(&mut self, res: Action) -> Result<Action, Box<ParsingError>> {
+    let res = self.parse_postfix(res)?;
+    let res = self.parse_casts(res)?;
+    match res {
+      Action::FieldAccess(meta) if self.action_to_expect == ActionToExpect::ActionInBody => {
+        self.parse_field_assignment(meta)
+      }
+      res => Ok(res),
+    }
+  }
+  /// Parses the casts after a value like the `as int` in `foo as int`,
+  /// a cast is applied before the operators so `a as int + 1` is `(a as int) + 1`
+  fn parse_casts(&mut self, mut value: Action) -> Result<Action, Box<ParsingError>> {
+    loop {
+      // Like operators the cast must be on the same line as the value
+      let before_cast = self.p.index;
+      match self.p.next_while(" \t") {
+        Some('a') => self.p.index -= 1,
+        _ => {
+          self.p.index = before_cast;
+          return Ok(value);
+        }
+      }
+      if self.p.try_match(&[(Keywords::As, " \t\n")]).is_none() {
+        self.p.index = before_cast;
+        return Ok(value);
+      }
+
+      let target_type = ParseType::start(self.p, false)?;
+      if target_type.name.is_empty() {
+        return self
+          .p
+          .error(ParsingErrorType::Custom("Missing type after as"));
+      }
+      // The type stops at operator chars so `a as int+1` is also `(a as int) + 1`
+      value = ActionCast {
+        value: Box::new(value),
+        target_type,
+      }
+      .into();
+    }
+  }
+  /// Parses the field accesses, method calls, indexes and null assertions after a value,
+  /// like the `.bar()?.baz[0]!` in `foo.bar()?.baz[0]!`
+  fn parse_postfix(&mut self, mut target: Action) -> Result<Action, Box<ParsingError>> {
+    match target {
+      Action::Variable(_)
+      | Action::Destructure(_)
+      | Action::Return(_)
+      | Action::Assigment(_)
+      | Action::FieldAssigment(_)
+      | Action::Discard(_)
+      | Action::Break(_)
+      | Action::Continue
+      | Action::Defer(_)
+      | Action::For(_)
+      | Action::ForClassic(_)
+      | Action::While(_)
+      | Action::DoWhile(_)
+      | Action::Try(_)
+      | Action::Loop(_)
+      | Action::If(_)
+      | Action::FunctionDecl(_) => return Ok(target),
+      _ => {}
+    }
+
+    loop {
+      let mut optional = false;
+      match self.p.seek_next_char() {
+        Some('.') if self.p.contents.get(self.p.index + 1) != Some(&b'.') => self.p.index += 1,
+        Some('?') if self.optional_chain_is_next() => {
+          self.p.index += 2;
+          optional = true;
+        }
+        Some('[') => {
+          self.p.index += 1;
+          target = self.parse_index(target)?;
+          continue;
+        }
+        Some('!') if self.p.contents.get(self.p.index + 1) != Some(&b'=') => {
+          // This is a null assertion and not the start of `!=`
+          self.p.index += 1;
+          target = Action::NullAssert(Box::new(target));
+          continue;
+        }
+        _ => return Ok(target),
+      }
+
+      if let Some(c) = self.p.seek_next_char() {
+        if c.is_ascii_digit() {
+          if optional {
+            return self
+              .p
+              .error(ParsingErrorType::Custom("A tuple index can't be optional"));
+          }
+          if let Action::StaticNumber(_) = target {
+            // A number with too many dots like `1.2.3`
+            return self.p.error(ParsingErrorType::Custom("Invalid number"));
+          }
+          // This is a tuple index like `foo.0`
+          target = ActionTupleIndex {
+            target: Box::new(target),
+            index: self.parse_tuple_index()?,
+          }
+          .into();
+          continue;
+        }
+      }
+
+      let name = self.p.next_ident()?.name;
+      if name.is_empty() {
+        return self.p.error(ParsingErrorType::Custom("Missing field name"));
+      }
+
+      target = if let Some('(') = self.p.seek_next_char() {
+        let call = self.parse_function(name, true)?;
+        ActionMethodCall {
+          target: Box::new(target),
+          name: call.name,
+          arguments: call.arguments,
+          optional,
         }
+        .into()
+      } else {
+        ActionFieldAccess {
+          target: Box::new(target),
+          field: name,
+          optional,
+        }
+        .into()
+      };
+    }
+  }
+  /// Returns true if the `?.` of a optional chain like `foo?.bar` starts at the current index
+  fn optional_chain_is_next(&self) -> bool {
+    let contents = &self.p.contents;
+    contents.get(self.p.index) == Some(&b'?')
+      && contents.get(self.p.index + 1) == Some(&b'.')
+      && contents.get(self.p.index + 2) != Some(&b'.')
+  }
+  /// Parses the `bar]` part of `foo[bar]`
+  fn parse_index(&mut self, target: Action) -> Result<Action, Box<ParsingError>> {
+    if self.p.next_while(" \t\n").is_none() {
+      return self.p.unexpected_eof();
+    }
+    let index = ParseAction::start(self.p, true, ActionToExpect::Assignment("]"))?;
+    match self.p.next_while(" \t\n") {
+      Some(']') => {}
+      Some(c) => return self.p.unexpected_char(c),
+      None => return self.p.unexpected_eof(),
+    }
+    Ok(
+      ActionIndex {
+        target: Box::new(target),
+        index: Box::new(index),
+      }
+      .into(),
+    )
+  }
+  /// Returns true if c is the first char of a number like the `1` in `1.5` or the `.` in `.5`
+  fn number_starts_with(&mut self, c: char) -> bool {
+    c.is_ascii_digit() || c == '.' && self.p.seek_next_char().is_some_and(|c| c.is_ascii_digit())
+  }
+  /// Parses the `0` part of `foo.0`
+  fn parse_tuple_index(&mut self) -> Result<usize, Box<ParsingError>> {
+    let mut index = String::new();
+    while let Some(c) = self.p.next_char() {
+      if !legal_name_char(c) {
+        self.p.index -= 1;
+        break;
+      }
+      index.push(c);
+    }
+    match index.parse() {
+      Ok(index) => Ok(index),
+      Err(_) => self
+        .p
+        .error(ParsingErrorType::Custom("Invalid tuple index")),
+    }
+  }
+  /// Parses the ` = baz` part of `foo.bar = baz`
+  fn parse_field_assignment(
+    &mut self,
+    field: ActionFieldAccess,
+  ) -> Result<Action, Box<ParsingError>> {
+    match self.p.next_while(" \t") {
+      Some('=') if self.p.seek_next_char() != Some('=') && field.optional => {
+        return self.p.error(ParsingErrorType::Custom(
+          "Can't assign to a optional field like foo?.bar",
+        ));
+      }
+      Some('=') if self.p.seek_next_char() != Some('=') => {}
+      Some(_) => {
+        self.p.index -= 1;
+        return self.p.error(ParsingErrorType::Custom(
+          "Unused value, use `_ = value` to discard it",
+        ));
+      }
+      None => return self.p.unexpected_eof(),
+    }
+
+    if self.p.next_while(" \t\n").is_none() {
+      return self.p.unexpected_eof();
+    }
+    let action = ParseAction::start(self.p, true, ActionToExpect::Assignment(""))?;
+    Ok(
+      ActionFieldAssigment {
+        target: field.target,
+        field: field.field,
+        action: Box::new(action),
+      }
+      .into(),
+    )
+  }
+  /// Parses the operators after the left side, like the `+ b * 2` in `a + b * 2`
+  /// Only operators with at least the min precedence are parsed
+  /// The left start is the index the left side starts at, this is used for the span of a piped value
+  fn parse_operators(
+    p: &mut Parser,
+    mut left: Action,
+    left_start: usize,
+    min_precedence: u8,
+    action_to_expect: ActionToExpect,
+  ) -> Result<Action, Box<ParsingError>> {
+    let mut prev_operator: Option<BinaryOperator> = None;
+    loop {
+      let before_operator = p.index;
+      let operator = match p.match_operator() {
+        Some(operator) if operator.precedence() >= min_precedence => operator,
+        _ => {
+          p.index = before_operator;
+          return Ok(left);
+        }
+      };
+      // `a < b < c` would compare the result of `a < b` with c, this is almost always a mistake
+      // The left side can still be a comparison inside of parentheses like `(a == b) == c`
+      if let (Some(BinaryOperator::Comparison(_)), BinaryOperator::Comparison(_)) =
+        (prev_operator, operator)
+      {
+        return p.error(ParsingErrorType::Custom("Comparisons can't be chained"));
+      }
+      prev_operator = Some(operator);
+
+      let left_span = p.trimmed_span(left_start, before_operator);
+      p.skip_whitespace();
+      if p.seek_next_char().is_none() {
+        return p.unexpected_eof();
+      }
+      let right_start = p.index;
+      let mut right = ParseAction::start_operand(p, action_to_expect)?;
+
+      // Operators with a higher precedence on the right side are applied first
+      loop {
+        let before_next_operator = p.index;
+        let next_operator = p.match_operator();
+        p.index = before_next_operator;
+        match next_operator {
+          Some(next) if next.precedence() > operator.precedence() => {
+            right = ParseAction::parse_operators(
+              p,
+              right,
+              right_start,
+              next.precedence(),
+              action_to_expect,
+            )?;
+          }
+          _ => break,
+        }
+      }
+
+      left = operator.into_action(p, left, left_span, right)?;
+    }
+  }
+  fn commit_state(&mut self, state: impl Into<ParseActionState>) -> Result<(), Box<ParsingError>> {
+    self.res = Some(match state.into() {
+      ParseActionState::Return(mut meta) => {
+        let return_action = match meta.actions.len() {
+          0 => None,
+          1 => Some(Box::new(meta.actions.remove(0))),
+          _ => Some(Box::new(Action::TupleInit(meta.actions))),
+        };
         Action::Return(return_action)
       }
       ParseActionState::Assigment(meta) => {
-        if let None = meta.action {
+        if meta.action.is_none() {
           return self
             .p
             .error(ParsingErrorType::Custom("Missing variable assignment"));
         }
 
-        ActionAssigment {
-          name: meta.name,
-          action: Box::new(meta.action.unwrap()),
+        if meta.name == "_" {
+          Action::Discard(Box::new(meta.action.unwrap()))
+        } else {
+          ActionAssigment {
+            name: meta.name,
+            action: Box::new(meta.action.unwrap()),
+          }
+          .into()
         }
-        .into()
       }
       ParseActionState::FunctionCall(meta) => ActionFunctionCall {
         name: meta.name,
         arguments: meta.arguments,
+        argument_spans: meta.argument_spans,
       }
       .into(),
       ParseActionState::VarRef(name) => Action::VarRef(name),
-      ParseActionState::Break => Action::Break,
+      ParseActionState::Break(action) => Action::Break(action.map(Box::new)),
       ParseActionState::Continue => Action::Continue,
       ParseActionState::While(meta) => meta.into(),
+      ParseActionState::DoWhile(meta) => Action::DoWhile(meta),
       ParseActionState::For(meta) => meta.into(),
+      ParseActionState::ForClassic(meta) => meta.into(),
       ParseActionState::Loop(actions) => Action::Loop(actions),
+      ParseActionState::If(meta) => meta.into(),
     });
     Ok(())
   }
 
-  fn detect(&mut self) -> Result<(), ParsingError> {
+  fn detect(&mut self) -> Result<(), Box<ParsingError>> {
     let matched_res = if self.action_to_expect == ActionToExpect::ActionInBody {
       self.p.try_match(&[
         (Keywords::Const, " \t\n"),
@@ -223,12 +900,26 @@ impl<'a> ParseAction<'a> {
         (Keywords::Return, "} \t\n"),
         (Keywords::Loop, "{ \t\n"),
         (Keywords::While, " \t\n"),
+        (Keywords::Do, "{ \t\n"),
+        (Keywords::Try, "{ \t\n"),
         (Keywords::For, "} \t\n"),
         (Keywords::Break, "} \t\n"),
+        (Keywords::Continue, "} \t\n"),
+        (Keywords::Defer, " \t\n"),
+        (Keywords::If, " \t\n"),
+        (Keywords::Match, " \t\n"),
+        (Keywords::Fn, " \t\n"),
       ])
     } else {
-      // Matching keywords is only allowed when inside the body
-      None
+      // Most keywords can only be matched when inside the body,
+      // if, match, loop and fn can also be used as a value
+      self.p.skip_whitespace();
+      self.p.try_match(&[
+        (Keywords::If, " \t\n"),
+        (Keywords::Match, " \t\n"),
+        (Keywords::Loop, "{ \t\n"),
+        (Keywords::Fn, "( \t\n"),
+      ])
     };
 
     // Try to match a keyword and react to it
@@ -241,8 +932,11 @@ impl<'a> ParseAction<'a> {
           } else {
             VarType::Let
           };
-          let new_var = parse_var(self.p, Some(var_type))?;
-          self.res = Some(new_var.into());
+          self.res = Some(if is_destructure(self.p) {
+            parse_destructure(self.p, var_type)?.into()
+          } else {
+            parse_var(self.p, Some(var_type))?.into()
+          });
         }
         Keywords::Return => {
           // Go to parsing the return
@@ -254,11 +948,56 @@ impl<'a> ParseAction<'a> {
           let to_commit = self.parse_looper(matched.into())?;
           self.commit_state(to_commit)?;
         }
-        Keywords::Break => self.commit_state(ParseActionState::Break)?,
+        Keywords::Do => {
+          let to_commit = self.parse_do_while()?;
+          self.commit_state(to_commit)?;
+        }
+        Keywords::Try => {
+          let parsed = self.parse_try()?;
+          self.res = Some(parsed.into());
+        }
+        Keywords::If => {
+          // Parse the if statement, or the if expression like `if a { 1 } else { 2 }`
+          let as_value = self.action_to_expect != ActionToExpect::ActionInBody;
+          let to_commit = self.parse_if(as_value)?;
+          self.commit_state(to_commit)?;
+        }
+        Keywords::Match => {
+          let parsed = parse_match(self.p)?;
+          self.res = Some(parsed.into());
+        }
+        Keywords::Break => {
+          let to_commit = self.parse_break()?;
+          self.commit_state(ParseActionState::Break(to_commit))?;
+        }
         Keywords::Continue => self.commit_state(ParseActionState::Continue)?,
-        Keywords::Fn | Keywords::Struct | Keywords::Enum | Keywords::Type => {
-          return self.p.error(ParsingErrorType::UnexpectedResult)
+        Keywords::Defer => {
+          let action = self.parse_defer()?;
+          self.res = Some(Action::Defer(Box::new(action)));
         }
+        Keywords::Fn if self.action_to_expect == ActionToExpect::ActionInBody => {
+          // A function declared inside of this function
+          let function = ParseFunction::start(self.p)?;
+          self.res = Some(Action::FunctionDecl(function));
+        }
+        Keywords::Fn => {
+          // A function without a name used as a value like `fn(a int) {}`
+          let function = ParseFunction::start_anonymous(self.p)?;
+          self.res = Some(Action::Closure(function));
+        }
+        Keywords::Struct
+        | Keywords::Enum
+        | Keywords::Impl
+        | Keywords::Import
+        | Keywords::Pub
+        | Keywords::Trait
+        | Keywords::Type
+        | Keywords::Else
+        | Keywords::Catch
+        | Keywords::As
+        | Keywords::True
+        | Keywords::False
+        | Keywords::Null => return self.p.error(ParsingErrorType::UnexpectedResult),
       }
       return Ok(());
     }
@@ -271,43 +1010,174 @@ impl<'a> ParseAction<'a> {
     // 5. inline arrays `[foo, bar]`
     // 6. inline structs `foo{bar: baz}`
     //
-    // The code underhere will detect what the action is,
-    // TODO: 2, 3, 4, 5, 6
-    let mut name = NameBuilder::new();
+    // The code underhere will detect what the action is
+    let mut name: Option<Ident> = None;
     let mut detected_action = DetectedAction::VarRefName;
     let mut name_completed = false;
 
     while let Some(c) = self.p.next_char() {
       match c {
-        '"' if name.len() == 0 => {
+        '"' if name.is_none() => {
           // Parse a static string
           let parsed = parse_static_str(self.p)?;
           self.res = Some(parsed.into());
           return Ok(());
         }
+        '`' if name.is_none() => {
+          // Parse a raw string
+          let parsed = parse_raw_str(self.p)?;
+          self.res = Some(parsed.into());
+          return Ok(());
+        }
         ' ' | '\t' | '\n' => {
-          if name.len() > 0 {
+          if name.is_some() {
             name_completed = true;
           }
           // Else ignore this
         }
+        '(' if name.is_none() => {
+          // Parse a action between parentheses like `(a + b)` or a tuple like `(a, b)`
+          let mut items = vec![];
+          loop {
+            items.push(ParseAction::start(
+              self.p,
+              false,
+              ActionToExpect::Assignment(",)"),
+            )?);
+            match self.p.next_while(" \t\n") {
+              Some(',') => {}
+              Some(')') => break,
+              Some(c) => return self.p.unexpected_char(c),
+              None => return self.p.unexpected_eof(),
+            }
+          }
+          self.res = Some(if items.len() == 1 {
+            items.remove(0)
+          } else {
+            Action::TupleInit(items)
+          });
+          return Ok(());
+        }
         '(' => {
           // Detected start of a function call
           detected_action = DetectedAction::Function;
           break;
         }
+        '[' if name.is_none() => {
+          // Parse a inline array like `[foo, bar]`
+          let items = self.parse_array_init()?;
+          self.res = Some(Action::ArrayInit(items));
+          return Ok(());
+        }
+        '{' if name.is_none() && self.struct_init_allowed() => {
+          // Parse a inline map like `{"foo": bar}`
+          let entries = self.parse_map_init()?;
+          self.res = Some(Action::MapInit(entries));
+          return Ok(());
+        }
+        '|' if name.is_none() => {
+          // Parse a closure like `|x| x + 1`
+          let closure = self.parse_closure()?;
+          self.res = Some(Action::Closure(closure));
+          return Ok(());
+        }
+        '!' | '-' if name.is_none() => {
+          // Parse a unary operator like `!foo` or `-foo`
+          let operator = if c == '!' {
+            UnaryOperator::Not
+          } else {
+            UnaryOperator::Negate
+          };
+          let action = match self.p.seek_next_char() {
+            Some(next) if c == '-' && next.is_ascii_digit() => {
+              // The range of a number like `-128i8` is checked after the negation
+              let number = NumberParser::start(self.p)?.negated().result()?;
+              let mut operand = ParseAction {
+                action_to_expect: self.action_to_expect,
+                p: self.p,
+                res: None,
+              };
+              operand.finish_operand(number.into())?
+            }
+            _ => ParseAction::start_operand(self.p, self.action_to_expect)?,
+          };
+          self.res = Some(
+            ActionUnaryOp {
+              operator,
+              action: Box::new(action),
+            }
+            .into(),
+          );
+          return Ok(());
+        }
+        '{' if name.is_some() && self.struct_init_allowed() => {
+          // Detected the start of a inline struct
+          detected_action = DetectedAction::StructInit;
+          break;
+        }
+        '=' if name.is_some() && matches!(self.p.seek_next_char(), Some('=') | Some('>')) => {
+          // This is the end of the left side of a comparison like `a==b` or a match pattern like `a=>`
+          self.p.index -= 1;
+          break;
+        }
         '=' => {
           // Detected variable assigment
           detected_action = DetectedAction::Assignment;
           break;
         }
-        _ if (legal_name_char(c) || c == '.') && !name_completed => name.push(c),
+        '+' | '-' | '*' | '/' | '%' if name.is_some() && self.p.seek_next_char() == Some('=') => {
+          // Detected a compound assignment like `foo += bar`
+          self.p.index += 1;
+          let operator = match c {
+            '+' => Operator::Add,
+            '-' => Operator::Subtract,
+            '*' => Operator::Multiply,
+            '/' => Operator::Divide,
+            _ => Operator::Remainder,
+          };
+          detected_action = DetectedAction::CompoundAssignment(operator);
+          break;
+        }
+        '.' if name.is_some() && self.p.seek_next_char() == Some('.') => {
+          // This is the start of a range like `0..10`
+          self.p.index -= 1;
+          break;
+        }
+        _ if name.is_none() && self.number_starts_with(c) => {
+          // Parse a number like `1`, `1.5`, `.5` or `0xff`
+          self.p.index -= 1;
+          let number = NumberParser::start(self.p)?.result()?;
+          self.res = Some(number.into());
+          return Ok(());
+        }
+        '.' | '[' | '!' if name.is_some() => {
+          // This is the start of a postfix like `foo.bar()`, `foo[0]` or `foo!`
+          self.p.index -= 1;
+          break;
+        }
+        '?' if name.is_some() && self.p.seek_next_char() == Some('.') => {
+          // This is the start of a optional chain like `foo?.bar`
+          self.p.index -= 1;
+          break;
+        }
+        _ if legal_name_char(c) && name.is_none() => {
+          self.p.index -= 1;
+          name = Some(self.p.next_ident()?);
+        }
         c => {
           if name_completed {
             self.p.index -= 1;
             break;
           }
 
+          if let ActionToExpect::Assignment(_) = self.action_to_expect {
+            if name.is_some() && OPERATOR_CHARS.contains(c) {
+              // This is the end of the left side of a operator like `a+b`
+              self.p.index -= 1;
+              break;
+            }
+          }
+
           if let ActionToExpect::Assignment(valid_unexpted_chars) = self.action_to_expect {
             if valid_unexpted_chars.contains(c) {
               self.p.index -= 1;
@@ -319,14 +1189,29 @@ impl<'a> ParseAction<'a> {
       }
     }
 
-    if let Some(number_parser) = name.is_number(self.p) {
-      // The defined name is actually a number
-      let number = number_parser.result(NumberTypes::Auto)?;
-      self.res = Some(number.into());
-      return Ok(());
-    }
+    let name_string = match name {
+      Some(name) => name.name,
+      // There is nothing here that can be a value, for example the condition in `if {}`
+      None => return self.p.error(ParsingErrorType::Custom("Missing value")),
+    };
 
-    let name_string = name.to_string(self.p)?;
+    // `true`, `false` and `null` are keywords and can't be used as names
+    let true_keyword: &str = Keywords::True.into();
+    let false_keyword: &str = Keywords::False.into();
+    let null_keyword: &str = Keywords::Null.into();
+    if name_string == true_keyword || name_string == false_keyword || name_string == null_keyword {
+      if let DetectedAction::VarRefName = detected_action {
+        self.res = Some(if name_string == null_keyword {
+          Action::StaticNull
+        } else {
+          Action::StaticBoolean(name_string == true_keyword)
+        });
+        return Ok(());
+      }
+      return self.p.error(ParsingErrorType::Custom(
+        "true, false and null are keywords and can't be used as a name",
+      ));
+    }
 
     // Do things relative to the detected action
     match detected_action {
@@ -334,24 +1219,205 @@ impl<'a> ParseAction<'a> {
         self.commit_state(ParseActionState::VarRef(name_string))?;
       }
       DetectedAction::Assignment => {
-        let res = self.parse_var_assignment(name_string, true)?;
+        let res = self.parse_var_assignment(name_string, false)?;
+        self.commit_state(res)?;
+      }
+      DetectedAction::CompoundAssignment(operator) => {
+        if name_string == "_" {
+          return self.p.error(ParsingErrorType::Custom(
+            "Can't use `_` in a compound assignment",
+          ));
+        }
+        // `foo += bar` is desugared into `foo = foo + bar`
+        let mut res = self.parse_var_assignment(name_string.clone(), false)?;
+        res.action = res.action.map(|action| {
+          ActionBinaryOp {
+            operator,
+            left: Box::new(Action::VarRef(name_string)),
+            right: Box::new(action),
+          }
+          .into()
+        });
         self.commit_state(res)?;
       }
       DetectedAction::Function => {
         let res = self.parse_function(name_string, false)?;
         self.commit_state(res)?;
       }
+      DetectedAction::StructInit => {
+        let res = self.parse_struct_init(name_string)?;
+        self.res = Some(res.into());
+      }
+    };
+    Ok(())
+  }
+  /// Inline structs and maps are not allowed if the action ends with a `{`,
+  /// for example `if foo {}` should not parse `foo {}` as a struct
+  fn struct_init_allowed(&self) -> bool {
+    match self.action_to_expect {
+      ActionToExpect::Assignment(valid_unexpted_chars) => !valid_unexpted_chars.contains('{'),
+      ActionToExpect::ActionInBody => false,
+    }
+  }
+  /// Parses the `bar: baz}` part of `foo{bar: baz}`
+  fn parse_struct_init(&mut self, name: String) -> Result<ActionStructInit, Box<ParsingError>> {
+    let mut res = ActionStructInit {
+      name,
+      fields: vec![],
+    };
+
+    loop {
+      match self.p.next_while(" \t\n") {
+        Some('}') => return Ok(res),
+        Some(c) if legal_name_char(c) => self.p.index -= 1,
+        Some(c) => return self.p.unexpected_char(c),
+        None => return self.p.unexpected_eof(),
+      }
+
+      let field_name = self.p.next_ident()?.name;
+      if res.fields.iter().any(|(name, _)| *name == field_name) {
+        return self
+          .p
+          .error(ParsingErrorType::Custom("Duplicate field name"));
+      }
+
+      match self.p.next_while(" \t\n") {
+        Some(':') => {}
+        Some(c) => return self.p.unexpected_char(c),
+        None => return self.p.unexpected_eof(),
+      }
+      if self.p.next_while(" \t\n").is_none() {
+        return self.p.unexpected_eof();
+      }
+      let value = ParseAction::start(self.p, true, ActionToExpect::Assignment(",}"))?;
+      res.fields.push((field_name, value));
+
+      match self.p.next_while(" \t\n") {
+        Some(',') => {}
+        Some('}') => return Ok(res),
+        Some(c) => return self.p.unexpected_char(c),
+        None => return self.p.unexpected_eof(),
+      }
+    }
+  }
+  /// Parses the `foo, bar]` part of `[foo, bar]`
+  fn parse_array_init(&mut self) -> Result<Vec<Action>, Box<ParsingError>> {
+    let mut res = vec![];
+
+    loop {
+      match self.p.next_while(" \t\n") {
+        Some(']') => return Ok(res),
+        Some(_) => {}
+        None => return self.p.unexpected_eof(),
+      }
+
+      let item = ParseAction::start(self.p, true, ActionToExpect::Assignment(",]"))?;
+      res.push(item);
+
+      match self.p.next_while(" \t\n") {
+        Some(',') => {}
+        Some(']') => return Ok(res),
+        Some(c) => return self.p.unexpected_char(c),
+        None => return self.p.unexpected_eof(),
+      }
+    }
+  }
+  /// Parses the `"foo": bar}` part of `{"foo": bar}`
+  fn parse_map_init(&mut self) -> Result<Vec<(Action, Action)>, Box<ParsingError>> {
+    let mut res = vec![];
+
+    loop {
+      match self.p.next_while(" \t\n") {
+        Some('}') => return Ok(res),
+        Some(_) => {}
+        None => return self.p.unexpected_eof(),
+      }
+
+      let key = ParseAction::start(self.p, true, ActionToExpect::Assignment(":"))?;
+      match self.p.next_while(" \t\n") {
+        Some(':') => {}
+        Some(c) => return self.p.unexpected_char(c),
+        None => return self.p.unexpected_eof(),
+      }
+      if self.p.next_while(" \t\n").is_none() {
+        return self.p.unexpected_eof();
+      }
+      let value = ParseAction::start(self.p, true, ActionToExpect::Assignment(",}"))?;
+      res.push((key, value));
+
+      match self.p.next_while(" \t\n") {
+        Some(',') => {}
+        Some('}') => return Ok(res),
+        Some(c) => return self.p.unexpected_char(c),
+        None => return self.p.unexpected_eof(),
+      }
+    }
+  }
+  /// Parses the `x, y| x + y` part of `|x, y| x + y`
+  /// A closure without a block as body returns the value of its expression
+  fn parse_closure(&mut self) -> Result<Function, Box<ParsingError>> {
+    let mut args = vec![];
+
+    loop {
+      match self.p.next_while(" \t\n") {
+        Some('|') if args.is_empty() => break,
+        Some(c) if legal_name_char(c) => self.p.index -= 1,
+        Some(c) => return self.p.unexpected_char(c),
+        None => return self.p.unexpected_eof(),
+      }
+
+      let arg_name = self.p.next_ident()?.name;
+      if args.iter().any(|(name, _)| *name == arg_name) {
+        return self
+          .p
+          .error(ParsingErrorType::Custom("Duplicate argument name"));
+      }
+      args.push((arg_name, Type::inferred()));
+      self
+        .p
+        .check_limit(self.p.options.max_arguments, args.len(), "arguments count")?;
+
+      match self.p.next_while(" \t\n") {
+        Some(',') => {}
+        Some('|') => break,
+        Some(c) => return self.p.unexpected_char(c),
+        None => return self.p.unexpected_eof(),
+      }
+    }
+
+    let body = match self.p.next_while(" \t\n") {
+      Some('{') => ParseBlock::start(self.p)?,
+      Some(_) => {
+        let start = self.p.index - 1;
+        let action = ParseAction::start(self.p, true, self.action_to_expect)?;
+        Block::new(
+          vec![Action::Return(Some(Box::new(action)))],
+          vec![self.p.trimmed_span(start, self.p.index)],
+          Span::new(start, self.p.index),
+        )
+      }
+      None => return self.p.unexpected_eof(),
     };
-    return Ok(());
+
+    Ok(Function {
+      name: None,
+      args,
+      return_type: None,
+      body,
+      lazy_body: None,
+      visibility: Visibility::Private,
+      span: Span::default(),
+    })
   }
   fn parse_function(
     &mut self,
     name: String,
     check_for_function_open_sign: bool,
-  ) -> Result<ParseActionStateFunctionCall, ParsingError> {
+  ) -> Result<ParseActionStateFunctionCall, Box<ParsingError>> {
     let mut res = ParseActionStateFunctionCall {
       name,
       arguments: vec![],
+      argument_spans: vec![],
     };
 
     if check_for_function_open_sign {
@@ -363,22 +1429,25 @@ impl<'a> ParseAction<'a> {
     }
 
     loop {
-      match self.p.next_while(" \t\n") {
-        Some(')') | None => {
-          self.p.index -= 1;
-          break;
-        }
-        _ => {}
+      if let Some(')') | None = self.p.peek_non_whitespace() {
+        break;
       }
 
-      let action = ParseAction::start(self.p, true, ActionToExpect::Assignment(",)"))?;
+      self.p.skip_whitespace();
+      let start = self.p.index;
+      let action = ParseAction::start(self.p, false, ActionToExpect::Assignment(",)"))?;
       res.arguments.push(action);
-      match self.p.next_while(" \t\n") {
-        Some(',') => continue,
-        _ => {
-          self.p.index -= 1;
-          break;
-        }
+      res
+        .argument_spans
+        .push(self.p.trimmed_span(start, self.p.index));
+      self.p.check_limit(
+        self.p.options.max_arguments,
+        res.arguments.len(),
+        "arguments count",
+      )?;
+      self.p.skip_whitespace();
+      if !self.p.eat(',') {
+        break;
       }
     }
 
@@ -394,7 +1463,7 @@ impl<'a> ParseAction<'a> {
     &mut self,
     name: String,
     check_for_equal_sign: bool,
-  ) -> Result<ParseActionStateAssigment, ParsingError> {
+  ) -> Result<ParseActionStateAssigment, Box<ParsingError>> {
     let mut res = ParseActionStateAssigment { name, action: None };
 
     if check_for_equal_sign {
@@ -407,7 +1476,12 @@ impl<'a> ParseAction<'a> {
 
     match self.p.next_while(" \t\n") {
       Some(_) => {
-        let action = ParseAction::start(self.p, true, ActionToExpect::Assignment(""))?;
+        // The value ends where the assignment ends, like the `{` after `i = i + 1` in a for loop
+        let end = match self.action_to_expect {
+          ActionToExpect::Assignment(end) => end,
+          ActionToExpect::ActionInBody => "",
+        };
+        let action = ParseAction::start(self.p, true, ActionToExpect::Assignment(end))?;
         res.action = Some(action);
       }
       None => return self.p.unexpected_eof(),
@@ -415,38 +1489,65 @@ impl<'a> ParseAction<'a> {
 
     Ok(res)
   }
-  fn parse_looper(&mut self, loop_type: LoopType) -> Result<ParseActionState, ParsingError> {
+  fn parse_looper(&mut self, loop_type: LoopType) -> Result<ParseActionState, Box<ParsingError>> {
     self.p.next_while(" \t\n");
 
     let mut for_item_name: Option<String> = None;
+    let mut for_index_name: Option<String> = None;
 
     // Parse the bit between the "for"/"while" and "{"
+    // A loop doesn't have anything here
     let loop_based_on = match loop_type {
-      LoopType::While => ParseAction::start(self.p, true, ActionToExpect::Assignment("{"))?,
+      LoopType::While => Some(ParseAction::start(
+        self.p,
+        true,
+        ActionToExpect::Assignment("{"),
+      )?),
       LoopType::For => {
-        let mut name = NameBuilder::new();
-        loop {
-          let c = self.p.next_char();
-          match c {
-            Some(' ') | Some('\t') | Some('\n') => break,
-            Some(c) if legal_name_char(c) => name.push(c),
-            Some(c) => return self.p.unexpected_char(c),
-            None => return self.p.unexpected_eof(),
+        self.p.index -= 1;
+        if self.p.try_match(&[(Keywords::Let, " \t\n")]).is_some() {
+          return self.parse_classic_for();
+        }
+        let mut name = self.p.next_ident()?;
+        if self.p.peek_non_whitespace() == Some(',') {
+          // This is the index of a `for i, item in items`
+          if name.name.is_empty() {
+            return self
+              .p
+              .error(ParsingErrorType::Custom("Missing for loop index name"));
+          }
+          self.p.skip_whitespace();
+          self.p.index += 1;
+          for_index_name = Some(name.name);
+          name = self.p.next_ident()?;
+          if name.name.is_empty() {
+            return self
+              .p
+              .error(ParsingErrorType::Custom("Missing for loop item name"));
           }
         }
+        match self.p.next_char() {
+          Some(' ') | Some('\t') | Some('\n') => {}
+          Some(c) => return self.p.unexpected_char(c),
+          None => return self.p.unexpected_eof(),
+        }
 
-        for_item_name = Some(name.to_string(self.p)?);
+        for_item_name = Some(name.name);
         self.p.expect("in")?;
 
-        if let None = self.p.next_while(" \t\n") {
+        if self.p.next_while(" \t\n").is_none() {
           return self.p.unexpected_eof();
         }
 
-        ParseAction::start(self.p, true, ActionToExpect::Assignment("{"))?
+        Some(ParseAction::start(
+          self.p,
+          true,
+          ActionToExpect::Assignment("{"),
+        )?)
       }
       LoopType::Loop => {
         self.p.index -= 1;
-        Action::NOOP
+        None
       }
     };
 
@@ -456,32 +1557,273 @@ impl<'a> ParseAction<'a> {
       None => return self.p.unexpected_eof(),
     };
 
-    let actions = ParseActions::start(self.p)?;
+    let body = ParseBlock::start(self.p)?;
 
-    Ok(match loop_type {
-      LoopType::For => ParseActionState::For(ActionFor {
-        actions,
-        list: Box::new(loop_based_on),
-        item_name: for_item_name.unwrap_or(String::new()),
+    Ok(match (loop_type, loop_based_on) {
+      (LoopType::For, Some(list)) => ParseActionState::For(ActionFor {
+        body,
+        list: Box::new(list),
+        item_name: for_item_name.unwrap_or_default(),
+        index_name: for_index_name,
       }),
-      LoopType::While => ParseActionState::While(ActionWhile {
-        actions,
-        true_value: Box::new(loop_based_on),
+      (LoopType::While, Some(true_value)) => ParseActionState::While(ActionWhile {
+        body,
+        true_value: Box::new(true_value),
       }),
-      LoopType::Loop => ParseActionState::Loop(actions),
+      _ => ParseActionState::Loop(body),
     })
   }
-  fn parse_return(&mut self) -> Result<ParseActionStateReturn, ParsingError> {
-    let mut res = ParseActionStateReturn { action: None };
+  /// Parses the `i = 0; i < 10; i += 1 {}` part of `for let i = 0; i < 10; i += 1 {}`
+  fn parse_classic_for(&mut self) -> Result<ParseActionState, Box<ParsingError>> {
+    let init = parse_var_until(self.p, Some(VarType::Let), ";")?;
+    self.expect_clause_end(';')?;
+
+    if self.p.next_while(" \t\n").is_none() {
+      return self.p.unexpected_eof();
+    }
+    let condition = ParseAction::start(self.p, true, ActionToExpect::Assignment(";"))?;
+    self.expect_clause_end(';')?;
+
+    if self.p.next_while(" \t\n").is_none() {
+      return self.p.unexpected_eof();
+    }
+    let update = ParseAction::start(self.p, true, ActionToExpect::Assignment("{"))?;
+    match update {
+      Action::Assigment(_)
+      | Action::FieldAssigment(_)
+      | Action::FunctionCall(_)
+      | Action::MethodCall(_) => {}
+      _ => {
+        return self.p.error(ParsingErrorType::Custom(
+          "The last part of a for loop must be a assignment or function call",
+        ))
+      }
+    }
+    self.expect_clause_end('{')?;
 
+    Ok(ParseActionState::ForClassic(ActionForClassic {
+      init,
+      condition: Box::new(condition),
+      update: Box::new(update),
+      body: ParseBlock::start(self.p)?,
+    }))
+  }
+  /// Skips the whitespace until the char that ends a part of a statement like the `;` in a for loop
+  fn expect_clause_end(&mut self, end: char) -> Result<(), Box<ParsingError>> {
     match self.p.next_while(" \t\n") {
+      Some(c) if c == end => Ok(()),
+      Some(c) => self.p.unexpected_char(c),
+      None => self.p.unexpected_eof(),
+    }
+  }
+  /// Parses the `{} while foo` part of `do {} while foo`
+  fn parse_do_while(&mut self) -> Result<ParseActionState, Box<ParsingError>> {
+    self.expect_clause_end('{')?;
+    let body = ParseBlock::start(self.p)?;
+
+    if self.p.next_while(" \t\n").is_none() {
+      return self.p.unexpected_eof();
+    }
+    self.p.index -= 1;
+    if self.p.try_match(&[(Keywords::While, " \t\n")]).is_none() {
+      return self
+        .p
+        .error(ParsingErrorType::Custom("Missing while after the do body"));
+    }
+
+    if self.p.next_while(" \t\n").is_none() {
+      return self.p.unexpected_eof();
+    }
+    let true_value = ParseAction::start(self.p, true, ActionToExpect::Assignment("}"))?;
+    Ok(ParseActionState::DoWhile(ActionWhile {
+      body,
+      true_value: Box::new(true_value),
+    }))
+  }
+  /// Parses the `{} catch err {}` part of `try {} catch err {}`
+  fn parse_try(&mut self) -> Result<ActionTry, Box<ParsingError>> {
+    self.expect_clause_end('{')?;
+    let body = ParseBlock::start(self.p)?;
+
+    if self.p.next_while(" \t\n").is_none() {
+      return self.p.unexpected_eof();
+    }
+    self.p.index -= 1;
+    if self.p.try_match(&[(Keywords::Catch, "{ \t\n")]).is_none() {
+      return self
+        .p
+        .error(ParsingErrorType::Custom("Missing catch after the try body"));
+    }
+
+    let mut error_name = None;
+    if self.p.peek_non_whitespace() != Some('{') {
+      let ident = self.p.next_ident()?;
+      if ident.name.is_empty() {
+        return self
+          .p
+          .error(ParsingErrorType::Custom("Missing catch error name"));
+      }
+      error_name = Some(ident.name);
+    }
+
+    self.expect_clause_end('{')?;
+    let catch_body = ParseBlock::start(self.p)?;
+    Ok(ActionTry {
+      body,
+      error_name,
+      catch_body,
+    })
+  }
+  /// Parses a if statement, if as_value is true every body contains a single value
+  /// like `if a { 1 } else { 2 }` and the else is required
+  fn parse_if(&mut self, as_value: bool) -> Result<ParseActionState, Box<ParsingError>> {
+    let (condition, body) = self.parse_condition_and_body(as_value)?;
+    let mut res = ActionIf {
+      condition: Box::new(condition),
+      body,
+      else_ifs: vec![],
+      else_body: None,
+    };
+
+    loop {
+      // Check if the if statement is followed by an else
+      let before_else = self.p.index;
+      if self.p.next_while(" \t\n").is_none() {
+        self.p.index = before_else;
+        break;
+      }
+      self.p.index -= 1;
+      if self.p.try_match(&[(Keywords::Else, "{ \t\n")]).is_none() {
+        self.p.index = before_else;
+        break;
+      }
+
+      match self.p.next_while(" \t\n") {
+        Some('{') => {
+          res.else_body = Some(self.parse_if_body(as_value)?);
+          break;
+        }
+        Some(c) => {
+          self.p.index -= 1;
+          if self.p.try_match(&[(Keywords::If, " \t\n")]).is_none() {
+            return self.p.unexpected_char(c);
+          }
+          res.else_ifs.push(self.parse_condition_and_body(as_value)?);
+        }
+        None => return self.p.unexpected_eof(),
+      }
+    }
+
+    if as_value && res.else_body.is_none() {
+      return self.p.error(ParsingErrorType::Custom(
+        "A if used as a value needs a else",
+      ));
+    }
+    Ok(ParseActionState::If(res))
+  }
+  /// Parses the `foo {}` part of a `if foo {}`
+  fn parse_condition_and_body(
+    &mut self,
+    as_value: bool,
+  ) -> Result<(Action, Block), Box<ParsingError>> {
+    if self.p.next_while(" \t\n").is_none() {
+      return self.p.unexpected_eof();
+    }
+    let condition = ParseAction::start(self.p, true, ActionToExpect::Assignment("{"))?;
+
+    match self.p.next_while(" \t\n") {
+      Some('{') => {}
+      Some(c) => return self.p.unexpected_char(c),
+      None => return self.p.unexpected_eof(),
+    };
+
+    let body = self.parse_if_body(as_value)?;
+    Ok((condition, body))
+  }
+  /// Parses the body of a if, the `{` must already be consumed
+  /// A if used as a value has only the value inside of its body like the `1` in `{ 1 }`
+  fn parse_if_body(&mut self, as_value: bool) -> Result<Block, Box<ParsingError>> {
+    if !as_value {
+      return ParseBlock::start(self.p);
+    }
+
+    let start = self.p.index - 1;
+    match self.p.next_while(" \t\n") {
+      Some('}') => return self.p.error(ParsingErrorType::Custom("Missing if value")),
+      Some(_) => {}
+      None => return self.p.unexpected_eof(),
+    }
+    let value_start = self.p.index - 1;
+    let value = ParseAction::start(self.p, true, ActionToExpect::Assignment("}"))?;
+    let value_span = self.p.trimmed_span(value_start, self.p.index);
+    self.expect_clause_end('}')?;
+    Ok(Block::new(
+      vec![value],
+      vec![value_span],
+      Span::new(start, self.p.index),
+    ))
+  }
+  fn parse_return(&mut self) -> Result<ParseActionStateReturn, Box<ParsingError>> {
+    let mut res = ParseActionStateReturn { actions: vec![] };
+
+    self.p.skip_whitespace();
+    match self.p.seek_next_char() {
+      Some('}') => {}
+      Some(_) => loop {
+        let action = ParseAction::start(self.p, false, ActionToExpect::Assignment(",}"))?;
+        res.actions.push(action);
+
+        // Multiple values are separated by a comma like `return a, b`
+        let before_comma = self.p.index;
+        if self.p.next_while(" \t") != Some(',') {
+          self.p.index = before_comma;
+          break;
+        }
+        self.p.skip_whitespace();
+        if self.p.seek_next_char().is_none() {
+          return self.p.unexpected_eof();
+        }
+      },
+      None => return self.p.unexpected_eof(),
+    }
+    Ok(res)
+  }
+  /// Parses the action after a defer like the `foo()` in `defer foo()`
+  fn parse_defer(&mut self) -> Result<Action, Box<ParsingError>> {
+    self.p.skip_whitespace();
+    match self.p.seek_next_char() {
+      Some('}') => {
+        return self
+          .p
+          .error(ParsingErrorType::Custom("Missing action after defer"))
+      }
+      Some(_) => {}
+      None => return self.p.unexpected_eof(),
+    }
+
+    let action = ParseAction::start(self.p, false, ActionToExpect::ActionInBody)?;
+    match action {
+      // These would jump away from the place where the deferred action is run
+      Action::Return(_) | Action::Break(_) | Action::Continue => self.p.error(
+        ParsingErrorType::Custom("Can't defer a return, break or continue"),
+      ),
+      action => Ok(action),
+    }
+  }
+  /// Parses the optional value after a break like the `5` in `break 5`
+  fn parse_break(&mut self) -> Result<Option<Action>, Box<ParsingError>> {
+    let start = self.p.index;
+    let value_start = self.p.skip_whitespace();
+    match self.p.seek_next_char() {
       Some('}') => {}
+      // The value must be on the same line as the break
+      Some(_) if self.p.contents[start..value_start].contains(&b'\n') => {}
       Some(_) => {
-        let action = ParseAction::start(self.p, true, ActionToExpect::Assignment("}"))?;
-        res.action = Some(action);
+        let action = ParseAction::start(self.p, false, ActionToExpect::Assignment("}"))?;
+        return Ok(Some(action));
       }
       None => return self.p.unexpected_eof(),
     }
-    Ok(res)
+    Ok(None)
   }
 }
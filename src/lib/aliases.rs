@@ -0,0 +1,54 @@
+use super::*;
+
+/// A `type Foo = []string` that gives a type another name
+#[derive(Debug)]
+pub struct TypeAlias {
+  pub name: String,
+  /// The type after the `=`
+  pub type_: Type,
+}
+
+pub struct ParseTypeAlias<'a> {
+  p: &'a mut Parser,
+  res: TypeAlias,
+}
+
+impl<'a> ParseTypeAlias<'a> {
+  pub fn start(p: &'a mut Parser) -> Result<TypeAlias, Box<ParsingError>> {
+    let start = p.index;
+    let mut s = Self {
+      p,
+      res: TypeAlias {
+        name: String::new(),
+        type_: Type::inferred(),
+      },
+    };
+    s.parse()?;
+    s.p.trace_consumed("type alias", start);
+    Ok(s.res)
+  }
+  fn parse(&mut self) -> Result<(), Box<ParsingError>> {
+    let name = self.p.next_ident()?.name;
+    if name.is_empty() {
+      return self
+        .p
+        .error(ParsingErrorType::Custom("Missing type alias name"));
+    }
+    self.res.name = name;
+
+    match self.p.next_while(" \t\n") {
+      Some('=') => {}
+      Some(c) => return self.p.unexpected_char(c),
+      None => return self.p.unexpected_eof(),
+    }
+
+    let type_ = ParseType::start(self.p, false)?;
+    if type_.name.is_empty() {
+      return self
+        .p
+        .error(ParsingErrorType::Custom("Missing type after ="));
+    }
+    self.res.type_ = type_;
+    Ok(())
+  }
+}
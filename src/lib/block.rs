@@ -0,0 +1,103 @@
+use super::*;
+
+/// The body of a function, loop, if or match arm
+#[derive(Debug)]
+pub struct Block {
+  pub actions: Vec<Action>,
+  /// The span of every action inside of actions, without the whitespace and comments around it
+  pub action_spans: Vec<Span>,
+  /// From the `{` up to and including the `}`,
+  /// for a body without braces like `|x| x + 1` this is the expression
+  pub span: Span,
+  /// The scope of the variables declared in this block,
+  /// this is None until names are resolved
+  pub scope_id: Option<usize>,
+}
+
+impl Block {
+  pub fn new(actions: Vec<Action>, action_spans: Vec<Span>, span: Span) -> Self {
+    Self {
+      actions,
+      action_spans,
+      span,
+      scope_id: None,
+    }
+  }
+  pub fn empty() -> Self {
+    Self::new(vec![], vec![], Span::default())
+  }
+  /// Calls f for every action inside of this block and the actions nested inside of them
+  pub fn visit<'a>(&'a self, f: &mut dyn FnMut(&'a Action)) {
+    for action in &self.actions {
+      action.visit(f);
+    }
+  }
+  /// Same as visit but also gives f the span of the action,
+  /// actions without a span of their own get the span of the closest parent that has one
+  pub fn visit_spanned<'a>(&'a self, f: &mut dyn FnMut(&'a Action, Span)) {
+    for (i, action) in self.actions.iter().enumerate() {
+      action.visit_spanned(self.action_span(i), f);
+    }
+  }
+  /// The span of the action at the index, falls back to the span of the block
+  /// when the actions were changed after parsing (for example by a plugin)
+  pub fn action_span(&self, index: usize) -> Span {
+    match self.action_spans.get(index) {
+      Some(span) if self.action_spans.len() == self.actions.len() => *span,
+      _ => self.span,
+    }
+  }
+}
+
+enum ParseBlockState {
+  Nothing,
+}
+
+pub struct ParseBlock<'a> {
+  p: &'a mut Parser,
+  res: Block,
+  state: ParseBlockState,
+}
+
+impl<'a> ParseBlock<'a> {
+  /// Parses a block, the `{` must already be consumed
+  pub fn start(p: &'a mut Parser) -> Result<Block, Box<ParsingError>> {
+    let start = p.index;
+    let mut s = Self {
+      p,
+      res: Block::empty(),
+      state: ParseBlockState::Nothing,
+    };
+    s.parse()?;
+    s.res.span = Span::new(start - 1, s.p.index);
+    s.p.trace_consumed("block", start);
+    Ok(s.res)
+  }
+  fn parse(&mut self) -> Result<(), Box<ParsingError>> {
+    while let Some(c) = self.p.next_char() {
+      match self.state {
+        ParseBlockState::Nothing => match c {
+          '\t' | '\n' | ' ' => {
+            // Ignore these chars
+          }
+          '}' => return Ok(()),
+          _ if legal_name_char(c) => {
+            self.p.check_cancelled()?;
+            let start = self.p.index - 1;
+            let action = ParseAction::start(self.p, true, ActionToExpect::ActionInBody)?;
+            self.res.actions.push(action);
+            let span = self.p.trimmed_span(start, self.p.index);
+            self.res.action_spans.push(span);
+
+            self.p.skip_whitespace();
+            if self.p.seek_next_char().is_none() {
+              return self.p.unexpected_eof();
+            }
+          }
+          c => return self.p.unexpected_char(c),
+        },
+      }
+    }
+    Ok(())
+  }
+}
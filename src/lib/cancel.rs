@@ -0,0 +1,54 @@
+use super::*;
+use alloc::sync::Arc;
+use core::sync::atomic::{AtomicBool, Ordering};
+#[cfg(feature = "std")]
+use std::time::{Duration, Instant};
+
+/// Stops long running work like parsing a big program when it's no longer needed,
+/// for example when the LSP gets a newer request for the same file
+///
+/// Clones of a token share the same state so a token can be cancelled from another thread
+#[derive(Debug, Clone, Default)]
+pub struct CancellationToken {
+  cancelled: Arc<AtomicBool>,
+  #[cfg(feature = "std")]
+  deadline: Option<Instant>,
+}
+
+impl CancellationToken {
+  pub fn new() -> Self {
+    Self::default()
+  }
+  /// A token that cancels itself after the timeout, this needs the std feature for the clock
+  #[cfg(feature = "std")]
+  pub fn with_timeout(timeout: Duration) -> Self {
+    Self {
+      cancelled: Arc::default(),
+      deadline: Some(Instant::now() + timeout),
+    }
+  }
+  pub fn cancel(&self) {
+    self.cancelled.store(true, Ordering::Relaxed);
+  }
+  pub fn is_cancelled(&self) -> bool {
+    if self.cancelled.load(Ordering::Relaxed) {
+      return true;
+    }
+    #[cfg(feature = "std")]
+    if let Some(deadline) = self.deadline {
+      return Instant::now() >= deadline;
+    }
+    false
+  }
+}
+
+impl Parser {
+  /// Returns a error if the cancellation token in the options was cancelled,
+  /// this is called between the items and statements so the parser stops soon after a cancel
+  pub fn check_cancelled(&self) -> Result<(), Box<ParsingError>> {
+    match &self.options.cancellation {
+      Some(token) if token.is_cancelled() => self.error(ParsingErrorType::Cancelled),
+      _ => Ok(()),
+    }
+  }
+}
@@ -0,0 +1,132 @@
+use super::*;
+use std::backtrace::Backtrace;
+use std::cell::{Cell, RefCell};
+use std::fs;
+use std::io;
+use std::panic;
+use std::path::{Path, PathBuf};
+use std::sync::Once;
+
+/// A report of a internal error (a panic) that the user can attach to an issue
+/// Reports are only written to disk, nothing is ever send over the network
+#[derive(Debug)]
+pub struct CrashReport {
+  /// The stage that crashed like `parse`
+  pub stage: &'static str,
+  pub version: &'static str,
+  /// Identifies the input without sharing it, crashes on the same input have the same hash
+  pub input_hash: u64,
+  /// The input itself, only set if the user opted in to share it
+  pub source: Option<String>,
+  pub message: String,
+  pub backtrace: String,
+}
+
+impl CrashReport {
+  pub fn file_name(&self) -> String {
+    format!("gpl-crash-{:016x}.txt", self.input_hash)
+  }
+  /// Writes the report into the dir and returns the path of the written file
+  pub fn write_to(&self, dir: &Path) -> io::Result<PathBuf> {
+    let path = dir.join(self.file_name());
+    fs::write(&path, self.to_string())?;
+    Ok(path)
+  }
+}
+
+impl Display for CrashReport {
+  fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+    writeln!(f, "gpl crash report")?;
+    writeln!(f, "version: {}", self.version)?;
+    writeln!(f, "stage: {}", self.stage)?;
+    writeln!(f, "input hash: {:016x}", self.input_hash)?;
+    writeln!(f, "message: {}", self.message)?;
+    writeln!(f, "\nbacktrace:\n{}", self.backtrace)?;
+    match &self.source {
+      Some(source) => writeln!(f, "\nsource:\n{}", source),
+      None => writeln!(f, "\nsource: not included"),
+    }
+  }
+}
+
+/// A caught panic
+#[derive(Debug)]
+pub struct Panic {
+  /// The panic message including where it happened
+  pub message: String,
+  pub backtrace: String,
+}
+
+impl CrashReport {
+  pub fn new(stage: &'static str, input: &[u8], include_source: bool, panic: Panic) -> Self {
+    Self {
+      stage,
+      version: env!("CARGO_PKG_VERSION"),
+      input_hash: hash(input),
+      source: if include_source {
+        Some(String::from_utf8_lossy(input).into_owned())
+      } else {
+        None
+      },
+      message: panic.message,
+      backtrace: panic.backtrace,
+    }
+  }
+}
+
+thread_local! {
+  static CATCHING: Cell<bool> = const { Cell::new(false) };
+  static LAST_PANIC: RefCell<Option<(String, String)>> = const { RefCell::new(None) };
+}
+
+static INSTALL_HOOK: Once = Once::new();
+
+/// Installs a panic hook that stores the panics that happen inside of `catch`,
+/// all other panics are passed on to the hook that was set before
+fn install_hook() {
+  INSTALL_HOOK.call_once(|| {
+    let previous_hook = panic::take_hook();
+    panic::set_hook(Box::new(move |info| {
+      if !CATCHING.with(|catching| catching.get()) {
+        return previous_hook(info);
+      }
+      let backtrace = Backtrace::force_capture().to_string();
+      LAST_PANIC.with(|last| *last.borrow_mut() = Some((info.to_string(), backtrace)));
+    }));
+  });
+}
+
+/// Runs f and turns a panic inside of it into a crash report
+pub fn catch<T>(
+  stage: &'static str,
+  input: &[u8],
+  include_source: bool,
+  f: impl FnOnce() -> T + panic::UnwindSafe,
+) -> Result<T, CrashReport> {
+  catch_panic(f).map_err(|panic| CrashReport::new(stage, input, include_source, panic))
+}
+
+/// Runs f and returns the panic if it panics, the panic is not printed
+pub fn catch_panic<T>(f: impl FnOnce() -> T + panic::UnwindSafe) -> Result<T, Panic> {
+  install_hook();
+  let was_catching = CATCHING.with(|catching| catching.replace(true));
+  let res = panic::catch_unwind(f);
+  CATCHING.with(|catching| catching.set(was_catching));
+
+  res.map_err(|_| {
+    let (message, backtrace) = LAST_PANIC
+      .with(|last| last.borrow_mut().take())
+      .unwrap_or_default();
+    Panic { message, backtrace }
+  })
+}
+
+/// FNV-1a, unlike the std hasher the result is the same for every build
+fn hash(input: &[u8]) -> u64 {
+  let mut hash: u64 = 0xcbf29ce484222325;
+  for byte in input {
+    hash ^= *byte as u64;
+    hash = hash.wrapping_mul(0x100000001b3);
+  }
+  hash
+}
@@ -0,0 +1,142 @@
+use super::*;
+
+/// A change to the text of the code, the span is of the text before the edits are applied
+/// SourceMap can convert the span into the UTF-16 positions a LSP client expects
+#[derive(Debug, Clone, PartialEq)]
+pub struct TextEdit {
+  pub span: Span,
+  pub new_text: String,
+}
+
+/// The result of a committed AstEdit
+#[derive(Debug)]
+pub struct EditResult {
+  /// The program parsed from the edited code
+  pub program: Parser,
+  /// The edits to go from the old code to the new code, sorted by their position
+  pub edits: Vec<TextEdit>,
+  /// The edits to go from the new code back to the old code
+  pub undo: Vec<TextEdit>,
+}
+
+/// Edits a parsed program as one transaction
+///
+/// The parsed program is never changed, committing the edits parses the edited code into a new program
+/// so the old program can be kept around to go back to
+pub struct AstEdit<'a> {
+  p: &'a Parser,
+  edits: Vec<TextEdit>,
+}
+
+impl<'a> AstEdit<'a> {
+  pub fn new(p: &'a Parser) -> Self {
+    Self { p, edits: vec![] }
+  }
+  /// Adds a statement like `foo()` at the end of the block
+  pub fn insert_statement(&mut self, block: &Block, code: &str) -> Result<(), Box<ParsingError>> {
+    if block.span.len() < 2 {
+      return self.p.error(ParsingErrorType::Custom(
+        "The block has no position in the code",
+      ));
+    }
+
+    // Replace the whitespace before the `}` so the new statement gets its own line
+    let close = block.span.end - 1;
+    let mut start = close;
+    while start > block.span.start + 1 && b" \t\n".contains(&self.p.contents[start - 1]) {
+      start -= 1;
+    }
+    let indent = self.indent_of(close);
+    self.edits.push(TextEdit {
+      span: Span::new(start, close),
+      new_text: format!("\n{}  {}\n{}", indent, code, indent),
+    });
+    Ok(())
+  }
+  /// Replaces the expression inside of the span, like the value of a variable using Variable.action_span
+  pub fn replace_expression(&mut self, span: Span, code: &str) {
+    self.edits.push(TextEdit {
+      span,
+      new_text: String::from(code),
+    });
+  }
+  /// Removes a top level item like a function, struct, enum or import together with the rest of its line
+  pub fn delete_item(&mut self, span: Span) -> Result<(), Box<ParsingError>> {
+    if span.is_empty() {
+      return self.p.error(ParsingErrorType::Custom(
+        "The item has no position in the code",
+      ));
+    }
+
+    let mut end = span.end;
+    while let Some(c) = self.p.contents.get(end) {
+      end += 1;
+      if *c == b'\n' {
+        break;
+      }
+    }
+    self.edits.push(TextEdit {
+      span: Span::new(span.start, end),
+      new_text: String::new(),
+    });
+    Ok(())
+  }
+  /// Applies the edits and parses the edited code with the same options as the old program
+  pub fn commit(mut self) -> Result<EditResult, Box<ParsingError>> {
+    self
+      .edits
+      .sort_by_key(|edit| (edit.span.start, edit.span.end));
+    for pair in self.edits.windows(2) {
+      if pair[0].span.end > pair[1].span.start {
+        return self.p.error(ParsingErrorType::Custom("Edits overlap"));
+      }
+    }
+
+    let mut undo = vec![];
+    // How much the code after the edit moved because of the earlier edits
+    let mut shift: isize = 0;
+    for edit in &self.edits {
+      let start = (edit.span.start as isize + shift) as usize;
+      undo.push(TextEdit {
+        span: Span::new(start, start + edit.new_text.len()),
+        new_text: self.p.span_text(edit.span),
+      });
+      shift += edit.new_text.len() as isize - edit.span.len() as isize;
+    }
+
+    let contents = apply_edits(&self.p.contents, &self.edits);
+    let program = Parser::parse_with_options(contents, self.p.options.clone())?;
+    Ok(EditResult {
+      program,
+      edits: self.edits,
+      undo,
+    })
+  }
+  /// Returns the whitespace at the start of the line the index is on
+  fn indent_of(&self, index: usize) -> String {
+    let line_start = match self.p.contents[..index].iter().rposition(|c| *c == b'\n') {
+      Some(newline) => newline + 1,
+      None => 0,
+    };
+    let indent_len = self.p.contents[line_start..]
+      .iter()
+      .take_while(|c| **c == b' ' || **c == b'\t')
+      .count();
+    self
+      .p
+      .span_text(Span::new(line_start, line_start + indent_len))
+  }
+}
+
+/// Applies the edits to the contents, the edits must be sorted and not overlap
+pub fn apply_edits(contents: &[u8], edits: &[TextEdit]) -> Vec<u8> {
+  let mut res = vec![];
+  let mut last = 0;
+  for edit in edits {
+    res.extend_from_slice(&contents[last..edit.span.start]);
+    res.extend_from_slice(edit.new_text.as_bytes());
+    last = edit.span.end;
+  }
+  res.extend_from_slice(&contents[last..]);
+  res
+}
@@ -0,0 +1,117 @@
+use super::*;
+
+#[derive(Debug)]
+pub struct Enum {
+  pub name: String,
+  pub variants: Vec<EnumVariant>,
+  pub visibility: Visibility,
+  /// From the `enum` (or the `pub` before it) up to and including the `}`
+  pub span: Span,
+}
+
+#[derive(Debug)]
+pub struct EnumVariant {
+  pub name: String,
+  /// The types between the parentheses of `Custom(string)`, empty if there are no parentheses
+  pub payload: Vec<Type>,
+}
+
+pub struct ParseEnum<'a> {
+  p: &'a mut Parser,
+  res: Enum,
+}
+
+impl<'a> ParseEnum<'a> {
+  pub fn start(p: &'a mut Parser) -> Result<Enum, Box<ParsingError>> {
+    let start = p.index;
+    let mut s = Self {
+      p,
+      res: Enum {
+        name: String::new(),
+        variants: vec![],
+        visibility: Visibility::Private,
+        span: Span::default(),
+      },
+    };
+    s.parse()?;
+    s.p.trace_consumed("enum", start);
+    Ok(s.res)
+  }
+  fn parse(&mut self) -> Result<(), Box<ParsingError>> {
+    let name = self.p.next_ident()?.name;
+    if name.is_empty() {
+      return self.p.error(ParsingErrorType::Custom("Missing enum name"));
+    }
+    self.res.name = name;
+
+    match self.p.next_while(" \t\n") {
+      Some('{') => {}
+      Some(c) => return self.p.unexpected_char(c),
+      None => return self.p.unexpected_eof(),
+    }
+
+    loop {
+      match self.p.next_while(" \t\n") {
+        Some('}') => return Ok(()),
+        Some(c) if legal_name_char(c) => self.p.index -= 1,
+        Some(c) => return self.p.unexpected_char(c),
+        None => return self.p.unexpected_eof(),
+      }
+
+      let variant_name = self.p.next_ident()?.name;
+      if self
+        .res
+        .variants
+        .iter()
+        .any(|variant| variant.name == variant_name)
+      {
+        return self
+          .p
+          .error(ParsingErrorType::Custom("Duplicate variant name"));
+      }
+      let mut variant = EnumVariant {
+        name: variant_name,
+        payload: vec![],
+      };
+
+      let mut next = self.p.next_while(" \t");
+      if let Some('(') = next {
+        variant.payload = self.parse_payload()?;
+        next = self.p.next_while(" \t");
+      }
+      self.res.variants.push(variant);
+
+      // Variants are separated by a comma or a newline
+      match next {
+        Some(',') | Some('\n') => {}
+        Some('}') => return Ok(()),
+        Some(c) => return self.p.unexpected_char(c),
+        None => return self.p.unexpected_eof(),
+      }
+    }
+  }
+  /// Parses the `string)` part of `Custom(string)`
+  fn parse_payload(&mut self) -> Result<Vec<Type>, Box<ParsingError>> {
+    let mut payload = vec![];
+    loop {
+      match self.p.next_while(" \t\n") {
+        Some(')') if payload.is_empty() => {
+          return self
+            .p
+            .error(ParsingErrorType::Custom("Empty enum variant payload"))
+        }
+        Some(_) => self.p.index -= 1,
+        None => return self.p.unexpected_eof(),
+      }
+
+      payload.push(ParseType::start(self.p, false)?);
+
+      match self.p.next_while(" \t\n") {
+        Some(',') => {}
+        Some(')') => return Ok(payload),
+        Some(c) => return self.p.unexpected_char(c),
+        None => return self.p.unexpected_eof(),
+      }
+    }
+  }
+}
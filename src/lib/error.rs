@@ -1,5 +1,4 @@
 use super::*;
-use std::error::Error;
 
 pub struct ParsingError {
   pub location: CodeLocation,
@@ -14,6 +13,10 @@ impl ParsingError {
     let mut output: Vec<String> = vec![];
     let y = self.location.y;
 
+    if let Some(file_name) = &self.location.file_name {
+      output.push(format!("In {}", file_name));
+    }
+
     if let Some(line) = self.prev_line.clone() {
       output.push(format!("{}: {}", y - 1, line.replace("\t", "  ")));
     }
@@ -34,43 +37,65 @@ impl ParsingError {
       output.push(format!("{}: {}", y + 1, line.replace("\t", "  ")));
     }
 
-    format!("{}", output.join("\n"))
+    output.join("\n")
   }
 }
 
-impl Error for ParsingError {}
+#[cfg(feature = "std")]
+impl std::error::Error for ParsingError {}
 
-impl std::fmt::Debug for ParsingError {
-  fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+impl core::fmt::Debug for ParsingError {
+  fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
     write!(f, "{}", self.err())
   }
 }
 
 impl Display for ParsingError {
-  fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+  fn fmt(&self, f: &mut core::fmt::Formatter) -> core::fmt::Result {
     write!(f, "{}", self.err())
   }
 }
 
-#[derive(Debug)]
+#[derive(Debug, PartialEq)]
 pub enum ParsingErrorType {
   IncompletedArgument,
   UnexpectedEOF,
   UnexpectedChar(char),
   UnexpectedResult,
   InvalidNameChar,
+  LimitExceeded(&'static str),
+  /// The parser used more of a resource (like memory) than allowed by the options
+  ResourceExhausted(&'static str),
+  /// A unknown or malformed escape sequence inside of a string like `\q` or `\u{zz}`
+  InvalidEscape,
+  /// Syntax that is behind a experimental feature that is not enabled in the options
+  ExperimentalFeature(&'static str),
+  /// The cancellation token in the options was cancelled before parsing was done
+  Cancelled,
   Custom(&'static str),
+  /// A bug in the parser, the parser panicked while parsing the code at this location
+  InternalError(String),
 }
 
 impl Display for ParsingErrorType {
-  fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+  fn fmt(&self, f: &mut core::fmt::Formatter) -> core::fmt::Result {
     match self {
       Self::IncompletedArgument => write!(f, "Incompletted argument"),
       Self::UnexpectedEOF => write!(f, "Unexpected EOF"),
       Self::UnexpectedChar(c) => write!(f, "Unexpected char: {}", c),
       Self::UnexpectedResult => write!(f, "Unexpected result"),
       Self::InvalidNameChar => write!(f, "Invalid name char"),
+      Self::LimitExceeded(name) => write!(f, "Limit exceeded: {}", name),
+      Self::ResourceExhausted(name) => write!(f, "Resource exhausted: {}", name),
+      Self::InvalidEscape => write!(f, "Invalid escape sequence"),
+      Self::ExperimentalFeature(name) => {
+        write!(f, "Experimental feature not enabled: {}", name)
+      }
+      Self::Cancelled => write!(f, "Cancelled"),
       Self::Custom(error) => write!(f, "{}", error),
+      Self::InternalError(message) => {
+        write!(f, "Internal error, please report this bug: {}", message)
+      }
     }
   }
 }
@@ -0,0 +1,234 @@
+use super::*;
+
+/// Options for how code is formatted
+#[derive(Debug, Clone)]
+pub struct FormatOptions {
+  /// Write the digits of hexadecimal numbers in uppercase like `0xFF` instead of `0xff`
+  pub uppercase_hex: bool,
+  /// Group the digits of decimal numbers by 3 like `1_000_000` when they have at least this amount of digits,
+  /// if this is None numbers keep the digit separators they were written with
+  pub group_digits: Option<usize>,
+  /// Group the top-level items by kind and sort them, if this is None the items keep the order they were written in
+  pub item_order: Option<ItemOrder>,
+}
+
+/// How the top-level items of a file are sorted within their group
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum ItemOrder {
+  Alphabetical,
+  /// Functions are placed before the functions they call and consts after the consts they use,
+  /// the other items are sorted alphabetically
+  Dependencies,
+}
+
+/// A top-level item of a file
+#[derive(Debug, Clone, PartialEq)]
+pub enum Item {
+  Const(String),
+  TypeAlias(String),
+  Struct(String),
+  Enum(String),
+  Trait(String),
+  /// The struct name and trait name of a impl block
+  Impl(String, Option<String>),
+  Function(String),
+}
+
+impl Default for FormatOptions {
+  fn default() -> Self {
+    Self {
+      uppercase_hex: true,
+      group_digits: None,
+      item_order: None,
+    }
+  }
+}
+
+/// Formats a number literal, the value and suffix of the number stay the same
+pub fn format_number(number: &NumberLiteral, options: &FormatOptions) -> String {
+  let raw = number.raw.as_str();
+  let suffix: &str = number.suffix.map(Into::into).unwrap_or("");
+  let body = &raw[..raw.len() - suffix.len()];
+
+  match (Radix::from_prefix(body.as_bytes()), options.group_digits) {
+    (Radix::Hexadecimal, _) => {
+      let digits = if options.uppercase_hex {
+        body[2..].to_uppercase()
+      } else {
+        body[2..].to_lowercase()
+      };
+      format!("0x{}{}", digits, suffix)
+    }
+    (Radix::Decimal, Some(min_digits)) => {
+      // Only the digits before the `.` or exponent are grouped
+      let end = body.find(['.', 'e', 'E']).unwrap_or(body.len());
+      let digits: String = body[..end].chars().filter(|c| *c != '_').collect();
+      let digits = if digits.len() >= min_digits {
+        group_digits(&digits)
+      } else {
+        digits
+      };
+      format!("{}{}{}", digits, &body[end..], suffix)
+    }
+    _ => raw.to_string(),
+  }
+}
+
+/// Places a `_` between every group of 3 digits counting from the right, like `1_000_000`
+fn group_digits(digits: &str) -> String {
+  let mut res = String::new();
+  for (i, c) in digits.chars().enumerate() {
+    if i > 0 && i % 3 == digits.len() % 3 {
+      res.push('_');
+    }
+    res.push(c);
+  }
+  res
+}
+
+/// Returns the top-level items in the order they are written by the formatter,
+/// they are grouped as consts, types, structs, enums, traits, impls and functions
+pub fn sorted_items(p: &Parser, order: ItemOrder) -> Vec<Item> {
+  let sorted = |names: Vec<&String>| {
+    let mut names: Vec<String> = names.into_iter().cloned().collect();
+    names.sort();
+    names
+  };
+
+  let const_names = p.globals.iter().map(|global| &global.name).collect();
+  let const_names = match order {
+    ItemOrder::Alphabetical => sorted(const_names),
+    ItemOrder::Dependencies => dependency_order(
+      sorted(const_names),
+      &|name| match p.globals.iter().find(|global| global.name == name) {
+        Some(global) => used_names(&global.action),
+        None => vec![],
+      },
+      false,
+    ),
+  };
+
+  let function_names = p.functions.iter().filter_map(|f| f.name.as_ref()).collect();
+  let function_names = match order {
+    ItemOrder::Alphabetical => sorted(function_names),
+    ItemOrder::Dependencies => dependency_order(
+      sorted(function_names),
+      &|name| {
+        let function = p
+          .functions
+          .iter()
+          .find(|function| function.name.as_deref() == Some(name));
+        let mut names = vec![];
+        if let Some(function) = function {
+          function.body.visit(&mut |action| {
+            if let Action::FunctionCall(meta) = action {
+              names.push(meta.name.clone());
+            }
+          });
+        }
+        names
+      },
+      true,
+    ),
+  };
+
+  let mut impls: Vec<(String, Option<String>)> = p
+    .impls
+    .iter()
+    .map(|impl_| (impl_.struct_name.clone(), impl_.trait_name.clone()))
+    .collect();
+  impls.sort();
+
+  let mut items: Vec<Item> = const_names.into_iter().map(Item::Const).collect();
+  let type_aliases = p.type_aliases.iter().map(|alias| &alias.name).collect();
+  items.extend(sorted(type_aliases).into_iter().map(Item::TypeAlias));
+  let structs = p.structs.iter().map(|struct_| &struct_.name).collect();
+  items.extend(sorted(structs).into_iter().map(Item::Struct));
+  let enums = p.enums.iter().map(|enum_| &enum_.name).collect();
+  items.extend(sorted(enums).into_iter().map(Item::Enum));
+  let traits = p.traits.iter().map(|trait_| &trait_.name).collect();
+  items.extend(sorted(traits).into_iter().map(Item::Trait));
+  items.extend(
+    impls
+      .into_iter()
+      .map(|(name, trait_name)| Item::Impl(name, trait_name)),
+  );
+  items.extend(function_names.into_iter().map(Item::Function));
+  items
+}
+
+/// The names of the variables used by the action
+fn used_names(action: &Action) -> Vec<String> {
+  let mut names = vec![];
+  action.visit(&mut |action| {
+    if let Action::VarRef(name) = action {
+      names.push(name.clone());
+    }
+  });
+  names
+}
+
+/// Orders the sorted names so every name is placed after the names it depends on,
+/// or before them if dependents_first is true. Dependencies that are not in names are ignored
+fn dependency_order(
+  mut names: Vec<String>,
+  dependencies: &dyn Fn(&str) -> Vec<String>,
+  dependents_first: bool,
+) -> Vec<String> {
+  if dependents_first {
+    // The result is reversed at the end, so this keeps the alphabetical order
+    names.reverse();
+  }
+
+  let mut res: Vec<String> = vec![];
+  let mut visited: Vec<String> = vec![];
+  for name in &names {
+    // (name, dependencies left to visit) of every name that is being visited
+    let mut stack: Vec<(String, Vec<String>)> = vec![];
+    if !visited.contains(name) {
+      visited.push(name.clone());
+      stack.push((
+        name.clone(),
+        sorted_dependencies(name, &names, dependencies, dependents_first),
+      ));
+    }
+    while let Some((name, left)) = stack.last_mut() {
+      match left.pop() {
+        Some(dependency) if !visited.contains(&dependency) => {
+          visited.push(dependency.clone());
+          let left = sorted_dependencies(&dependency, &names, dependencies, dependents_first);
+          stack.push((dependency, left));
+        }
+        Some(_) => {}
+        None => {
+          res.push(name.clone());
+          stack.pop();
+        }
+      }
+    }
+  }
+
+  if dependents_first {
+    res.reverse();
+  }
+  res
+}
+
+/// The dependencies of name that are in names in the order they are popped from the stack
+fn sorted_dependencies(
+  name: &str,
+  names: &[String],
+  dependencies: &dyn Fn(&str) -> Vec<String>,
+  dependents_first: bool,
+) -> Vec<String> {
+  let mut res: Vec<String> = dependencies(name)
+    .into_iter()
+    .filter(|dependency| names.contains(dependency))
+    .collect();
+  res.sort();
+  res.dedup();
+  if !dependents_first {
+    res.reverse();
+  }
+  res
+}
@@ -1,10 +1,20 @@
 use super::*;
+use core::ops::Range;
 
 #[derive(Debug)]
 pub struct Function {
   pub name: Option<String>,
   pub args: Vec<(String, Type)>,
-  pub body: Actions,
+  /// The type after the arguments like `fn foo() string {}` or `fn foo() -> string {}`
+  pub return_type: Option<Type>,
+  pub body: Block,
+  /// The range of the body when it was skipped because of ParserOptions.lazy_bodies,
+  /// the body stays empty until it's parsed with Parser::parse_body
+  pub lazy_body: Option<Range<usize>>,
+  pub visibility: Visibility,
+  /// From the `fn` (or the `pub` before it) up to and including the `}`,
+  /// only set for functions at the top level of the code and methods
+  pub span: Span,
 }
 
 impl Function {
@@ -12,19 +22,23 @@ impl Function {
     Self {
       name: None,
       args: vec![],
-      body: Actions::empty(),
+      return_type: None,
+      body: Block::empty(),
+      lazy_body: None,
+      visibility: Visibility::Private,
+      span: Span::default(),
     }
   }
 }
 
 #[derive(Debug)]
 struct ParseFunctionStateNothing {
-  function_name: Option<NameBuilder>,
+  function_name: Option<Ident>,
 }
 
 #[derive(Debug)]
 struct ParseFunctionStateArg {
-  name: NameBuilder,
+  name: Option<Ident>,
   type_: Option<Type>,
   parsing_name: bool,
 }
@@ -32,13 +46,40 @@ struct ParseFunctionStateArg {
 impl ParseFunctionStateArg {
   fn new() -> Self {
     Self {
-      name: NameBuilder::new(),
+      name: None,
       type_: None,
       parsing_name: true,
     }
   }
 }
 
+/// What kind of function is being parsed
+enum FunctionKind {
+  /// A function with a name like `fn foo() {}`
+  Named,
+  /// A function used as a value like `fn(a int) {}`, these don't have a name
+  Anonymous,
+  /// A method inside of a impl block, the first argument can be `self` of this type
+  Method(String),
+  /// A method signature inside of a trait, these end at the end of the line instead of having a body
+  Signature(String),
+}
+
+impl FunctionKind {
+  /// Returns true if the argument is the `self` argument of a method,
+  /// this is only allowed as the first argument
+  fn is_receiver(&self, arg: &ParseFunctionStateArg, parsed_args: usize) -> bool {
+    match self {
+      Self::Method(_) | Self::Signature(_) => {
+        parsed_args == 0
+          && arg.type_.is_none()
+          && arg.name.as_ref().is_some_and(|name| name.name == "self")
+      }
+      _ => false,
+    }
+  }
+}
+
 #[derive(Debug)]
 enum ParseFunctionState {
   Nothing(ParseFunctionStateNothing),
@@ -51,23 +92,26 @@ pub struct ParseFunction<'a> {
   p: &'a mut Parser,
   res: Function,
   state: ParseFunctionState,
+  kind: FunctionKind,
 }
 
 impl<'a> ParseFunction<'a> {
-  fn change_state(&mut self, to: ParseFunctionState) -> Result<(), ParsingError> {
+  fn change_state(&mut self, to: ParseFunctionState) -> Result<(), Box<ParsingError>> {
     // Check if the current state has data and if so commit it to the response
     match &self.state {
       ParseFunctionState::Nothing(info) => {
         if let Some(name) = &info.function_name {
-          self.res.name = Some(name.to_string(self.p)?);
+          self.res.name = Some(name.name.clone());
         }
       }
-      ParseFunctionState::Arg(info) if !info.parsing_name && info.name.len() > 0 => {
-        if let Some(type_) = &info.type_ {
-          self
-            .res
-            .args
-            .push((info.name.to_string(self.p)?, type_.clone()));
+      ParseFunctionState::Arg(info) if !info.parsing_name && info.name.is_some() => {
+        if let (Some(name), Some(type_)) = (&info.name, &info.type_) {
+          self.res.args.push((name.name.clone(), type_.clone()));
+          self.p.check_limit(
+            self.p.options.max_arguments,
+            self.res.args.len(),
+            "arguments count",
+          )?;
         }
       }
       ParseFunctionState::Arg(_) => {}
@@ -78,38 +122,73 @@ impl<'a> ParseFunction<'a> {
     self.state = to;
     Ok(())
   }
-  pub fn start(p: &'a mut Parser) -> Result<Function, ParsingError> {
+  pub fn start(p: &'a mut Parser) -> Result<Function, Box<ParsingError>> {
+    Self::start_with_kind(p, FunctionKind::Named)
+  }
+  /// Parses a function without a name like `fn(a int) { return a }`
+  pub fn start_anonymous(p: &'a mut Parser) -> Result<Function, Box<ParsingError>> {
+    Self::start_with_kind(p, FunctionKind::Anonymous)
+  }
+  /// Parses a method of the struct, like `fn bar(self) {}` inside of `impl Foo {}`
+  pub fn start_method(
+    p: &'a mut Parser,
+    struct_name: String,
+  ) -> Result<Function, Box<ParsingError>> {
+    Self::start_with_kind(p, FunctionKind::Method(struct_name))
+  }
+  /// Parses a method signature of the trait, like `fn bar(self) int` inside of `trait Foo {}`
+  pub fn start_signature(
+    p: &'a mut Parser,
+    trait_name: String,
+  ) -> Result<Function, Box<ParsingError>> {
+    Self::start_with_kind(p, FunctionKind::Signature(trait_name))
+  }
+  fn start_with_kind(p: &'a mut Parser, kind: FunctionKind) -> Result<Function, Box<ParsingError>> {
+    p.use_memory(core::mem::size_of::<Function>())?;
+    let start = p.index;
     let mut s = Self {
       p,
       res: Function::empty(),
       state: ParseFunctionState::Nothing(ParseFunctionStateNothing {
         function_name: None,
       }),
+      kind,
     };
     s.parse()?;
+    s.p.trace_consumed("function", start);
     Ok(s.res)
   }
-  fn parse(&mut self) -> Result<(), ParsingError> {
+  fn parse(&mut self) -> Result<(), Box<ParsingError>> {
     while let Some(c) = self.p.next_char() {
       match &mut self.state {
         ParseFunctionState::Nothing(meta) => match c {
           '\t' | '\n' | ' ' => {
-            if let Some(_) = meta.function_name {
+            if meta.function_name.is_some() {
               // Not a valid name char return error
               return self.p.error(ParsingErrorType::InvalidNameChar);
             }
           }
           '(' => {
+            match (&meta.function_name, &self.kind) {
+              (Some(_), FunctionKind::Anonymous) => {
+                return self.p.error(ParsingErrorType::Custom(
+                  "A function used as a value can't have a name",
+                ))
+              }
+              (Some(_), _) | (None, FunctionKind::Anonymous) => {}
+              (None, _) => {
+                return self
+                  .p
+                  .error(ParsingErrorType::Custom("Missing function name"))
+              }
+            }
             self.change_state(ParseFunctionState::Arg(ParseFunctionStateArg::new()))?;
             // end of function name, start parsing arguments
           }
-          c if legal_name_char(c) => {
+          c if legal_name_char(c) && meta.function_name.is_none() => {
             // Parsing the function name
-            if let Some(function_name) = &mut meta.function_name {
-              function_name.push(c);
-            } else {
-              meta.function_name = Some(NameBuilder::new_with_char(c));
-            }
+            self.p.index -= 1;
+            meta.function_name = Some(self.p.next_ident()?);
           }
           _ => {
             // Not a valid name char return error
@@ -117,13 +196,25 @@ impl<'a> ParseFunction<'a> {
           }
         },
         ParseFunctionState::Arg(meta) => match c {
+          ')' | ',' | ' ' | '\t' | '\n' if self.kind.is_receiver(meta, self.res.args.len()) => {
+            // The `self` argument of a method
+            if let FunctionKind::Method(struct_name) | FunctionKind::Signature(struct_name) =
+              &self.kind
+            {
+              let mut type_ = Type::inferred();
+              type_.name = struct_name.clone();
+              self.res.args.push((String::from("self"), type_));
+            }
+            self.p.index -= 1;
+            self.state = ParseFunctionState::AfterArg;
+          }
           '\t' | '\n' | ' ' => {
-            if meta.name.len() > 0 {
+            if meta.name.is_some() {
               meta.parsing_name = false;
             }
           }
           ')' => match meta.type_ {
-            None if meta.name.len() > 0 => {
+            None if meta.name.is_some() => {
               // Argument not completed
               return self.p.error(ParsingErrorType::IncompletedArgument);
             }
@@ -137,9 +228,10 @@ impl<'a> ParseFunction<'a> {
             meta.type_ = Some(ParseType::start(self.p, true)?);
             self.change_state(ParseFunctionState::AfterArg)?;
           }
-          c if legal_name_char(c) => {
-            // Parsing the function name
-            meta.name.push(c);
+          c if legal_name_char(c) && meta.name.is_none() => {
+            // Parsing the argument name
+            self.p.index -= 1;
+            meta.name = Some(self.p.next_ident()?);
           }
           _ => {
             // Not a valid name char return error
@@ -159,15 +251,65 @@ impl<'a> ParseFunction<'a> {
             return self.p.error(ParsingErrorType::InvalidNameChar);
           }
         },
+        ParseFunctionState::Response if matches!(self.kind, FunctionKind::Signature(_)) => {
+          match c {
+            '\n' | ',' => return Ok(()),
+            '}' => {
+              // This is the end of the trait
+              self.p.index -= 1;
+              return Ok(());
+            }
+            '{' => {
+              return self
+                .p
+                .error(ParsingErrorType::Custom("A trait method can't have a body"))
+            }
+            _ => self.parse_response_char(c)?,
+          }
+        }
         ParseFunctionState::Response => match c {
+          '{' if self.p.options.lazy_bodies && matches!(self.kind, FunctionKind::Named) => {
+            self.res.lazy_body = Some(self.p.skip_body()?);
+            return Ok(());
+          }
           '{' => {
-            self.res.body = ParseActions::start(self.p)?;
+            self.res.body = ParseBlock::start(self.p)?;
             return Ok(());
           }
-          _ => {}
+          _ => self.parse_response_char(c)?,
         },
       }
     }
+    // A function always ends with its body
+    self.p.unexpected_eof()
+  }
+  /// Parses a char between the arguments and the body, like the return type
+  fn parse_response_char(&mut self, c: char) -> Result<(), Box<ParsingError>> {
+    match c {
+      '\t' | '\n' | ' ' => {}
+      _ if self.res.return_type.is_some() => return self.p.unexpected_char(c),
+      '-' => {
+        // The return type can optionally be prefixed with `->`
+        match self.p.next_char() {
+          Some('>') => {}
+          Some(c) => return self.p.unexpected_char(c),
+          None => return self.p.unexpected_eof(),
+        }
+        self.res.return_type = Some(self.parse_return_type(false)?);
+      }
+      _ => {
+        self.res.return_type = Some(self.parse_return_type(true)?);
+      }
+    }
     Ok(())
   }
+  fn parse_return_type(&mut self, go_back_one: bool) -> Result<Type, Box<ParsingError>> {
+    let return_type = ParseType::start(self.p, go_back_one)?;
+    if return_type.name.is_empty() {
+      return self
+        .p
+        .error(ParsingErrorType::Custom("Missing return type"));
+    }
+    Ok(return_type)
+  }
 }
@@ -0,0 +1,87 @@
+use super::*;
+
+/// A `impl Foo {}` block with the methods of the struct Foo,
+/// or a `impl Bar for Foo {}` block that implements the trait Bar for Foo
+#[derive(Debug)]
+pub struct Impl {
+  pub struct_name: String,
+  pub trait_name: Option<String>,
+  pub methods: Vec<Function>,
+}
+
+pub struct ParseImpl<'a> {
+  p: &'a mut Parser,
+  res: Impl,
+}
+
+impl<'a> ParseImpl<'a> {
+  pub fn start(p: &'a mut Parser) -> Result<Impl, Box<ParsingError>> {
+    let start = p.index;
+    let mut s = Self {
+      p,
+      res: Impl {
+        struct_name: String::new(),
+        trait_name: None,
+        methods: vec![],
+      },
+    };
+    s.parse()?;
+    s.p.trace_consumed("impl", start);
+    Ok(s.res)
+  }
+  fn parse(&mut self) -> Result<(), Box<ParsingError>> {
+    let name = self.p.next_ident()?.name;
+    if name.is_empty() {
+      return self
+        .p
+        .error(ParsingErrorType::Custom("Missing struct name"));
+    }
+    self.res.struct_name = name;
+
+    self.p.skip_whitespace();
+    if self.p.try_match(&[(Keywords::For, " \t\n")]).is_some() {
+      // The first name was the trait, like `Bar` in `impl Bar for Foo {}`
+      let name = self.p.next_ident()?.name;
+      if name.is_empty() {
+        return self
+          .p
+          .error(ParsingErrorType::Custom("Missing struct name"));
+      }
+      self.res.trait_name = Some(core::mem::replace(&mut self.res.struct_name, name));
+    }
+
+    match self.p.next_while(" \t\n") {
+      Some('{') => {}
+      Some(c) => return self.p.unexpected_char(c),
+      None => return self.p.unexpected_eof(),
+    }
+
+    loop {
+      match self.p.next_while(" \t\n") {
+        Some('}') => return Ok(()),
+        Some(_) => self.p.index -= 1,
+        None => return self.p.unexpected_eof(),
+      }
+
+      let method_start = self.p.index;
+      if self.p.try_match(&[(Keywords::Fn, " \t\n")]).is_none() {
+        return self.p.error(ParsingErrorType::Custom(
+          "A impl block can only contain functions",
+        ));
+      }
+      let mut method = ParseFunction::start_method(self.p, self.res.struct_name.clone())?;
+      method.span = self.p.trimmed_span(method_start, self.p.index);
+      if self
+        .res
+        .methods
+        .iter()
+        .any(|other| other.name == method.name)
+      {
+        return self
+          .p
+          .error(ParsingErrorType::Custom("Duplicate method name"));
+      }
+      self.res.methods.push(method);
+    }
+  }
+}
@@ -0,0 +1,58 @@
+use super::*;
+
+/// A `import "other_file"` or `import foo.bar` at the top level
+#[derive(Debug)]
+pub struct Import {
+  pub path: ImportPath,
+  pub span: Span,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum ImportPath {
+  /// `import "other_file"`, relative to the file that imports it
+  File(String),
+  /// `import foo.bar`, relative to the directory of the entry file
+  Module(Vec<String>),
+}
+
+/// Parses the `"other_file"` or `foo.bar` part of a import, the keyword must already be consumed
+pub fn parse_import(p: &mut Parser) -> Result<Import, Box<ParsingError>> {
+  let start = p.index;
+  let next = p.next_while(" \t");
+  let path_start = p.index.saturating_sub(1);
+  let path = match next {
+    Some('"') => {
+      let path = parse_static_str(p)?.content;
+      if path.is_empty() {
+        return p.error(ParsingErrorType::Custom("Missing import path"));
+      }
+      ImportPath::File(path)
+    }
+    Some(c) if legal_name_char(c) => {
+      p.index -= 1;
+      let mut parts = vec![];
+      loop {
+        let name = p.next_ident()?.name;
+        if name.is_empty() {
+          return p.error(ParsingErrorType::Custom("Missing module name"));
+        }
+        parts.push(name);
+        if !p.eat('.') {
+          break;
+        }
+      }
+      ImportPath::Module(parts)
+    }
+    Some(c) => return p.unexpected_char(c),
+    None => return p.unexpected_eof(),
+  };
+  let span = Span::new(path_start, p.index);
+
+  // A import is the only thing on its line
+  match p.next_while(" \t") {
+    Some('\n') | None => {}
+    Some(c) => return p.unexpected_char(c),
+  }
+  p.trace_consumed("import", start);
+  Ok(Import { path, span })
+}
@@ -0,0 +1,95 @@
+use super::*;
+use core::ops::Range;
+#[cfg(feature = "std")]
+use std::panic;
+
+impl Parser {
+  /// Skips over the body of a function without parsing it, the `{` must already be consumed
+  /// Returns the range of the body without the braces
+  pub fn skip_body(&mut self) -> Result<Range<usize>, Box<ParsingError>> {
+    let start = self.index;
+    let mut depth = 0;
+    while let Some(c) = self.next_char() {
+      match c {
+        '{' => depth += 1,
+        '}' if depth == 0 => return Ok(start..self.index - 1),
+        '}' => depth -= 1,
+        '"' => self.skip_until('"', true)?,
+        '`' => self.skip_until('`', false)?,
+        _ => {}
+      }
+    }
+    self.unexpected_eof()
+  }
+  /// Skips the contents of a string, escaped chars are skipped if escapes is true
+  fn skip_until(&mut self, end: char, escapes: bool) -> Result<(), Box<ParsingError>> {
+    while let Some(c) = self.next_raw_char() {
+      if c == end {
+        return Ok(());
+      }
+      if escapes && c == '\\' {
+        self.next_raw_char();
+      }
+    }
+    self.unexpected_eof()
+  }
+  /// Parses the body of a function that was skipped because of ParserOptions.lazy_bodies
+  /// Does nothing if the body was already parsed, like parse_with_options this never panics when the std feature is enabled
+  pub fn parse_body(&mut self, function: usize) -> Result<(), Box<ParsingError>> {
+    let range = match self.functions.get_mut(function) {
+      Some(function) => match function.lazy_body.take() {
+        Some(range) => range,
+        None => return Ok(()),
+      },
+      None => return Ok(()),
+    };
+
+    let index = self.index;
+    let lazy_bodies = self.options.lazy_bodies;
+    // Functions nested inside of the body are parsed right away
+    self.options.lazy_bodies = false;
+    self.index = range.start;
+    #[cfg(feature = "std")]
+    let body = match crash::catch_panic(panic::AssertUnwindSafe(|| self.parse_lazy_block())) {
+      Ok(body) => body,
+      Err(panic) => self.error(ParsingErrorType::InternalError(panic.message)),
+    };
+    #[cfg(not(feature = "std"))]
+    let body = self.parse_lazy_block();
+    self.options.lazy_bodies = lazy_bodies;
+    self.index = index;
+
+    match body {
+      Ok(body) => {
+        self.functions[function].body = body;
+        Ok(())
+      }
+      Err(err) => {
+        // Keep the range so the error can be reproduced
+        self.functions[function].lazy_body = Some(range);
+        Err(err)
+      }
+    }
+  }
+  fn parse_lazy_block(&mut self) -> Result<Block, Box<ParsingError>> {
+    let body = ParseBlock::start(self)?;
+    #[cfg(debug_assertions)]
+    validate::assert_valid_block(self, &body);
+    Ok(body)
+  }
+  /// Parses the bodies of all functions that were skipped
+  pub fn parse_bodies(&mut self) -> Result<(), Box<ParsingError>> {
+    for function in 0..self.functions.len() {
+      self.check_cancelled()?;
+      self.parse_body(function)?;
+    }
+    Ok(())
+  }
+  /// Returns the index of the function with this name, to be used with parse_body
+  pub fn function_index(&self, name: &str) -> Option<usize> {
+    self
+      .functions
+      .iter()
+      .position(|function| function.name.as_deref() == Some(name))
+  }
+}
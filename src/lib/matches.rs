@@ -0,0 +1,140 @@
+use super::*;
+
+/// `match foo { 1 => bar(), _ => baz() }`
+#[derive(Debug)]
+pub struct ActionMatch {
+  pub subject: Box<Action>,
+  pub arms: Vec<MatchArm>,
+}
+
+impl From<ActionMatch> for Action {
+  fn from(value: ActionMatch) -> Self {
+    Action::Match(value)
+  }
+}
+
+#[derive(Debug)]
+pub struct MatchArm {
+  pub pattern: MatchPattern,
+  /// The body of an arm like `1 => foo()` only contains the expression
+  pub body: Block,
+}
+
+#[derive(Debug)]
+pub enum MatchPattern {
+  /// `_` matches everything
+  Wildcard,
+  /// A static value like `1`, `-1`, `"foo"` or `true`
+  Literal(Action),
+  /// `Foo.Bar` or `Foo.Bar(a, b)` where a and b are bound to the data of the variant
+  EnumVariant {
+    enum_name: String,
+    variant: String,
+    bindings: Vec<String>,
+  },
+}
+
+/// Parses everything after the match keyword, like `foo { 1 => bar() }`
+pub fn parse_match(p: &mut Parser) -> Result<ActionMatch, Box<ParsingError>> {
+  let start = p.index;
+  let subject = ParseAction::start(p, false, ActionToExpect::Assignment("{"))?;
+  match p.next_while(" \t\n") {
+    Some('{') => {}
+    Some(c) => return p.unexpected_char(c),
+    None => return p.unexpected_eof(),
+  }
+
+  let mut arms = vec![];
+  loop {
+    match p.next_while(" \t\n") {
+      Some('}') => break,
+      Some(_) => p.index -= 1,
+      None => return p.unexpected_eof(),
+    }
+
+    let pattern = parse_pattern(p)?;
+    match p.next_while(" \t\n") {
+      Some('=') => {}
+      Some(c) => return p.unexpected_char(c),
+      None => return p.unexpected_eof(),
+    }
+    match p.next_char() {
+      Some('>') => {}
+      Some(c) => return p.unexpected_char(c),
+      None => return p.unexpected_eof(),
+    }
+
+    let body = match p.next_while(" \t\n") {
+      Some('{') => ParseBlock::start(p)?,
+      Some(',') | Some('}') => return p.error(ParsingErrorType::Custom("Missing match arm body")),
+      Some(_) => {
+        p.index -= 1;
+        let start = p.index;
+        let action = ParseAction::start(p, false, ActionToExpect::Assignment(",}"))?;
+        let action_span = p.trimmed_span(start, p.index);
+        Block::new(vec![action], vec![action_span], Span::new(start, p.index))
+      }
+      None => return p.unexpected_eof(),
+    };
+    arms.push(MatchArm { pattern, body });
+
+    // Arms can be separated by a comma
+    match p.next_while(" \t\n") {
+      Some(',') => {}
+      Some('}') => break,
+      Some(_) => p.index -= 1,
+      None => return p.unexpected_eof(),
+    }
+  }
+
+  p.trace_consumed("match", start);
+  Ok(ActionMatch {
+    subject: Box::new(subject),
+    arms,
+  })
+}
+
+fn parse_pattern(p: &mut Parser) -> Result<MatchPattern, Box<ParsingError>> {
+  let action = ParseAction::start(p, false, ActionToExpect::Assignment("="))?;
+  let pattern = match action {
+    Action::VarRef(ref name) if name == "_" => MatchPattern::Wildcard,
+    Action::StaticString(_)
+    | Action::StaticNumber(_)
+    | Action::StaticBoolean(_)
+    | Action::StaticNull => MatchPattern::Literal(action),
+    Action::UnaryOp(ref meta)
+      if matches!(meta.operator, UnaryOperator::Negate)
+        && matches!(*meta.action, Action::StaticNumber(_)) =>
+    {
+      MatchPattern::Literal(action)
+    }
+    Action::FieldAccess(meta) => match *meta.target {
+      Action::VarRef(enum_name) => MatchPattern::EnumVariant {
+        enum_name,
+        variant: meta.field,
+        bindings: vec![],
+      },
+      _ => return p.error(ParsingErrorType::Custom("Invalid match pattern")),
+    },
+    Action::MethodCall(meta) => {
+      let enum_name = match *meta.target {
+        Action::VarRef(name) => name,
+        _ => return p.error(ParsingErrorType::Custom("Invalid match pattern")),
+      };
+      let mut bindings = vec![];
+      for argument in meta.arguments {
+        match argument {
+          Action::VarRef(name) => bindings.push(name),
+          _ => return p.error(ParsingErrorType::Custom("Invalid match pattern")),
+        }
+      }
+      MatchPattern::EnumVariant {
+        enum_name,
+        variant: meta.name,
+        bindings,
+      }
+    }
+    _ => return p.error(ParsingErrorType::Custom("Invalid match pattern")),
+  };
+  Ok(pattern)
+}
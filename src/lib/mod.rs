@@ -1,25 +1,87 @@
+// Without the std feature the parser only needs core and alloc
+#![cfg_attr(not(feature = "std"), no_std)]
+
+extern crate alloc;
+
 mod action;
-mod actions;
+mod aliases;
+mod block;
+mod cancel;
+#[cfg(feature = "std")]
+pub mod crash;
+pub mod edit;
+mod enums;
 mod error;
+pub mod format;
 mod function;
+mod impls;
+mod imports;
+mod lazy;
+mod matches;
 mod numbers;
+mod operators;
+mod options;
 mod parser;
+mod plugins;
+#[cfg(feature = "std")]
+pub mod program;
+pub mod query;
+pub mod reachability;
+pub mod reduce;
+mod source_map;
+mod span;
 pub mod statics;
+pub mod stats;
 mod strings;
+mod structs;
+pub mod tags;
+mod trace;
+mod traits;
 mod types;
+pub mod validate;
 mod variable;
 
-pub use action::{Action, ActionToExpect, ParseAction, ParseActionState};
-pub use actions::{Actions, ParseActions};
+pub use action::{
+  Action, ActionFieldAccess, ActionFieldAssigment, ActionForClassic, ActionIndex, ActionMethodCall,
+  ActionToExpect, ActionTry, ActionTupleIndex, ParseAction, ParseActionState,
+};
+pub use aliases::{ParseTypeAlias, TypeAlias};
+pub use block::{Block, ParseBlock};
+pub use cancel::CancellationToken;
+pub use core::fmt::Display;
+pub use enums::{Enum, EnumVariant, ParseEnum};
 pub use error::{ParsingError, ParsingErrorType};
 pub use function::{Function, ParseFunction};
-pub use numbers::{Number, NumberParser, NumberTypes};
+pub use impls::{Impl, ParseImpl};
+pub use imports::{parse_import, Import, ImportPath};
+pub use matches::{parse_match, ActionMatch, MatchArm, MatchPattern};
+pub use numbers::{NumberLiteral, NumberParser, NumberType, NumberValue, Radix};
+pub use operators::{
+  ActionBinaryOp, ActionCast, ActionComparison, ActionLogical, ActionNullCoalesce, ActionRange,
+  ActionUnaryOp, BinaryOperator, ComparisonOperator, LogicalOperator, Operator, UnaryOperator,
+  OPERATOR_CHARS,
+};
+pub use options::{ExperimentalFeatures, ParserOptions, Version};
 pub use parser::{CodeLocation, Parser};
-pub use statics::{legal_name_char, Keywords, NameBuilder};
-pub use std::fmt::Display;
-pub use strings::{parse_static_str, String_};
+pub use plugins::{ParserPlugin, Plugins};
+pub use source_map::{SourceMap, Utf16Position};
+pub use span::Span;
+pub use statics::{legal_name_char, Ident, Keywords, Visibility};
+pub use strings::{parse_raw_str, parse_static_str, String_};
+pub use structs::{ActionStructInit, ParseStruct, Struct};
+pub use trace::TraceEvent;
+pub use traits::{ParseTrait, Trait};
 pub use types::{ParseType, Type};
-pub use variable::{parse_var, VarType, Variable};
+pub use variable::{
+  is_destructure, parse_destructure, parse_var, parse_var_until, ActionDestructure, VarType,
+  Variable,
+};
+
+// These are in the std prelude but not in the core prelude
+use alloc::boxed::Box;
+use alloc::string::{String, ToString};
+use alloc::vec::Vec;
+use alloc::{format, vec};
 
 #[cfg(test)]
 mod tests;
@@ -1,99 +1,308 @@
 use super::*;
 
-#[derive(Debug)]
-pub enum Number {
+/// A number written in the code like `10`, `1.5` or `0xffu8`
+#[derive(Debug, Clone, PartialEq)]
+pub struct NumberLiteral {
+  /// The number exactly as it was written in the code
+  pub raw: String,
+  pub value: NumberValue,
+  /// The type suffix of numbers like `10u8` or `1.5f32`
+  pub suffix: Option<NumberType>,
+  pub span: Span,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum NumberValue {
   /// This matches the default int number type of the programming language,
   /// Note that the size of this value might differ over multiple languages
-  Int(i64),
+  /// The radix is the base the number was written in
+  Int(i64, Radix),
 
   /// This matches the default float number type of the programming language,
   /// Note that the size of this value might differ over multiple languages
   Float(f64),
+
+  /// A int that doesn't fit in a i64 like `0xffffffffffffffffu64`,
+  /// The digits are kept as written without the prefix and suffix
+  Big(String, Radix),
 }
 
-pub enum NumberTypes {
-  /// Detects the correct type automaticly
-  Auto,
-  // Int,
-  // Float,
+/// The type suffix of a number like the `u8` in `10u8`
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum NumberType {
+  I8,
+  I16,
+  I32,
+  I64,
+  U8,
+  U16,
+  U32,
+  U64,
+  F32,
+  F64,
+}
+
+impl NumberType {
+  pub fn from_suffix(suffix: &str) -> Option<Self> {
+    Some(match suffix {
+      "i8" => Self::I8,
+      "i16" => Self::I16,
+      "i32" => Self::I32,
+      "i64" => Self::I64,
+      "u8" => Self::U8,
+      "u16" => Self::U16,
+      "u32" => Self::U32,
+      "u64" => Self::U64,
+      "f32" => Self::F32,
+      "f64" => Self::F64,
+      _ => return None,
+    })
+  }
+  pub fn is_float(self) -> bool {
+    matches!(self, Self::F32 | Self::F64)
+  }
+  pub fn is_signed(self) -> bool {
+    matches!(self, Self::I8 | Self::I16 | Self::I32 | Self::I64)
+  }
+  /// The largest int that fits in this type, floats return None
+  fn int_max(self) -> Option<u128> {
+    Some(match self {
+      Self::I8 => i8::MAX as u128,
+      Self::I16 => i16::MAX as u128,
+      Self::I32 => i32::MAX as u128,
+      Self::I64 => i64::MAX as u128,
+      Self::U8 => u8::MAX as u128,
+      Self::U16 => u16::MAX as u128,
+      Self::U32 => u32::MAX as u128,
+      Self::U64 => u64::MAX as u128,
+      Self::F32 | Self::F64 => return None,
+    })
+  }
+}
+
+impl From<NumberType> for &'static str {
+  fn from(value: NumberType) -> Self {
+    match value {
+      NumberType::I8 => "i8",
+      NumberType::I16 => "i16",
+      NumberType::I32 => "i32",
+      NumberType::I64 => "i64",
+      NumberType::U8 => "u8",
+      NumberType::U16 => "u16",
+      NumberType::U32 => "u32",
+      NumberType::U64 => "u64",
+      NumberType::F32 => "f32",
+      NumberType::F64 => "f64",
+    }
+  }
+}
+
+/// The base a int is written in, like hexadecimal for `0xff`
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Radix {
+  Binary,
+  Octal,
+  Decimal,
+  Hexadecimal,
+}
+
+impl Radix {
+  /// Detects the radix of a number based on its prefix, `0b`, `0o` and `0x`
+  pub fn from_prefix(number: &[u8]) -> Self {
+    match number {
+      [b'0', b'b', ..] => Self::Binary,
+      [b'0', b'o', ..] => Self::Octal,
+      [b'0', b'x', ..] => Self::Hexadecimal,
+      _ => Self::Decimal,
+    }
+  }
+  pub fn base(self) -> u32 {
+    match self {
+      Self::Binary => 2,
+      Self::Octal => 8,
+      Self::Decimal => 10,
+      Self::Hexadecimal => 16,
+    }
+  }
 }
 
-impl Into<Action> for Number {
-  fn into(self) -> Action {
-    Action::StaticNumber(self)
+impl From<NumberLiteral> for Action {
+  fn from(value: NumberLiteral) -> Self {
+    Action::StaticNumber(value)
   }
 }
 
 pub struct NumberParser<'a> {
   p: &'a mut Parser,
   buff: Vec<u8>,
+  start: usize,
+  /// The number is directly after a minus like `-128i8`
+  negated: bool,
 }
 
 impl<'a> NumberParser<'a> {
-  pub fn new_without_starting(p: &'a mut Parser, buff: Vec<u8>) -> Self {
-    Self { p, buff }
-  }
-  pub fn result(&self, type_: NumberTypes) -> Result<Number, ParsingError> {
-    Ok(match type_ {
-      // NumberTypes::Float => Number::Float(self.to_float()?),
-      // NumberTypes::Int => Number::Int(self.to_int()?),
-      NumberTypes::Auto => {
-        if self.buff.contains(&('.' as u8)) {
-          Number::Float(self.to_float()?)
-        } else {
-          Number::Int(self.to_int()?)
+  pub fn result(&self) -> Result<NumberLiteral, Box<ParsingError>> {
+    let raw = self.err(String::from_utf8(self.buff.clone()))?;
+    let radix = Radix::from_prefix(&self.buff);
+    let prefix_len = if radix == Radix::Decimal { 0 } else { 2 };
+
+    // The `f` of a hexadecimal number is a digit and not the start of a suffix
+    let suffix_start = raw[prefix_len..]
+      .find(|c| c == 'i' || c == 'u' || (c == 'f' && radix == Radix::Decimal))
+      .map(|index| index + prefix_len);
+    let (number, suffix) = match suffix_start {
+      Some(index) => match NumberType::from_suffix(&raw[index..]) {
+        Some(suffix) => (&raw[..index], Some(suffix)),
+        None => {
+          return self
+            .p
+            .error(ParsingErrorType::Custom("Unknown number suffix"))
         }
+      },
+      None => (raw.as_str(), None),
+    };
+    let number = &self.remove_separators(number, prefix_len, radix)?;
+
+    let is_float = radix == Radix::Decimal && number.contains(['.', 'e', 'E']);
+    let value = if is_float || suffix.is_some_and(NumberType::is_float) {
+      if suffix.is_some_and(|suffix| !suffix.is_float()) {
+        return self
+          .p
+          .error(ParsingErrorType::Custom("A float can't have a int suffix"));
       }
+      NumberValue::Float(self.to_float(number, suffix)?)
+    } else {
+      self.to_int(&number[prefix_len..], radix, suffix)?
+    };
+
+    Ok(NumberLiteral {
+      value,
+      suffix,
+      span: Span::new(self.start, self.p.index),
+      raw,
     })
   }
-  fn to_float(&self) -> Result<f64, ParsingError> {
-    self.err(self.to_string()?.parse::<f64>())
+  /// Removes the digit separators of numbers like `1_000_000`,
+  /// a separator is only allowed between two digits
+  fn remove_separators(
+    &self,
+    number: &str,
+    prefix_len: usize,
+    radix: Radix,
+  ) -> Result<String, Box<ParsingError>> {
+    let chars: Vec<char> = number.chars().collect();
+    let is_digit = |c: Option<&char>| c.is_some_and(|c| c.is_digit(radix.base()));
+    for (i, c) in chars.iter().enumerate() {
+      if *c == '_'
+        && (i <= prefix_len || !is_digit(chars.get(i - 1)) || !is_digit(chars.get(i + 1)))
+      {
+        return self.p.error(ParsingErrorType::Custom(
+          "A digit separator must be between two digits",
+        ));
+      }
+    }
+    Ok(number.replace('_', ""))
   }
-  fn to_int(&self) -> Result<i64, ParsingError> {
-    self.err(self.to_string()?.parse::<i64>())
+  fn to_float(&self, number: &str, suffix: Option<NumberType>) -> Result<f64, Box<ParsingError>> {
+    let value = self.err(number.parse::<f64>())?;
+    let max = match suffix {
+      Some(NumberType::F32) => f32::MAX as f64,
+      _ => f64::MAX,
+    };
+    if value > max {
+      return self
+        .p
+        .error(ParsingErrorType::Custom("Number is too large for its type"));
+    }
+    Ok(value)
+  }
+  fn to_int(
+    &self,
+    digits: &str,
+    radix: Radix,
+    suffix: Option<NumberType>,
+  ) -> Result<NumberValue, Box<ParsingError>> {
+    if digits.is_empty() {
+      return self
+        .p
+        .error(ParsingErrorType::Custom("Missing digits in number"));
+    }
+    if !digits.chars().all(|c| c.is_digit(radix.base())) {
+      return self.p.error(ParsingErrorType::Custom(match radix {
+        Radix::Binary => "Invalid digit in binary number",
+        Radix::Octal => "Invalid digit in octal number",
+        Radix::Decimal => "Invalid digit in number",
+        Radix::Hexadecimal => "Invalid digit in hexadecimal number",
+      }));
+    }
+    let value = match u128::from_str_radix(digits, radix.base()) {
+      Ok(value) => value,
+      Err(_) => {
+        return self
+          .p
+          .error(ParsingErrorType::Custom("Number is too large"))
+      }
+    };
+    if let Some(mut max) = suffix.and_then(NumberType::int_max) {
+      if self.negated && suffix.is_some_and(NumberType::is_signed) {
+        // The minimum of a signed type is one further from zero than its maximum, like `-128i8`
+        max += 1;
+      }
+      if value > max {
+        return self
+          .p
+          .error(ParsingErrorType::Custom("Number is too large for its type"));
+      }
+    }
+    Ok(if value <= i64::MAX as u128 {
+      NumberValue::Int(value as i64, radix)
+    } else {
+      NumberValue::Big(digits.to_string(), radix)
+    })
   }
-  fn err<T, E>(&self, err: Result<T, E>) -> Result<T, ParsingError> {
+  fn err<T, E>(&self, err: Result<T, E>) -> Result<T, Box<ParsingError>> {
     match err {
       Ok(v) => Ok(v),
       Err(_) => self.p.error(ParsingErrorType::Custom("Invalid number")),
     }
   }
-  fn to_string(&self) -> Result<String, ParsingError> {
-    self.err(String::from_utf8(self.buff.clone()))
-  }
 
-  /*
-    The parser is no where used so the code is commented out for now
-  */
-
-  // pub fn start(p: &'a mut Parser) -> Result<Self, ParsingError> {
-  //   let mut parser = Self { p, buff: vec![] };
-  //   parser.parse()?;
-  //   Ok(parser)
-  // }
-  // pub fn start_with_buffer(p: &'a mut Parser, buff: Vec<u8>) -> Result<Self, ParsingError> {
-  //   let mut parser = Self { p, buff };
-  //   parser.parse()?;
-  //   Ok(parser)
-  // }
-  // fn parse(&mut self) -> Result<(), ParsingError> {
-  //   if self.buff.len() > 0 {
-  //     if let None = self.p.next_while("\n\t ") {
-  //       return self.p.unexpected_eof();
-  //     };
-  //     self.p.index -= 1;
-  //   }
-
-  //   while let Some(c) = self.p.next_char() {
-  //     match c {
-  //       '.' if !self.buff.contains(&('.' as u8)) => {}
-  //       '1' | '2' | '3' | '4' | '5' | '6' | '7' | '8' | '9' | '0' => {}
-  //       _ => break,
-  //     }
-  //     self.buff.push(c as u8);
-  //   }
-
-  //   self.p.index -= 1;
-  //   Ok(())
-  // }
+  /// Lexes a number like `1`, `1.5`, `.5`, `2e-10` or `0xff` starting at the current index
+  pub fn start(p: &'a mut Parser) -> Result<Self, Box<ParsingError>> {
+    let start = p.index;
+    let mut buff: Vec<u8> = vec![];
+    while let Some(c) = p.seek_next_char() {
+      let decimal = Radix::from_prefix(&buff) == Radix::Decimal;
+      let has_exponent = buff.contains(&b'e') || buff.contains(&b'E');
+      let continues = match c {
+        _ if legal_name_char(c) => true,
+        // The second dot of `1..5` is the start of a range
+        '.' => {
+          decimal
+            && !has_exponent
+            && !buff.contains(&b'.')
+            && p.contents.get(p.index + 1) != Some(&b'.')
+        }
+        // The sign of a exponent like `2e-10`
+        '+' | '-' => decimal && matches!(buff.last(), Some(b'e') | Some(b'E')),
+        _ => false,
+      };
+      if !continues {
+        break;
+      }
+      buff.push(c as u8);
+      p.index += 1;
+    }
+    Ok(Self {
+      p,
+      buff,
+      start,
+      negated: false,
+    })
+  }
+  /// Marks the number as directly negated so the minimum of a signed type like `-128i8` fits
+  pub fn negated(mut self) -> Self {
+    self.negated = true;
+    self
+  }
 }
@@ -0,0 +1,336 @@
+use super::*;
+
+/// A arithmetic operator like `+`
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Operator {
+  Add,
+  Subtract,
+  Multiply,
+  Divide,
+  Remainder,
+}
+
+impl From<Operator> for &'static str {
+  fn from(value: Operator) -> Self {
+    match value {
+      Operator::Add => "+",
+      Operator::Subtract => "-",
+      Operator::Multiply => "*",
+      Operator::Divide => "/",
+      Operator::Remainder => "%",
+    }
+  }
+}
+
+/// A comparison operator like `==`
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum ComparisonOperator {
+  Equal,
+  NotEqual,
+  Less,
+  LessOrEqual,
+  Greater,
+  GreaterOrEqual,
+}
+
+impl From<ComparisonOperator> for &'static str {
+  fn from(value: ComparisonOperator) -> Self {
+    match value {
+      ComparisonOperator::Equal => "==",
+      ComparisonOperator::NotEqual => "!=",
+      ComparisonOperator::Less => "<",
+      ComparisonOperator::LessOrEqual => "<=",
+      ComparisonOperator::Greater => ">",
+      ComparisonOperator::GreaterOrEqual => ">=",
+    }
+  }
+}
+
+/// A logical operator like `&&`
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum LogicalOperator {
+  And,
+  Or,
+}
+
+impl From<LogicalOperator> for &'static str {
+  fn from(value: LogicalOperator) -> Self {
+    match value {
+      LogicalOperator::And => "&&",
+      LogicalOperator::Or => "||",
+    }
+  }
+}
+
+/// A operator placed before a action like `!`
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum UnaryOperator {
+  /// `-foo`
+  Negate,
+  /// `!foo`
+  Not,
+}
+
+impl From<UnaryOperator> for &'static str {
+  fn from(value: UnaryOperator) -> Self {
+    match value {
+      UnaryOperator::Negate => "-",
+      UnaryOperator::Not => "!",
+    }
+  }
+}
+
+/// Any operator that can be placed between two actions
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum BinaryOperator {
+  Arithmetic(Operator),
+  Comparison(ComparisonOperator),
+  Logical(LogicalOperator),
+  /// `a |> foo(b)` is desugared into `foo(a, b)`
+  Pipe,
+  /// `a ?? b`
+  NullCoalesce,
+  /// `a..b` or the inclusive `a..=b`
+  Range {
+    inclusive: bool,
+  },
+}
+
+/// All chars a operator can start with
+pub static OPERATOR_CHARS: &str = "+-*/%=!<>&|?";
+
+impl BinaryOperator {
+  /// Operators with a higher precedence are applied first,
+  /// for example `a + b * c` is parsed as `a + (b * c)`
+  pub fn precedence(&self) -> u8 {
+    match self {
+      Self::Pipe => 1,
+      Self::Range { .. } => 2,
+      Self::NullCoalesce => 3,
+      Self::Logical(LogicalOperator::Or) => 4,
+      Self::Logical(LogicalOperator::And) => 5,
+      Self::Comparison(_) => 6,
+      Self::Arithmetic(Operator::Add) | Self::Arithmetic(Operator::Subtract) => 7,
+      Self::Arithmetic(_) => 8,
+    }
+  }
+  /// Creates the action that applies this operator to the left and right side
+  /// The left span is only used by a pipe, the left side becomes the first argument of the call
+  pub fn into_action(
+    self,
+    p: &Parser,
+    left: Action,
+    left_span: Span,
+    right: Action,
+  ) -> Result<Action, Box<ParsingError>> {
+    if let Self::Pipe = self {
+      p.require_feature(ExperimentalFeatures::PIPES)?;
+      return match right {
+        Action::FunctionCall(mut meta) => {
+          meta.arguments.insert(0, left);
+          meta.argument_spans.insert(0, left_span);
+          Ok(meta.into())
+        }
+        _ => p.error(ParsingErrorType::Custom(
+          "Expected a function call after |>",
+        )),
+      };
+    }
+
+    if let (Self::Range { .. }, Action::Range(_)) = (self, &left) {
+      return p.error(ParsingErrorType::Custom("Ranges can't be chained"));
+    }
+
+    let left = Box::new(left);
+    let right = Box::new(right);
+    Ok(match self {
+      Self::Arithmetic(operator) => ActionBinaryOp {
+        operator,
+        left,
+        right,
+      }
+      .into(),
+      Self::Comparison(operator) => ActionComparison {
+        operator,
+        left,
+        right,
+      }
+      .into(),
+      Self::Logical(operator) => ActionLogical {
+        operator,
+        left,
+        right,
+      }
+      .into(),
+      Self::NullCoalesce => ActionNullCoalesce { left, right }.into(),
+      Self::Range { inclusive } => ActionRange {
+        start: left,
+        end: right,
+        inclusive,
+      }
+      .into(),
+      Self::Pipe => unreachable!(),
+    })
+  }
+}
+
+#[derive(Debug)]
+pub struct ActionBinaryOp {
+  pub operator: Operator,
+  pub left: Box<Action>,
+  pub right: Box<Action>,
+}
+
+impl From<ActionBinaryOp> for Action {
+  fn from(value: ActionBinaryOp) -> Self {
+    Action::BinaryOp(value)
+  }
+}
+
+#[derive(Debug)]
+pub struct ActionComparison {
+  pub operator: ComparisonOperator,
+  pub left: Box<Action>,
+  pub right: Box<Action>,
+}
+
+impl From<ActionComparison> for Action {
+  fn from(value: ActionComparison) -> Self {
+    Action::Comparison(value)
+  }
+}
+
+#[derive(Debug)]
+pub struct ActionLogical {
+  pub operator: LogicalOperator,
+  pub left: Box<Action>,
+  pub right: Box<Action>,
+}
+
+impl From<ActionLogical> for Action {
+  fn from(value: ActionLogical) -> Self {
+    Action::Logical(value)
+  }
+}
+
+#[derive(Debug)]
+pub struct ActionUnaryOp {
+  pub operator: UnaryOperator,
+  pub action: Box<Action>,
+}
+
+impl From<ActionUnaryOp> for Action {
+  fn from(value: ActionUnaryOp) -> Self {
+    Action::UnaryOp(value)
+  }
+}
+
+/// `foo as int` converts foo into the type
+#[derive(Debug)]
+pub struct ActionCast {
+  pub value: Box<Action>,
+  pub target_type: Type,
+}
+
+impl From<ActionCast> for Action {
+  fn from(value: ActionCast) -> Self {
+    Action::Cast(value)
+  }
+}
+
+/// `a ?? b` results in b if a is null
+#[derive(Debug)]
+pub struct ActionNullCoalesce {
+  pub left: Box<Action>,
+  pub right: Box<Action>,
+}
+
+impl From<ActionNullCoalesce> for Action {
+  fn from(value: ActionNullCoalesce) -> Self {
+    Action::NullCoalesce(value)
+  }
+}
+
+/// `a..b` goes from a up to but not including b, `a..=b` also includes b
+#[derive(Debug)]
+pub struct ActionRange {
+  pub start: Box<Action>,
+  pub end: Box<Action>,
+  pub inclusive: bool,
+}
+
+impl From<ActionRange> for Action {
+  fn from(value: ActionRange) -> Self {
+    Action::Range(value)
+  }
+}
+
+impl Parser {
+  /// Matches a binary operator after the current index
+  /// Newlines are not skipped as they end the action
+  /// If there is no operator the index is not changed
+  pub fn match_operator(&mut self) -> Option<BinaryOperator> {
+    let start = self.index;
+    let next_is_equal = |p: &mut Self| {
+      if let Some('=') = p.seek_next_char() {
+        p.index += 1;
+        true
+      } else {
+        false
+      }
+    };
+    let next_is = |p: &mut Self, c: char| {
+      if p.seek_next_char() == Some(c) {
+        p.index += 1;
+        true
+      } else {
+        false
+      }
+    };
+
+    let operator = match self.next_while(" \t") {
+      Some('+') => Some(BinaryOperator::Arithmetic(Operator::Add)),
+      Some('-') => Some(BinaryOperator::Arithmetic(Operator::Subtract)),
+      Some('*') => Some(BinaryOperator::Arithmetic(Operator::Multiply)),
+      Some('/') => Some(BinaryOperator::Arithmetic(Operator::Divide)),
+      Some('%') => Some(BinaryOperator::Arithmetic(Operator::Remainder)),
+      Some('=') if next_is_equal(self) => {
+        Some(BinaryOperator::Comparison(ComparisonOperator::Equal))
+      }
+      Some('!') if next_is_equal(self) => {
+        Some(BinaryOperator::Comparison(ComparisonOperator::NotEqual))
+      }
+      Some('<') if next_is_equal(self) => {
+        Some(BinaryOperator::Comparison(ComparisonOperator::LessOrEqual))
+      }
+      Some('<') => Some(BinaryOperator::Comparison(ComparisonOperator::Less)),
+      Some('>') if next_is_equal(self) => Some(BinaryOperator::Comparison(
+        ComparisonOperator::GreaterOrEqual,
+      )),
+      Some('>') => Some(BinaryOperator::Comparison(ComparisonOperator::Greater)),
+      Some('&') if next_is(self, '&') => Some(BinaryOperator::Logical(LogicalOperator::And)),
+      Some('|') if next_is(self, '|') => Some(BinaryOperator::Logical(LogicalOperator::Or)),
+      Some('|') if next_is(self, '>') => Some(BinaryOperator::Pipe),
+      Some('?') if next_is(self, '?') => Some(BinaryOperator::NullCoalesce),
+      Some('.') if next_is(self, '.') && self.seek_next_char() != Some('.') => {
+        Some(BinaryOperator::Range {
+          inclusive: next_is_equal(self),
+        })
+      }
+      _ => None,
+    };
+    if let Some(operator) = operator {
+      return Some(operator);
+    }
+
+    // A pipe is also allowed at the start of the next line
+    self.index = start;
+    if let Some('|') = self.next_while(" \t\n") {
+      if next_is(self, '>') {
+        return Some(BinaryOperator::Pipe);
+      }
+    }
+    self.index = start;
+    None
+  }
+}
@@ -0,0 +1,169 @@
+use super::*;
+
+/// Options that change the behavior of the parser
+/// The limits are useful when parsing untrusted input, by default there are no limits
+#[derive(Debug, Clone, Default)]
+pub struct ParserOptions {
+  /// The max amount of bytes a string literal can contain
+  pub max_string_len: Option<usize>,
+  /// The max amount of chars a name can contain
+  pub max_name_len: Option<usize>,
+  /// The max amount of arguments a function can have or a function call can be given
+  pub max_arguments: Option<usize>,
+  /// The max amount of bytes the contents and the parsed program can use,
+  /// this is a estimate based on the size of the parsed actions and strings
+  pub max_memory: Option<usize>,
+  /// The version of the language the code is written in,
+  /// a `#version 0.1` at the top of the code overrides this and by default the latest version is used
+  /// Only 0.1 exists so the grammar is the same for every version, rules that change in a later version should check Parser::version
+  pub version: Option<Version>,
+  /// Only parse the signatures of functions and skip their bodies,
+  /// a body can be parsed when it's needed with Parser::parse_body
+  pub lazy_bodies: bool,
+  /// Only allow constant values like `1 + 2` or `[1, 2]` in global consts,
+  /// so their value can be computed without running the program
+  pub constant_globals: bool,
+  /// The unstable syntax the parser accepts
+  pub experimental: ExperimentalFeatures,
+  /// Stops parsing with a Cancelled error when the token is cancelled or its timeout passed,
+  /// this also applies to the lazy bodies parsed later and to every file of a program
+  pub cancellation: Option<CancellationToken>,
+  /// Plugins that can change the contents before parsing and lint or rewrite the parsed program
+  pub plugins: Plugins,
+}
+
+/// Unstable syntax that is only parsed when its feature is enabled,
+/// by default no features are enabled and features can be combined with `|`
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ExperimentalFeatures(u32);
+
+impl ExperimentalFeatures {
+  pub const NONE: Self = Self(0);
+  /// `a |> foo(b)`
+  pub const PIPES: Self = Self(1 << 0);
+
+  pub fn contains(self, feature: Self) -> bool {
+    self.0 & feature.0 == feature.0
+  }
+  /// The name of a single feature, used in errors
+  pub fn name(self) -> &'static str {
+    match self {
+      Self::PIPES => "pipes",
+      _ => "unknown",
+    }
+  }
+}
+
+impl Default for ExperimentalFeatures {
+  fn default() -> Self {
+    Self::NONE
+  }
+}
+
+impl core::ops::BitOr for ExperimentalFeatures {
+  type Output = Self;
+  fn bitor(self, other: Self) -> Self {
+    Self(self.0 | other.0)
+  }
+}
+
+/// A version of the language like `0.1`
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub struct Version {
+  pub major: u32,
+  pub minor: u32,
+}
+
+impl Version {
+  pub const LATEST: Version = Version { major: 0, minor: 1 };
+  /// All versions the parser accepts, a `#version` pragma with another version is an error
+  pub const SUPPORTED: &'static [Version] = &[Version { major: 0, minor: 1 }];
+}
+
+impl Display for Version {
+  fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+    write!(f, "{}.{}", self.major, self.minor)
+  }
+}
+
+impl Parser {
+  /// Returns a error if the experimental feature is not enabled in the options
+  pub fn require_feature(&self, feature: ExperimentalFeatures) -> Result<(), Box<ParsingError>> {
+    if self.options.experimental.contains(feature) {
+      Ok(())
+    } else {
+      self.error(ParsingErrorType::ExperimentalFeature(feature.name()))
+    }
+  }
+  /// The version of the language the code is parsed as
+  pub fn version(&self) -> Version {
+    self.options.version.unwrap_or(Version::LATEST)
+  }
+  /// Parses the `version 0.1` part of a `#version 0.1` pragma
+  pub fn parse_version_pragma(&mut self) -> Result<(), Box<ParsingError>> {
+    self.expect("version")?;
+    match self.next_char() {
+      Some(' ') | Some('\t') => {}
+      Some(c) => return self.unexpected_char(c),
+      None => return self.unexpected_eof(),
+    }
+    match self.next_while(" \t") {
+      Some(c) if c.is_ascii_digit() => self.index -= 1,
+      Some(c) => return self.unexpected_char(c),
+      None => return self.unexpected_eof(),
+    }
+
+    let mut text = String::new();
+    while let Some(c) = self.seek_next_char() {
+      if !c.is_ascii_digit() && c != '.' {
+        break;
+      }
+      text.push(c);
+      self.index += 1;
+    }
+
+    let mut parts = text.split('.').map(|part| part.parse::<u32>());
+    let version = match (parts.next(), parts.next(), parts.next()) {
+      (Some(Ok(major)), Some(Ok(minor)), None) => Version { major, minor },
+      _ => {
+        return self.error(ParsingErrorType::Custom(
+          "Invalid version, expected a version like 0.1",
+        ))
+      }
+    };
+    if !Version::SUPPORTED.contains(&version) {
+      return self.error(ParsingErrorType::Custom("Unsupported language version"));
+    }
+
+    // The pragma is the only thing on its line
+    match self.next_while(" \t") {
+      Some('\n') | None => {}
+      Some(c) => return self.unexpected_char(c),
+    }
+    self.options.version = Some(version);
+    Ok(())
+  }
+  /// Adds bytes to the memory used by the parsed program,
+  /// returns an error if this goes over the memory limit
+  pub fn use_memory(&mut self, bytes: usize) -> Result<(), Box<ParsingError>> {
+    self.memory_used = self.memory_used.saturating_add(bytes);
+    match self.options.max_memory {
+      Some(max) if self.memory_used > max => {
+        self.error(ParsingErrorType::ResourceExhausted("memory"))
+      }
+      _ => Ok(()),
+    }
+  }
+  /// Returns an error if the len goes over the limit
+  pub fn check_limit(
+    &self,
+    limit: Option<usize>,
+    len: usize,
+    name: &'static str,
+  ) -> Result<(), Box<ParsingError>> {
+    match limit {
+      Some(max) if len > max => self.error(ParsingErrorType::LimitExceeded(name)),
+      _ => Ok(()),
+    }
+  }
+}
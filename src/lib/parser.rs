@@ -1,33 +1,48 @@
 use super::*;
-use std::collections::HashMap;
+#[cfg(feature = "std")]
+use std::panic;
 
+/// Parses the code and holds the parsed program
+/// The parsed program is Send + Sync so it can be shared between threads,
+/// keep interior mutability out of it (that belongs in the interpreter)
 #[derive(Debug)]
 pub struct Parser {
   pub index: usize,
   pub contents: Vec<u8>,
   pub functions: Vec<Function>,
-  pub global_vars: Vec<Variable>,
+  pub globals: Vec<Variable>,
+  pub structs: Vec<Struct>,
+  pub enums: Vec<Enum>,
+  pub imports: Vec<Import>,
+  pub impls: Vec<Impl>,
+  pub traits: Vec<Trait>,
+  pub type_aliases: Vec<TypeAlias>,
+  pub options: ParserOptions,
+  /// A estimate of the amount of bytes used by the contents and the parsed program
+  pub memory_used: usize,
+  #[cfg(feature = "trace-parse")]
+  pub trace: Vec<TraceEvent>,
 }
 
 impl Parser {
-  pub fn error<T>(&self, error_type: ParsingErrorType) -> Result<T, ParsingError> {
+  pub fn error<T>(&self, error_type: ParsingErrorType) -> Result<T, Box<ParsingError>> {
     self.custom_error(error_type, None)
   }
-  pub fn unexpected_char<T>(&self, c: char) -> Result<T, ParsingError> {
+  pub fn unexpected_char<T>(&self, c: char) -> Result<T, Box<ParsingError>> {
     self.error(ParsingErrorType::UnexpectedChar(c))
   }
-  pub fn unexpected_eof<T>(&self) -> Result<T, ParsingError> {
+  pub fn unexpected_eof<T>(&self) -> Result<T, Box<ParsingError>> {
     self.error(ParsingErrorType::UnexpectedEOF)
   }
   pub fn custom_error<T>(
     &self,
     error_type: ParsingErrorType,
     file_char_number: Option<usize>,
-  ) -> Result<T, ParsingError> {
+  ) -> Result<T, Box<ParsingError>> {
     let use_index = if let Some(index) = file_char_number {
       index
     } else {
-      self.index - 1
+      self.index.saturating_sub(1)
     };
     let mut line_number = 1;
     let mut current_line_position = 1;
@@ -55,7 +70,7 @@ impl Parser {
 
     let mut prev_line = None;
     if let Some(line_data) = prev_line_bytes {
-      prev_line = Some(String::from_utf8(line_data).unwrap())
+      prev_line = Some(String::from_utf8_lossy(&line_data).into_owned())
     }
 
     let mut next_line_bytes: Option<Vec<u8>> = None;
@@ -63,7 +78,7 @@ impl Parser {
     for letter in iterrator {
       match *letter as char {
         '\n' => {
-          if let Some(_) = next_line_bytes {
+          if next_line_bytes.is_some() {
             break;
           }
           next_line_bytes = Some(vec![]);
@@ -80,11 +95,7 @@ impl Parser {
       }
     }
 
-    let next_line = if let Some(bytes) = next_line_bytes {
-      Some(String::from_utf8(bytes).unwrap())
-    } else {
-      None
-    };
+    let next_line = next_line_bytes.map(|bytes| String::from_utf8_lossy(&bytes).into_owned());
 
     let res = ParsingError {
       location: CodeLocation {
@@ -94,27 +105,77 @@ impl Parser {
       },
       error_type,
       prev_line,
-      line: String::from_utf8(current_line).unwrap(),
-      next_line: next_line,
+      line: String::from_utf8_lossy(&current_line).into_owned(),
+      next_line,
     };
-    Err(res)
+    Err(Box::new(res))
   }
-  pub fn parse(contents: impl Into<Vec<u8>>) -> Result<Self, ParsingError> {
-    // this removes \r as it seems to cause problems during parsing
-    let mut tokens = contents.into();
-    for i in 0..tokens.len() {
-      if let Some(&13) = tokens.get(i) {
-        tokens.remove(i);
+  pub fn parse(contents: impl Into<Vec<u8>>) -> Result<Self, Box<ParsingError>> {
+    Self::parse_with_options(contents, ParserOptions::default())
+  }
+  /// Parses the contents, this never panics
+  /// A bug in the parser results in a InternalError at the location that was being parsed
+  ///
+  /// Panics can only be caught with the std feature, without it this is the same as parse_unguarded
+  pub fn parse_with_options(
+    contents: impl Into<Vec<u8>>,
+    options: ParserOptions,
+  ) -> Result<Self, Box<ParsingError>> {
+    #[cfg(not(feature = "std"))]
+    return Self::parse_unguarded(contents, options);
+
+    #[cfg(feature = "std")]
+    {
+      let mut parser = Self::new(contents, options);
+      let res = crash::catch_panic(panic::AssertUnwindSafe(|| parser.parse_and_validate()));
+      match res {
+        Ok(res) => res?,
+        Err(panic) => return parser.error(ParsingErrorType::InternalError(panic.message)),
       }
+      Ok(parser)
     }
-    let mut parser = Self {
+  }
+  /// Same as parse_with_options but bugs in the parser panic,
+  /// this is useful for tools that need the backtrace of the panic like crash reports
+  pub fn parse_unguarded(
+    contents: impl Into<Vec<u8>>,
+    options: ParserOptions,
+  ) -> Result<Self, Box<ParsingError>> {
+    let mut parser = Self::new(contents, options);
+    parser.parse_and_validate()?;
+    Ok(parser)
+  }
+  fn parse_and_validate(&mut self) -> Result<(), Box<ParsingError>> {
+    let plugins = self.options.plugins.clone();
+    plugins.pre_parse(&mut self.contents);
+    self.use_memory(self.contents.len())?;
+    self.parse_nothing()?;
+    plugins.post_parse(self)?;
+    #[cfg(debug_assertions)]
+    validate::assert_valid(self);
+    Ok(())
+  }
+  /// Creates a parser without parsing anything yet
+  pub fn new(contents: impl Into<Vec<u8>>, options: ParserOptions) -> Self {
+    // this removes \r as it seems to cause problems during parsing
+    let mut tokens = contents.into();
+    tokens.retain(|c| *c != b'\r');
+    Self {
       index: 0,
       contents: tokens,
       functions: vec![],
-      global_vars: vec![],
-    };
-    parser.parse_nothing()?;
-    Ok(parser)
+      globals: vec![],
+      structs: vec![],
+      enums: vec![],
+      imports: vec![],
+      impls: vec![],
+      traits: vec![],
+      type_aliases: vec![],
+      options,
+      memory_used: 0,
+      #[cfg(feature = "trace-parse")]
+      trace: vec![],
+    }
   }
   pub fn next_char(&mut self) -> Option<char> {
     let letter = *self.contents.get(self.index)? as char;
@@ -154,10 +215,26 @@ impl Parser {
           }
         }
       }
-      _ => return Some(letter),
+      _ => Some(letter),
     }
   }
-  fn seek_next_char(&mut self) -> Option<char> {
+  /// Returns the next char without skipping comments
+  pub fn next_raw_char(&mut self) -> Option<char> {
+    let letter = *self.contents.get(self.index)? as char;
+    self.index += 1;
+    Some(letter)
+  }
+  /// Returns true if a comment starts at the current index
+  fn comment_is_next(&self) -> bool {
+    matches!(
+      (
+        self.contents.get(self.index),
+        self.contents.get(self.index + 1),
+      ),
+      (Some(b'/'), Some(b'/')) | (Some(b'/'), Some(b'*'))
+    )
+  }
+  pub fn seek_next_char(&mut self) -> Option<char> {
     let letter = self.contents.get(self.index)?;
     Some(*letter as char)
   }
@@ -169,91 +246,161 @@ impl Parser {
     }
     None
   }
+  /// Skips whitespace and comments without consuming the char after them
+  /// Returns the index of the first char after the whitespace
+  pub fn skip_whitespace(&mut self) -> usize {
+    if self.next_while(" \t\n").is_some() {
+      self.index -= 1;
+    }
+    self.index
+  }
+  /// Returns the next char that isn't whitespace or a comment without consuming anything
+  pub fn peek_non_whitespace(&mut self) -> Option<char> {
+    let start = self.index;
+    let c = self.next_while(" \t\n");
+    self.index = start;
+    c
+  }
+  /// Consumes the next char if it is c, whitespace before it is not skipped
+  pub fn eat(&mut self, c: char) -> bool {
+    if self.seek_next_char() == Some(c) {
+      self.index += 1;
+      return true;
+    }
+    false
+  }
 
   /// Tries to match something
   /// The second string for the options array is for checking if the matched value has a certen surfix
   /// The next char after the matched value will be checked against it
   /// For example surfix "abc" will match the following matched string surfix: 'a', 'b' or 'c'
+  /// This is called for every statement so it doesn't allocate,
+  /// the options that still match are tracked as bits in a u64
   pub fn try_match<'a, T>(&mut self, options: &[(T, &'static str)]) -> Option<T>
   where
     T: Into<&'a str> + Copy,
   {
-    if options.len() == 0 {
-      return None;
-    }
-
-    let mut surfix_map: HashMap<&'a str, &'static str> = HashMap::with_capacity(options.len());
-    let mut options_vec: Vec<&str> = vec![];
-    for option in options.iter() {
-      if option.0.into().len() == 0 {
-        continue;
-      }
-      options_vec.push(&option.0.into());
-
-      if option.1.len() > 0 {
-        surfix_map.insert(option.0.into(), option.1);
+    debug_assert!(options.len() <= 64, "try_match supports up to 64 options");
+    let mut candidates: u64 = 0;
+    for (i, option) in options.iter().enumerate() {
+      if !option.0.into().is_empty() {
+        candidates |= 1 << i;
       }
     }
 
+    let start = self.index;
     let mut char_count: usize = 0;
-    while let Some(c) = self.next_char() {
-      let mut new_options_vec: Vec<&str> = vec![];
-      for option in options_vec {
-        if option.len() <= char_count {
+    while candidates != 0 {
+      let c = match self.next_char() {
+        Some(c) => c,
+        None => break,
+      };
+      for (i, option) in options.iter().enumerate() {
+        if candidates & (1 << i) == 0 {
+          continue;
+        }
+        let name: &str = option.0.into();
+        if name.as_bytes()[char_count] as char != c {
+          candidates &= !(1 << i);
+          continue;
+        }
+        if name.len() != char_count + 1 {
           continue;
         }
-        match option.as_bytes().get(char_count) {
-          Some(found_char) if *found_char as char == c => {
-            if option.len() != char_count + 1 {
-              new_options_vec.push(&option);
-              continue;
-            }
-
-            if let Some(must_match_surfix) = surfix_map.get(option) {
-              // This option contains a surfix match, lets test it here
-              let next_char = self.seek_next_char();
-              if let None = next_char {
-                continue;
-              } else if !must_match_surfix.contains(next_char.unwrap()) {
-                continue;
-              }
-            }
 
-            for opt in options {
-              if opt.0.into() == option {
-                return Some(opt.0);
-              }
-            }
-            return None;
+        // The full name matched so this option is done
+        candidates &= !(1 << i);
+        if !option.1.is_empty() && !self.comment_is_next() {
+          // This option contains a surfix match, lets test it here
+          // A comment directly after the matched value is always allowed
+          match self.seek_next_char() {
+            Some(next_char) if option.1.contains(next_char) => {}
+            _ => continue,
           }
-          _ => continue,
         }
+
+        return Some(option.0);
       }
-      if new_options_vec.len() == 0 {
-        break;
-      }
-      options_vec = new_options_vec;
       char_count += 1;
     }
 
     // Reset the index if we havent found the requested item
-    self.index -= char_count + 1;
+    let from = self.index;
+    self.index = start;
+    self.trace_backtracked(from);
     None
   }
-  fn parse_nothing(&mut self) -> Result<(), ParsingError> {
-    if let None = self.next_while(" \n\t") {
-      return Ok(());
+  fn parse_nothing(&mut self) -> Result<(), Box<ParsingError>> {
+    match self.next_while(" \n\t") {
+      // A `#version 0.1` pragma is only allowed at the top of the code
+      Some('#') => self.parse_version_pragma()?,
+      Some(_) => self.index -= 1,
+      None => return Ok(()),
     }
-    self.index -= 1;
-    while let Some(_) = self.next_while(" \n\t") {
+    while self.next_while(" \n\t").is_some() {
       self.index -= 1;
-      match self.try_match(&[(Keywords::Fn, " \t\n"), (Keywords::Const, " \t\n")]) {
-        Some(Keywords::Const) => {
-          let parsed_variable = parse_var(self, Some(VarType::Const))?;
-          self.global_vars.push(parsed_variable);
+      self.check_cancelled()?;
+      let item_start = self.index;
+      let visibility = match self.try_match(&[(Keywords::Pub, " \t\n")]) {
+        Some(_) => {
+          self.skip_whitespace();
+          Visibility::Public
+        }
+        None => Visibility::Private,
+      };
+      match self.try_match(&[
+        (Keywords::Fn, " \t\n"),
+        (Keywords::Const, " \t\n"),
+        (Keywords::Struct, " \t\n"),
+        (Keywords::Enum, " \t\n"),
+        (Keywords::Impl, " \t\n"),
+        (Keywords::Trait, " \t\n"),
+        (Keywords::Type, " \t\n"),
+        (Keywords::Import, " \t"),
+      ]) {
+        Some(Keywords::Impl)
+        | Some(Keywords::Trait)
+        | Some(Keywords::Type)
+        | Some(Keywords::Import)
+          if visibility.is_public() =>
+        {
+          return self.error(ParsingErrorType::Custom(
+            "pub can only be used before fn, struct, enum and const",
+          ));
+        }
+        Some(Keywords::Const) => self.parse_global(visibility)?,
+        Some(Keywords::Struct) => {
+          let mut parsed_struct = ParseStruct::start(self)?;
+          parsed_struct.visibility = visibility;
+          parsed_struct.span = self.trimmed_span(item_start, self.index);
+          self.structs.push(parsed_struct);
+        }
+        Some(Keywords::Enum) => {
+          let mut parsed_enum = ParseEnum::start(self)?;
+          parsed_enum.visibility = visibility;
+          parsed_enum.span = self.trimmed_span(item_start, self.index);
+          self.enums.push(parsed_enum);
+        }
+        Some(Keywords::Impl) => {
+          let parsed_impl = ParseImpl::start(self)?;
+          self.impls.push(parsed_impl);
+        }
+        Some(Keywords::Trait) => {
+          let parsed_trait = ParseTrait::start(self)?;
+          self.traits.push(parsed_trait);
+        }
+        Some(Keywords::Type) => {
+          let parsed_alias = ParseTypeAlias::start(self)?;
+          self.type_aliases.push(parsed_alias);
+        }
+        Some(Keywords::Import) => {
+          let parsed_import = parse_import(self)?;
+          self.imports.push(parsed_import);
         }
         Some(Keywords::Fn) => {
-          let parsed_function = ParseFunction::start(self)?;
+          let mut parsed_function = ParseFunction::start(self)?;
+          parsed_function.visibility = visibility;
+          parsed_function.span = self.trimmed_span(item_start, self.index);
           self.functions.push(parsed_function);
         }
         _ => {
@@ -269,7 +416,21 @@ impl Parser {
     Ok(())
   }
 
-  pub fn expect(&mut self, text: &str) -> Result<(), ParsingError> {
+  /// Parses a global like `const foo = 1 + 2`
+  fn parse_global(&mut self, visibility: Visibility) -> Result<(), Box<ParsingError>> {
+    let mut global = parse_var(self, Some(VarType::Const))?;
+    if self.options.constant_globals && !global.action.is_constant() {
+      self.index = global.action_span.start;
+      return self.error(ParsingErrorType::Custom(
+        "The value of a global const must be a constant like `1 + 2` or `[1, 2]`",
+      ));
+    }
+    global.visibility = visibility;
+    self.globals.push(global);
+    Ok(())
+  }
+
+  pub fn expect(&mut self, text: &str) -> Result<(), Box<ParsingError>> {
     for letter in text.chars() {
       match self.next_char() {
         Some(v) if v == letter => {}
@@ -280,32 +441,39 @@ impl Parser {
     Ok(())
   }
 
+  /// Lexes a identifier like `foo`, whitespace and comments before it are skipped
+  /// The identifier is empty if the next char can't be part of a identifier
+  pub fn next_ident(&mut self) -> Result<Ident, Box<ParsingError>> {
+    let start = self.skip_whitespace();
+    while let Some(c) = self.seek_next_char() {
+      if !legal_name_char(c) {
+        break;
+      }
+      self.index += 1;
+    }
+    let span = Span::new(start, self.index);
+    self.check_limit(self.options.max_name_len, span.len(), "name length")?;
+    if let Some(c) = self.contents.get(start) {
+      if !span.is_empty() && c.is_ascii_digit() {
+        return self.error(ParsingErrorType::Custom("name cannot start with a number"));
+      }
+    }
+    Ok(Ident {
+      // Identifiers only contain ascii chars so this never has to replace anything
+      name: self.span_text(span),
+      span,
+    })
+  }
+
   /*
       Functions written but not used so commented out
   */
 
-  // pub fn match_name(&mut self) -> Result<(String, usize), ParsingError> {
-  //   let mut name = NameBuilder::new();
-
-  //   while let Some(c) = self.next_char() {
-  //     match c {
-  //       ' ' | '\t' | '\n' if name.len() == 0 => {} // Ignore this char
-  //       _ if legal_name_char(c) => name.push(c),
-  //       _ => break,
-  //     }
-  //   }
-
-  //   self.index -= 1;
-  //   let name_len = name.len();
-  //   let res_name = name.to_string(self)?;
-  //   Ok((res_name, name_len))
-  // }
-
   // fn forward_until(
   //     &mut self,
   //     allowed_chars: impl Into<String>,
   //     until: char,
-  // ) -> Result<(), ParsingError> {
+  // ) -> Result<(), Box<ParsingError>> {
   //     let allowed_chars_string = allowed_chars.into();
   //     while let Some(c) = self.next_char() {
   //         if c == until {
@@ -0,0 +1,51 @@
+use super::*;
+use alloc::sync::Arc;
+
+/// A plugin that hooks into the parsing stage
+/// This allows external code to add custom lints or rewrite the parsed code without forking the parser
+///
+/// Plugins are shared between parsers through the options so the hooks take `&self`,
+/// a plugin that needs to keep state can use a Mutex or atomics
+pub trait ParserPlugin: Send + Sync {
+  /// Called with the raw file contents before the parsing starts
+  fn pre_parse(&self, _contents: &mut Vec<u8>) {}
+  /// Called with the parsed program after the parsing completed
+  /// Returning an error here makes the parsing fail with that error
+  fn post_parse(&self, _p: &mut Parser) -> Result<(), Box<ParsingError>> {
+    Ok(())
+  }
+}
+
+/// A list of registered plugins that are ran when parsing, these are set through ParserOptions.plugins
+///
+/// There is no CheckerPlugin yet as there is no checker, see docs/dev/Checker.md
+#[derive(Clone, Default)]
+pub struct Plugins {
+  parser: Vec<Arc<dyn ParserPlugin>>,
+}
+
+impl Plugins {
+  pub fn new() -> Self {
+    Self::default()
+  }
+  pub fn add_parser_plugin(&mut self, plugin: impl ParserPlugin + 'static) {
+    self.parser.push(Arc::new(plugin));
+  }
+  pub fn pre_parse(&self, contents: &mut Vec<u8>) {
+    for plugin in &self.parser {
+      plugin.pre_parse(contents);
+    }
+  }
+  pub fn post_parse(&self, p: &mut Parser) -> Result<(), Box<ParsingError>> {
+    for plugin in &self.parser {
+      plugin.post_parse(p)?;
+    }
+    Ok(())
+  }
+}
+
+impl core::fmt::Debug for Plugins {
+  fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+    write!(f, "Plugins({} parser plugins)", self.parser.len())
+  }
+}
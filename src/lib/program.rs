@@ -0,0 +1,230 @@
+use super::*;
+use std::fs;
+use std::path::{Component, Path, PathBuf};
+
+/// A program made out of multiple files that import each other
+#[derive(Debug)]
+pub struct Program {
+  /// The dir of the entry file, module imports like `import foo.bar` are resolved from here
+  pub root: String,
+  /// The parsed files by their resolved path, the entry file is always the first
+  pub files: Vec<(String, Parser)>,
+}
+
+impl Program {
+  pub fn file(&self, path: &str) -> Option<&Parser> {
+    self
+      .files
+      .iter()
+      .find(|(file_path, _)| file_path == path)
+      .map(|(_, parser)| parser)
+  }
+  /// Returns the function with this name from any of the files
+  pub fn function(&self, name: &str) -> Option<&Function> {
+    self
+      .files
+      .iter()
+      .flat_map(|(_, parser)| &parser.functions)
+      .find(|function| function.name.as_deref() == Some(name))
+  }
+  /// Returns the resolved paths of the files imported by every file
+  pub fn import_graph(&self) -> Vec<(&str, Vec<String>)> {
+    self
+      .files
+      .iter()
+      .map(|(path, parser)| {
+        let imports = parser
+          .imports
+          .iter()
+          .map(|import| resolve_import(&self.root, path, &import.path))
+          .collect();
+        (path.as_str(), imports)
+      })
+      .collect()
+  }
+  /// Returns the first circular import as the path of files that starts and ends with the same file,
+  /// like `["a.tp", "b.tp", "a.tp"]` when a imports b and b imports a
+  pub fn import_cycle(&self) -> Option<Vec<String>> {
+    let graph = self.import_graph();
+    let index_of = |path: &str| graph.iter().position(|(file, _)| *file == path);
+    let mut visited = vec![false; graph.len()];
+
+    for start in 0..graph.len() {
+      if visited[start] {
+        continue;
+      }
+      visited[start] = true;
+      // The files that are being visited with the index of the next import to check
+      let mut stack: Vec<(usize, usize)> = vec![(start, 0)];
+      while let Some(&(file, import)) = stack.last() {
+        let next = match graph[file].1.get(import) {
+          Some(path) => index_of(path),
+          None => {
+            stack.pop();
+            continue;
+          }
+        };
+        if let Some(last) = stack.last_mut() {
+          last.1 += 1;
+        }
+        let next = match next {
+          Some(next) => next,
+          None => continue,
+        };
+        if let Some(position) = stack.iter().position(|(file, _)| *file == next) {
+          let mut cycle: Vec<String> = stack[position..]
+            .iter()
+            .map(|(file, _)| graph[*file].0.to_string())
+            .collect();
+          cycle.push(graph[next].0.to_string());
+          return Some(cycle);
+        }
+        if !visited[next] {
+          visited[next] = true;
+          stack.push((next, 0));
+        }
+      }
+    }
+    None
+  }
+  /// Returns the import graph in the DOT format of graphviz
+  pub fn graph_dot(&self) -> String {
+    let mut res = String::from("digraph imports {\n");
+    for (path, imports) in self.import_graph() {
+      res.push_str(&format!("  {:?};\n", path));
+      for import in imports {
+        res.push_str(&format!("  {:?} -> {:?};\n", path, import));
+      }
+    }
+    res.push('}');
+    res
+  }
+  /// Returns the import graph as JSON like `{"imports": {"main.tp": ["utils.tp"]}, "cycle": null}`
+  pub fn graph_json(&self) -> String {
+    let files: Vec<String> = self
+      .import_graph()
+      .into_iter()
+      .map(|(path, imports)| format!("{:?}: {:?}", path, imports))
+      .collect();
+    let cycle = match self.import_cycle() {
+      Some(cycle) => format!("{:?}", cycle),
+      None => String::from("null"),
+    };
+    format!(
+      "{{\"imports\": {{{}}}, \"cycle\": {}}}",
+      files.join(", "),
+      cycle
+    )
+  }
+}
+
+/// Returns the path of the file a import refers to
+/// `import "other"` is resolved relative to the importer, `import foo.bar` to `foo/bar.tp` in the root
+pub fn resolve_import(root: &str, importer: &str, path: &ImportPath) -> String {
+  let path = match path {
+    ImportPath::File(file) => {
+      let dir = Path::new(importer).parent().unwrap_or(Path::new(""));
+      let mut path = dir.join(file);
+      if path.extension().is_none() {
+        path.set_extension("tp");
+      }
+      path
+    }
+    ImportPath::Module(parts) => {
+      let mut path = PathBuf::from(root);
+      path.extend(parts);
+      path.set_extension("tp");
+      path
+    }
+  };
+  normalize(&path)
+}
+
+/// Removes the `.` and `..` parts of a path so every file has one name
+fn normalize(path: &Path) -> String {
+  let mut parts: Vec<&str> = vec![];
+  for component in path.components() {
+    match component {
+      Component::CurDir => {}
+      // Joining a empty first part results in a path that starts with a /
+      Component::RootDir => parts.push(""),
+      Component::ParentDir if matches!(parts.last(), Some(last) if *last != ".." && !last.is_empty()) =>
+      {
+        parts.pop();
+      }
+      component => parts.push(component.as_os_str().to_str().unwrap_or("")),
+    }
+  }
+  parts.join("/")
+}
+
+/// Parses the entry file and all files it imports
+/// The contents of a file are loaded using load, this returns None if the file doesn't exist
+/// A file imported multiple times is only parsed once
+pub fn parse_program(
+  entry: &str,
+  options: ParserOptions,
+  mut load: impl FnMut(&str) -> Option<Vec<u8>>,
+) -> Result<Program, Box<ParsingError>> {
+  let entry = normalize(Path::new(entry));
+  let root = match Path::new(&entry).parent() {
+    Some(root) => root.to_string_lossy().into_owned(),
+    None => String::new(),
+  };
+
+  let mut program = Program {
+    root: root.clone(),
+    files: vec![],
+  };
+  let contents = match load(&entry) {
+    Some(contents) => contents,
+    None => {
+      return Parser::new("", options).error(ParsingErrorType::Custom("Entry file not found"))
+    }
+  };
+  let mut to_parse = vec![(entry, contents)];
+
+  while let Some((path, contents)) = to_parse.pop() {
+    let parser = match Parser::parse_with_options(contents, options.clone()) {
+      Ok(parser) => parser,
+      Err(mut err) => {
+        err.location.file_name = Some(path);
+        return Err(err);
+      }
+    };
+
+    for import in &parser.imports {
+      let import_path = resolve_import(&root, &path, &import.path);
+      let known = program.files.iter().any(|(file, _)| *file == import_path)
+        || to_parse.iter().any(|(file, _)| *file == import_path)
+        || import_path == path;
+      if known {
+        continue;
+      }
+      match load(&import_path) {
+        Some(contents) => to_parse.push((import_path, contents)),
+        None => {
+          let mut err = parser
+            .custom_error::<()>(
+              ParsingErrorType::Custom("Imported file not found"),
+              Some(import.span.start),
+            )
+            .unwrap_err();
+          err.location.file_name = Some(path);
+          return Err(err);
+        }
+      }
+    }
+    program.files.push((path, parser));
+  }
+
+  Ok(program)
+}
+
+/// Same as parse_program but loads the files from disk
+pub fn parse_program_from_disk(
+  entry: &str,
+  options: ParserOptions,
+) -> Result<Program, Box<ParsingError>> {
+  parse_program(entry, options, |path| fs::read(path).ok())
+}
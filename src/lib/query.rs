@@ -0,0 +1,341 @@
+use super::*;
+
+/// A structural pattern like `call(name="print")` or `assignment(target=$x, value=number)`
+#[derive(Debug)]
+pub struct Pattern {
+  pub kind: String,
+  pub fields: Vec<(String, PatternValue)>,
+}
+
+#[derive(Debug)]
+pub enum PatternValue {
+  /// `"foo"` matches a name or a static string with the same content
+  Text(String),
+  /// `$foo` matches anything and captures the matched value as foo
+  Capture(String),
+  /// `number` or `call(name="foo")` matches a nested action
+  Pattern(Pattern),
+}
+
+/// A value captured by a `$name` inside of a pattern
+#[derive(Debug, Clone, Copy)]
+pub enum Captured<'a> {
+  Name(&'a str),
+  Action(&'a Action),
+}
+
+#[derive(Debug)]
+pub struct QueryMatch<'a> {
+  pub action: &'a Action,
+  /// Where the action is inside of the code, a action without a span of its own like the `a + b` in `foo(a + b)`
+  /// gets the span of the closest parent that has one like the statement, variable value or argument it's part of
+  pub span: Span,
+  pub captures: Vec<(String, Captured<'a>)>,
+}
+
+/// Every kind a pattern can match, see kind for the action each kind matches
+pub static KINDS: &[&str] = &[
+  "call",
+  "assignment",
+  "variable",
+  "destructure",
+  "return",
+  "var_ref",
+  "string",
+  "number",
+  "boolean",
+  "null",
+  "for",
+  "while",
+  "do_while",
+  "try",
+  "loop",
+  "if",
+  "break",
+  "continue",
+  "defer",
+  "binary_op",
+  "comparison",
+  "logical",
+  "unary_op",
+  "discard",
+  "struct_init",
+  "array",
+  "map",
+  "tuple",
+  "method_call",
+  "field_access",
+  "field_assignment",
+  "index",
+  "tuple_index",
+  "null_coalesce",
+  "null_assert",
+  "cast",
+  "match",
+  "range",
+  "closure",
+  "function_decl",
+];
+
+/// Finds all actions inside of the parsed program that match the pattern
+/// Global variables are not actions themselves so only their values are searched
+pub fn find<'a>(
+  program: &'a Parser,
+  pattern: &str,
+) -> Result<Vec<QueryMatch<'a>>, Box<ParsingError>> {
+  let pattern = parse_pattern(pattern)?;
+  let mut matches = vec![];
+
+  let mut check = |action: &'a Action, span: Span| {
+    let mut captures = vec![];
+    if match_action(&pattern, action, &mut captures) {
+      matches.push(QueryMatch {
+        action,
+        span,
+        captures,
+      });
+    }
+  };
+  for var in &program.globals {
+    var.action.visit_spanned(var.action_span, &mut check);
+  }
+  for function in &program.functions {
+    function.body.visit_spanned(&mut check);
+  }
+  for impl_ in &program.impls {
+    for method in &impl_.methods {
+      method.body.visit_spanned(&mut check);
+    }
+  }
+
+  Ok(matches)
+}
+
+fn match_action<'a>(
+  pattern: &Pattern,
+  action: &'a Action,
+  captures: &mut Vec<(String, Captured<'a>)>,
+) -> bool {
+  if pattern.kind != kind(action) {
+    return false;
+  }
+
+  for (key, value) in &pattern.fields {
+    let matches = match field(action, key) {
+      Some(field) => match_value(value, field, captures),
+      None => false,
+    };
+    if !matches {
+      return false;
+    }
+  }
+  true
+}
+
+/// Returns the pattern kind that matches the action
+pub fn kind(action: &Action) -> &'static str {
+  match action {
+    Action::FunctionCall(_) => "call",
+    Action::Assigment(_) => "assignment",
+    Action::Variable(_) => "variable",
+    Action::Destructure(_) => "destructure",
+    Action::Return(_) => "return",
+    Action::VarRef(_) => "var_ref",
+    Action::StaticString(_) => "string",
+    Action::StaticNumber(_) => "number",
+    Action::StaticBoolean(_) => "boolean",
+    Action::StaticNull => "null",
+    Action::For(_) | Action::ForClassic(_) => "for",
+    Action::While(_) => "while",
+    Action::DoWhile(_) => "do_while",
+    Action::Try(_) => "try",
+    Action::Loop(_) => "loop",
+    Action::If(_) => "if",
+    Action::Break(_) => "break",
+    Action::Continue => "continue",
+    Action::Defer(_) => "defer",
+    Action::BinaryOp(_) => "binary_op",
+    Action::Comparison(_) => "comparison",
+    Action::Logical(_) => "logical",
+    Action::UnaryOp(_) => "unary_op",
+    Action::Discard(_) => "discard",
+    Action::StructInit(_) => "struct_init",
+    Action::ArrayInit(_) => "array",
+    Action::MapInit(_) => "map",
+    Action::TupleInit(_) => "tuple",
+    Action::MethodCall(_) => "method_call",
+    Action::FieldAccess(_) => "field_access",
+    Action::FieldAssigment(_) => "field_assignment",
+    Action::Index(_) => "index",
+    Action::TupleIndex(_) => "tuple_index",
+    Action::NullCoalesce(_) => "null_coalesce",
+    Action::NullAssert(_) => "null_assert",
+    Action::Cast(_) => "cast",
+    Action::Match(_) => "match",
+    Action::Range(_) => "range",
+    Action::Closure(_) => "closure",
+    Action::FunctionDecl(_) => "function_decl",
+  }
+}
+
+fn field<'a>(action: &'a Action, key: &str) -> Option<Captured<'a>> {
+  Some(match (action, key) {
+    (Action::FunctionCall(meta), "name") => Captured::Name(&meta.name),
+    (Action::Assigment(meta), "target") => Captured::Name(&meta.name),
+    (Action::Assigment(meta), "value") => Captured::Action(&meta.action),
+    (Action::Variable(meta), "name") => Captured::Name(&meta.name),
+    (Action::Variable(meta), "value") => Captured::Action(&meta.action),
+    (Action::Destructure(meta), "value") => Captured::Action(&meta.action),
+    (Action::Return(Some(action)), "value") => Captured::Action(action),
+    (Action::Break(Some(action)), "value") => Captured::Action(action),
+    (Action::Defer(action), "value") => Captured::Action(action),
+    (Action::VarRef(name), "name") => Captured::Name(name),
+    (Action::StaticString(meta), "value") => Captured::Name(&meta.content),
+    (Action::For(meta), "item") => Captured::Name(&meta.item_name),
+    (Action::For(meta), "list") => Captured::Action(&meta.list),
+    (Action::For(meta), "index") => Captured::Name(meta.index_name.as_ref()?),
+    (Action::While(meta), "condition") | (Action::DoWhile(meta), "condition") => {
+      Captured::Action(&meta.true_value)
+    }
+    (Action::If(meta), "condition") => Captured::Action(&meta.condition),
+    (Action::Try(meta), "error") => Captured::Name(meta.error_name.as_ref()?),
+    (Action::BinaryOp(meta), "operator") => Captured::Name(meta.operator.into()),
+    (Action::BinaryOp(meta), "left") => Captured::Action(&meta.left),
+    (Action::BinaryOp(meta), "right") => Captured::Action(&meta.right),
+    (Action::Comparison(meta), "operator") => Captured::Name(meta.operator.into()),
+    (Action::Comparison(meta), "left") => Captured::Action(&meta.left),
+    (Action::Comparison(meta), "right") => Captured::Action(&meta.right),
+    (Action::Logical(meta), "operator") => Captured::Name(meta.operator.into()),
+    (Action::Logical(meta), "left") => Captured::Action(&meta.left),
+    (Action::Logical(meta), "right") => Captured::Action(&meta.right),
+    (Action::UnaryOp(meta), "operator") => Captured::Name(meta.operator.into()),
+    (Action::UnaryOp(meta), "value") => Captured::Action(&meta.action),
+    (Action::Discard(action), "value") => Captured::Action(action),
+    (Action::StructInit(meta), "name") => Captured::Name(&meta.name),
+    (Action::MethodCall(meta), "name") => Captured::Name(&meta.name),
+    (Action::MethodCall(meta), "target") => Captured::Action(&meta.target),
+    (Action::FieldAccess(meta), "name") => Captured::Name(&meta.field),
+    (Action::FieldAccess(meta), "target") => Captured::Action(&meta.target),
+    (Action::FieldAssigment(meta), "name") => Captured::Name(&meta.field),
+    (Action::FieldAssigment(meta), "target") => Captured::Action(&meta.target),
+    (Action::FieldAssigment(meta), "value") => Captured::Action(&meta.action),
+    (Action::Index(meta), "target") => Captured::Action(&meta.target),
+    (Action::Index(meta), "index") => Captured::Action(&meta.index),
+    (Action::TupleIndex(meta), "target") => Captured::Action(&meta.target),
+    (Action::NullCoalesce(meta), "left") => Captured::Action(&meta.left),
+    (Action::NullCoalesce(meta), "right") => Captured::Action(&meta.right),
+    (Action::NullAssert(action), "value") => Captured::Action(action),
+    (Action::Cast(meta), "value") => Captured::Action(&meta.value),
+    (Action::Cast(meta), "type") => Captured::Name(&meta.target_type.name),
+    (Action::Match(meta), "subject") => Captured::Action(&meta.subject),
+    (Action::Range(meta), "start") => Captured::Action(&meta.start),
+    (Action::Range(meta), "end") => Captured::Action(&meta.end),
+    (Action::FunctionDecl(meta), "name") => Captured::Name(meta.name.as_ref()?),
+    _ => return None,
+  })
+}
+
+fn match_value<'a>(
+  value: &PatternValue,
+  field: Captured<'a>,
+  captures: &mut Vec<(String, Captured<'a>)>,
+) -> bool {
+  match (value, field) {
+    (PatternValue::Capture(name), field) => {
+      captures.push((name.clone(), field));
+      true
+    }
+    (PatternValue::Text(text), Captured::Name(name)) => text == name,
+    (PatternValue::Text(text), Captured::Action(Action::VarRef(name))) => text == name,
+    (PatternValue::Text(text), Captured::Action(Action::StaticString(meta))) => {
+      *text == meta.content
+    }
+    (PatternValue::Text(text), Captured::Action(Action::StaticBoolean(value))) => {
+      *text == value.to_string()
+    }
+    (PatternValue::Pattern(pattern), Captured::Action(action)) => {
+      match_action(pattern, action, captures)
+    }
+    _ => false,
+  }
+}
+
+/// Parses a pattern like `call(name="print")`
+pub fn parse_pattern(pattern: &str) -> Result<Pattern, Box<ParsingError>> {
+  let mut p = Parser::new(pattern, ParserOptions::default());
+  let res = parse_pattern_kind(&mut p)?;
+  match p.next_while(" \t\n") {
+    Some(c) => p.unexpected_char(c),
+    None => Ok(res),
+  }
+}
+
+fn parse_pattern_kind(p: &mut Parser) -> Result<Pattern, Box<ParsingError>> {
+  match p.next_while(" \t\n") {
+    Some(_) => p.index -= 1,
+    None => return p.unexpected_eof(),
+  }
+  let kind = parse_pattern_name(p)?;
+  if !KINDS.contains(&kind.as_str()) {
+    return p.error(ParsingErrorType::Custom("Unknown pattern kind"));
+  }
+
+  let mut res = Pattern {
+    kind,
+    fields: vec![],
+  };
+  match p.next_while(" \t\n") {
+    Some('(') => {}
+    Some(_) => {
+      p.index -= 1;
+      return Ok(res);
+    }
+    None => return Ok(res),
+  }
+
+  loop {
+    match p.next_while(" \t\n") {
+      Some(')') => break,
+      Some(_) => p.index -= 1,
+      None => return p.unexpected_eof(),
+    }
+
+    let key = parse_pattern_name(p)?;
+    match p.next_while(" \t\n") {
+      Some('=') => {}
+      Some(c) => return p.unexpected_char(c),
+      None => return p.unexpected_eof(),
+    }
+
+    let value = match p.next_while(" \t\n") {
+      Some('"') => PatternValue::Text(parse_static_str(p)?.content),
+      Some('$') => PatternValue::Capture(parse_pattern_name(p)?),
+      Some(_) => {
+        p.index -= 1;
+        PatternValue::Pattern(parse_pattern_kind(p)?)
+      }
+      None => return p.unexpected_eof(),
+    };
+    res.fields.push((key, value));
+
+    match p.next_while(" \t\n") {
+      Some(',') => {}
+      Some(')') => break,
+      Some(c) => return p.unexpected_char(c),
+      None => return p.unexpected_eof(),
+    }
+  }
+
+  Ok(res)
+}
+
+fn parse_pattern_name(p: &mut Parser) -> Result<String, Box<ParsingError>> {
+  let name = p.next_ident()?.name;
+  if name.is_empty() {
+    return match p.next_char() {
+      Some(c) => p.unexpected_char(c),
+      None => p.unexpected_eof(),
+    };
+  }
+  Ok(name)
+}
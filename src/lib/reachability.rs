@@ -0,0 +1,351 @@
+use super::*;
+#[cfg(feature = "std")]
+use program::Program;
+
+/// The functions, impl methods and structs of a file that can't be reached from the entry function,
+/// these are reported as warnings by `gpl check`
+#[derive(Debug, Default)]
+pub struct Unreachable<'a> {
+  pub functions: Vec<&'a Function>,
+  /// The methods together with the impl they are declared in
+  pub methods: Vec<(&'a Impl, &'a Function)>,
+  pub structs: Vec<&'a Struct>,
+}
+
+impl<'a> Unreachable<'a> {
+  pub fn is_empty(&self) -> bool {
+    self.functions.is_empty() && self.methods.is_empty() && self.structs.is_empty()
+  }
+}
+
+/// The items taken out of a file by remove_unreachable
+#[derive(Debug, Default)]
+pub struct Removed {
+  pub functions: Vec<Function>,
+  pub methods: Vec<Function>,
+  pub structs: Vec<Struct>,
+}
+
+/// Returns what can't be reached from the entry function of a single file without removing it
+pub fn unreachable<'a>(p: &'a Parser, entry: &str) -> Unreachable<'a> {
+  let reached = Reached::analyze(&[p], &[vec![]], entry);
+  reached.unreachable(p, 0)
+}
+
+/// Removes the functions, impl methods and structs that can't be reached from the entry function and returns them
+pub fn remove_unreachable(p: &mut Parser, entry: &str) -> Removed {
+  let reached = Reached::analyze(&[p], &[vec![]], entry);
+  reached.remove(p, 0)
+}
+
+/// Returns what can't be reached from the entry function in every file of the program,
+/// the entry function is looked up in the entry file
+#[cfg(feature = "std")]
+pub fn unreachable_in_program<'a>(
+  program: &'a Program,
+  entry: &str,
+) -> Vec<(&'a str, Unreachable<'a>)> {
+  let files: Vec<&Parser> = program.files.iter().map(|(_, p)| p).collect();
+  let reached = Reached::analyze(&files, &program_imports(program), entry);
+  program
+    .files
+    .iter()
+    .enumerate()
+    .map(|(i, (path, p))| (path.as_str(), reached.unreachable(p, i)))
+    .collect()
+}
+
+/// Removes what can't be reached from the entry function from every file of the program
+#[cfg(feature = "std")]
+pub fn remove_unreachable_from_program(
+  program: &mut Program,
+  entry: &str,
+) -> Vec<(String, Removed)> {
+  let files: Vec<&Parser> = program.files.iter().map(|(_, p)| p).collect();
+  let reached = Reached::analyze(&files, &program_imports(program), entry);
+  program
+    .files
+    .iter_mut()
+    .enumerate()
+    .map(|(i, (path, p))| (path.clone(), reached.remove(p, i)))
+    .collect()
+}
+
+/// The index of every file each file of the program imports
+#[cfg(feature = "std")]
+fn program_imports(program: &Program) -> Vec<Vec<usize>> {
+  program
+    .import_graph()
+    .into_iter()
+    .map(|(_, imports)| {
+      imports
+        .iter()
+        .filter_map(|import| program.files.iter().position(|(path, _)| path == import))
+        .collect()
+    })
+    .collect()
+}
+
+/// A name used by the code of a file
+enum Reference {
+  /// A function call or a name used as a value like `map(items, double)`,
+  /// the function can be called later so both are seen as a call
+  Name(String),
+  /// The name of a called method, the type of the value it's called on is not known yet
+  /// so `a.foo()` reaches the `foo` method of every reachable struct
+  Method(String),
+  /// A type like the `Foo` in `[]Foo` or `Foo{}`
+  Type(String),
+}
+
+/// What is reached by the index of the item inside of its file
+struct Reached {
+  functions: Vec<Vec<bool>>,
+  /// By impl and then by method
+  methods: Vec<Vec<Vec<bool>>>,
+  structs: Vec<Vec<bool>>,
+  /// The names of all reached structs, a impl can be in another file than its struct
+  struct_names: Vec<String>,
+}
+
+impl Reached {
+  /// Follows every reference from the entry function and the globals of every file
+  ///
+  /// A name used in a file refers to the items of that file and the public items of the files it imports
+  fn analyze(files: &[&Parser], imports: &[Vec<usize>], entry: &str) -> Self {
+    let mut reached = Self {
+      functions: files
+        .iter()
+        .map(|p| vec![false; p.functions.len()])
+        .collect(),
+      methods: files
+        .iter()
+        .map(|p| {
+          p.impls
+            .iter()
+            .map(|impl_| vec![false; impl_.methods.len()])
+            .collect()
+        })
+        .collect(),
+      structs: files.iter().map(|p| vec![false; p.structs.len()]).collect(),
+      struct_names: vec![],
+    };
+    let mut called_methods: Vec<String> = vec![];
+
+    let mut to_check: Vec<(usize, Reference)> = vec![(0, Reference::Name(String::from(entry)))];
+    for (file, p) in files.iter().enumerate() {
+      for var in &p.globals {
+        push_variable_references(file, var, &mut to_check);
+        var
+          .action
+          .visit(&mut |action| push_references(file, action, &mut to_check));
+      }
+    }
+
+    while let Some((file, reference)) = to_check.pop() {
+      match reference {
+        Reference::Name(name) => {
+          for target in visible_files(file, imports) {
+            for (i, function) in files[target].functions.iter().enumerate() {
+              let visible = target == file || function.visibility == Visibility::Public;
+              if !visible || reached.functions[target][i] || function.name.as_ref() != Some(&name) {
+                continue;
+              }
+              reached.functions[target][i] = true;
+              push_function_references(target, function, &mut to_check);
+            }
+          }
+        }
+        Reference::Type(name) => {
+          let mut found = false;
+          for target in visible_files(file, imports) {
+            for (i, struct_) in files[target].structs.iter().enumerate() {
+              let visible = target == file || struct_.visibility == Visibility::Public;
+              if !visible || reached.structs[target][i] || struct_.name != name {
+                continue;
+              }
+              reached.structs[target][i] = true;
+              for (_, type_) in &struct_.fields {
+                push_type_references(target, type_, &mut to_check);
+              }
+              found = true;
+            }
+          }
+          if found {
+            reached.struct_names.push(name);
+            reached.reach_methods(files, &called_methods, &mut to_check);
+          }
+        }
+        Reference::Method(name) => {
+          if !called_methods.contains(&name) {
+            called_methods.push(name);
+            reached.reach_methods(files, &called_methods, &mut to_check);
+          }
+        }
+      }
+    }
+
+    reached
+  }
+  /// Reaches the called methods of the impls of the reached structs
+  fn reach_methods(
+    &mut self,
+    files: &[&Parser],
+    called_methods: &[String],
+    to_check: &mut Vec<(usize, Reference)>,
+  ) {
+    for (file, p) in files.iter().enumerate() {
+      for (i, impl_) in p.impls.iter().enumerate() {
+        if !self.struct_names.contains(&impl_.struct_name) {
+          continue;
+        }
+        for (j, method) in impl_.methods.iter().enumerate() {
+          let called = match &method.name {
+            Some(name) => called_methods.contains(name),
+            None => false,
+          };
+          if called && !self.methods[file][i][j] {
+            self.methods[file][i][j] = true;
+            push_function_references(file, method, to_check);
+          }
+        }
+      }
+    }
+  }
+  fn unreachable<'a>(&self, p: &'a Parser, file: usize) -> Unreachable<'a> {
+    let mut res = Unreachable::default();
+    for (i, function) in p.functions.iter().enumerate() {
+      if !self.functions[file][i] {
+        res.functions.push(function);
+      }
+    }
+    for (i, impl_) in p.impls.iter().enumerate() {
+      for (j, method) in impl_.methods.iter().enumerate() {
+        if !self.methods[file][i][j] {
+          res.methods.push((impl_, method));
+        }
+      }
+    }
+    for (i, struct_) in p.structs.iter().enumerate() {
+      if !self.structs[file][i] {
+        res.structs.push(struct_);
+      }
+    }
+    res
+  }
+  fn remove(&self, p: &mut Parser, file: usize) -> Removed {
+    let mut removed = Removed::default();
+    let functions = &self.functions[file];
+    let (kept, unreachable) = split_by(p.functions.drain(..), |i| functions[i]);
+    p.functions = kept;
+    removed.functions = unreachable;
+
+    for (i, impl_) in p.impls.iter_mut().enumerate() {
+      let methods = &self.methods[file][i];
+      let (kept, mut unreachable) = split_by(impl_.methods.drain(..), |j| methods[j]);
+      impl_.methods = kept;
+      removed.methods.append(&mut unreachable);
+    }
+    // The impls of a struct that is never used are removed together with the struct
+    let struct_names = &self.struct_names;
+    p.impls
+      .retain(|impl_| struct_names.contains(&impl_.struct_name));
+
+    let structs = &self.structs[file];
+    let (kept, unreachable) = split_by(p.structs.drain(..), |i| structs[i]);
+    p.structs = kept;
+    removed.structs = unreachable;
+    removed
+  }
+}
+
+/// Splits the items into the ones to keep and the ones to remove by their index
+fn split_by<T>(items: impl Iterator<Item = T>, keep: impl Fn(usize) -> bool) -> (Vec<T>, Vec<T>) {
+  let mut kept = vec![];
+  let mut removed = vec![];
+  for (i, item) in items.enumerate() {
+    if keep(i) {
+      kept.push(item);
+    } else {
+      removed.push(item);
+    }
+  }
+  (kept, removed)
+}
+
+/// The file itself and the files it imports
+fn visible_files(file: usize, imports: &[Vec<usize>]) -> Vec<usize> {
+  let mut files = vec![file];
+  if let Some(imported) = imports.get(file) {
+    files.extend(imported.iter().filter(|imported| **imported != file));
+  }
+  files
+}
+
+/// Adds the references of the signature and the body of a function,
+/// this also walks into the closures and nested functions declared inside of the body
+fn push_function_references(
+  file: usize,
+  function: &Function,
+  to_check: &mut Vec<(usize, Reference)>,
+) {
+  push_signature_references(file, function, to_check);
+  function
+    .body
+    .visit(&mut |action| push_references(file, action, to_check));
+}
+
+fn push_signature_references(
+  file: usize,
+  function: &Function,
+  to_check: &mut Vec<(usize, Reference)>,
+) {
+  for (_, type_) in &function.args {
+    push_type_references(file, type_, to_check);
+  }
+  if let Some(type_) = &function.return_type {
+    push_type_references(file, type_, to_check);
+  }
+}
+
+fn push_variable_references(file: usize, var: &Variable, to_check: &mut Vec<(usize, Reference)>) {
+  if let Some(type_) = &var.data_type {
+    push_type_references(file, type_, to_check);
+  }
+}
+
+/// Adds the names the action uses, the actions nested inside of it are visited on their own
+fn push_references(file: usize, action: &Action, to_check: &mut Vec<(usize, Reference)>) {
+  match action {
+    Action::FunctionCall(meta) => to_check.push((file, Reference::Name(meta.name.clone()))),
+    // The name can also be a struct like the `Foo` in `Foo.new()`
+    Action::VarRef(name) => {
+      to_check.push((file, Reference::Name(name.clone())));
+      to_check.push((file, Reference::Type(name.clone())));
+    }
+    Action::MethodCall(meta) => to_check.push((file, Reference::Method(meta.name.clone()))),
+    Action::StructInit(meta) => to_check.push((file, Reference::Type(meta.name.clone()))),
+    Action::Cast(meta) => push_type_references(file, &meta.target_type, to_check),
+    Action::Variable(var) => push_variable_references(file, var, to_check),
+    Action::Closure(meta) | Action::FunctionDecl(meta) => {
+      push_signature_references(file, meta, to_check)
+    }
+    _ => {}
+  }
+}
+
+/// Adds every name inside of the type, like `Foo` and `Bar` in `map[Foo][]list<Bar>`
+fn push_type_references(file: usize, type_: &Type, to_check: &mut Vec<(usize, Reference)>) {
+  if let Some(map) = &type_.map {
+    push_type_references(file, &map.0, to_check);
+    push_type_references(file, &map.1, to_check);
+  }
+  for item in type_.tuple.iter().flatten() {
+    push_type_references(file, item, to_check);
+  }
+  for name in type_.name.split(|c: char| !legal_name_char(c)) {
+    if name.starts_with(|c: char| !c.is_ascii_digit()) {
+      to_check.push((file, Reference::Type(String::from(name))));
+    }
+  }
+}
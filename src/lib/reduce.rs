@@ -0,0 +1,79 @@
+use super::*;
+
+/// The way the parser fails on a input
+#[derive(Debug, PartialEq)]
+pub enum Failure {
+  Panic,
+  Error(ParsingErrorType),
+}
+
+/// Returns how the parser fails on the contents or None if the contents parse without problems
+pub fn failure(contents: &[u8]) -> Option<Failure> {
+  match Parser::parse(contents) {
+    Err(err) => match err.error_type {
+      ParsingErrorType::InternalError(_) => Some(Failure::Panic),
+      error_type => Some(Failure::Error(error_type)),
+    },
+    Ok(_) => None,
+  }
+}
+
+/// Shrinks the contents into the smallest input that still fails the same way as the original
+/// Returns None if the contents do not fail
+///
+/// This uses delta debugging, first over the lines and after that over the chars
+pub fn reduce(contents: &[u8]) -> Option<Vec<u8>> {
+  let expected = failure(contents)?;
+  let fails = |units: &[Vec<u8>]| failure(&units.concat()).as_ref() == Some(&expected);
+
+  let lines = contents
+    .split_inclusive(|c| *c == b'\n')
+    .map(|line| line.to_vec())
+    .collect();
+  let lines = ddmin(lines, &fails);
+
+  let chars = lines.concat().into_iter().map(|c| vec![c]).collect();
+  let chars = ddmin(chars, &fails);
+
+  Some(chars.concat())
+}
+
+fn ddmin(mut units: Vec<Vec<u8>>, fails: &impl Fn(&[Vec<u8>]) -> bool) -> Vec<Vec<u8>> {
+  let mut chunks_count = 2;
+  while units.len() >= 2 {
+    let chunk_size = units.len().div_ceil(chunks_count);
+    let mut reduced = false;
+
+    for start in (0..units.len()).step_by(chunk_size) {
+      let end = (start + chunk_size).min(units.len());
+
+      let subset = &units[start..end];
+      if fails(subset) {
+        units = subset.to_vec();
+        chunks_count = 2;
+        reduced = true;
+        break;
+      }
+
+      let complement: Vec<Vec<u8>> = units[..start]
+        .iter()
+        .chain(units[end..].iter())
+        .cloned()
+        .collect();
+      if fails(&complement) {
+        units = complement;
+        chunks_count = (chunks_count - 1).max(2);
+        reduced = true;
+        break;
+      }
+    }
+
+    if !reduced {
+      if chunks_count >= units.len() {
+        break;
+      }
+      chunks_count = (chunks_count * 2).min(units.len());
+    }
+  }
+  units
+}
@@ -0,0 +1,147 @@
+use super::*;
+
+/// A position the way LSP clients count it, both start at 0 and the character is in UTF-16 code units
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct Utf16Position {
+  pub line: usize,
+  pub character: usize,
+}
+
+/// Converts between the byte offsets used by the parser,
+/// char offsets and the UTF-16 line and column pairs used by LSP clients
+///
+/// A invalid utf8 sequence is counted as one char (and one UTF-16 code unit),
+/// just like the replacement char it becomes when the code is shown
+#[derive(Debug, Clone)]
+pub struct SourceMap {
+  contents: Vec<u8>,
+  /// The byte offset of the start of every line
+  line_starts: Vec<usize>,
+}
+
+impl SourceMap {
+  pub fn new(contents: impl Into<Vec<u8>>) -> Self {
+    let contents = contents.into();
+    let mut line_starts = vec![0];
+    for (index, c) in contents.iter().enumerate() {
+      if *c == b'\n' {
+        line_starts.push(index + 1);
+      }
+    }
+    Self {
+      contents,
+      line_starts,
+    }
+  }
+  pub fn line_count(&self) -> usize {
+    self.line_starts.len()
+  }
+  /// Returns the line the byte offset is on, starting at 0
+  pub fn line_of(&self, offset: usize) -> usize {
+    match self.line_starts.binary_search(&offset) {
+      Ok(line) => line,
+      Err(line) => line - 1,
+    }
+  }
+  /// Returns the amount of chars before the byte offset,
+  /// a offset inside of a multibyte char is moved back to the start of that char
+  pub fn byte_to_char(&self, offset: usize) -> usize {
+    chars(&self.contents)
+      .take_while(|c| c.offset + c.len <= offset)
+      .count()
+  }
+  /// Returns the byte offset of the char, None if there are not that many chars
+  pub fn char_to_byte(&self, char_offset: usize) -> Option<usize> {
+    match chars(&self.contents).nth(char_offset) {
+      Some(c) => Some(c.offset),
+      None if char_offset == self.byte_to_char(self.contents.len()) => Some(self.contents.len()),
+      None => None,
+    }
+  }
+  /// Returns the UTF-16 position of the byte offset,
+  /// a offset inside of a multibyte char is moved back to the start of that char
+  pub fn byte_to_utf16(&self, offset: usize) -> Utf16Position {
+    let offset = offset.min(self.contents.len());
+    let line = self.line_of(offset);
+    let line_start = self.line_starts[line];
+    // The whole line is decoded as the offset might be in the middle of a char
+    let character = chars(&self.contents[line_start..self.line_end(line)])
+      .take_while(|c| line_start + c.offset + c.len <= offset)
+      .map(|c| c.utf16_len)
+      .sum();
+    Utf16Position { line, character }
+  }
+  /// Returns the byte offset of the UTF-16 position,
+  /// None if the line doesn't exist or the character is past the end of the line or inside of a surrogate pair
+  pub fn utf16_to_byte(&self, position: Utf16Position) -> Option<usize> {
+    let line_start = *self.line_starts.get(position.line)?;
+    let line_end = self.line_end(position.line);
+
+    let mut character = 0;
+    for c in chars(&self.contents[line_start..line_end]) {
+      if character == position.character {
+        return Some(line_start + c.offset);
+      }
+      if character > position.character {
+        return None;
+      }
+      character += c.utf16_len;
+    }
+    if character == position.character {
+      Some(line_end)
+    } else {
+      None
+    }
+  }
+  /// Returns the byte offset of the end of the line without the newline
+  fn line_end(&self, line: usize) -> usize {
+    match self.line_starts.get(line + 1) {
+      Some(next_line) => next_line - 1,
+      None => self.contents.len(),
+    }
+  }
+  /// Returns the UTF-16 positions of the start and end of the span
+  pub fn span_to_utf16(&self, span: Span) -> (Utf16Position, Utf16Position) {
+    (self.byte_to_utf16(span.start), self.byte_to_utf16(span.end))
+  }
+}
+
+impl Parser {
+  /// A source map of the parsed contents, the byte offsets of the spans can be converted with it
+  /// The `\r` chars are removed before parsing so a client with `\r\n` line endings should remove them too
+  pub fn source_map(&self) -> SourceMap {
+    SourceMap::new(self.contents.clone())
+  }
+}
+
+struct SourceChar {
+  /// The byte offset of the char
+  offset: usize,
+  /// The amount of bytes of the char
+  len: usize,
+  utf16_len: usize,
+}
+
+/// Returns all chars inside of the bytes, every invalid utf8 sequence is one char
+fn chars(bytes: &[u8]) -> impl Iterator<Item = SourceChar> + '_ {
+  let mut offset = 0;
+  bytes.utf8_chunks().flat_map(move |chunk| {
+    let start = offset;
+    offset += chunk.valid().len() + chunk.invalid().len();
+    let valid = chunk
+      .valid()
+      .char_indices()
+      .map(move |(index, c)| SourceChar {
+        offset: start + index,
+        len: c.len_utf8(),
+        utf16_len: c.len_utf16(),
+      });
+    let invalid = Some(SourceChar {
+      offset: start + chunk.valid().len(),
+      len: chunk.invalid().len(),
+      utf16_len: 1,
+    })
+    .filter(|c| c.len > 0);
+    valid.chain(invalid)
+  })
+}
@@ -0,0 +1,67 @@
+use super::*;
+
+/// A range of bytes inside of the parsed contents, the end is exclusive
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct Span {
+  pub start: usize,
+  pub end: usize,
+}
+
+impl Span {
+  pub fn new(start: usize, end: usize) -> Self {
+    Self { start, end }
+  }
+  pub fn len(&self) -> usize {
+    self.end - self.start
+  }
+  pub fn is_empty(&self) -> bool {
+    self.start == self.end
+  }
+}
+
+impl Parser {
+  /// Returns a span from start to end without the whitespace and comments at the end,
+  /// useful when a sub parser consumed the whitespace after what it parsed
+  pub fn trimmed_span(&self, start: usize, end: usize) -> Span {
+    let code = &self.contents[..end];
+    let mut last = start;
+    let mut i = start;
+    while i < end {
+      match (code[i], code.get(i + 1)) {
+        (b' ', _) | (b'\t', _) | (b'\n', _) => i += 1,
+        (b'/', Some(b'/')) => {
+          while i < end && code[i] != b'\n' {
+            i += 1;
+          }
+        }
+        (b'/', Some(b'*')) => {
+          i += 2;
+          while i < end && !code[i..].starts_with(b"*/") {
+            i += 1;
+          }
+          i += 2;
+        }
+        (quote @ b'"', _) | (quote @ b'`', _) => {
+          i += 1;
+          while i < end && code[i] != quote {
+            if quote == b'"' && code[i] == b'\\' {
+              i += 1;
+            }
+            i += 1;
+          }
+          i += 1;
+          last = i.min(end);
+        }
+        _ => {
+          i += 1;
+          last = i;
+        }
+      }
+    }
+    Span::new(start, last)
+  }
+  /// Returns the code inside of the span
+  pub fn span_text(&self, span: Span) -> String {
+    String::from_utf8_lossy(&self.contents[span.start..span.end]).into_owned()
+  }
+}
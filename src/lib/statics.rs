@@ -1,51 +1,26 @@
 use super::*;
 
-pub static VALID_NAME_CHARS: &'static str =
+pub static VALID_NAME_CHARS: &str =
   "abcdefghijklmnopqrstuvwxyzABCDEFGHIJKLMNOPQRSTUVWXYZ1234567890_";
 
-#[derive(Debug)]
-pub struct NameBuilder(Vec<u8>);
+/// A identifier like `foo` together with where it is in the code
+#[derive(Debug, Clone, PartialEq)]
+pub struct Ident {
+  pub name: String,
+  pub span: Span,
+}
 
-impl NameBuilder {
-  pub fn new() -> Self {
-    Self(vec![])
-  }
-  pub fn new_with_char(first_char: char) -> Self {
-    Self(vec![first_char as u8])
-  }
-  pub fn is_number<'a>(&self, p: &'a mut Parser) -> Option<NumberParser<'a>> {
-    for letter in &self.0 {
-      match *letter as char {
-        '.' | '1' | '2' | '3' | '4' | '5' | '6' | '7' | '8' | '9' | '0' => {}
-        _ => return None,
-      }
-    }
-    let parser = NumberParser::new_without_starting(p, self.0.clone());
-    Some(parser)
-  }
-  pub fn to_string<'a>(&self, p: &'a Parser) -> Result<String, ParsingError> {
-    if self.len() == 0 {
-      return Ok(String::new());
-    }
-    if let Some(c) = self.0.get(0) {
-      match *c as char {
-        '1' | '2' | '3' | '4' | '5' | '6' | '7' | '8' | '9' | '0' => {
-          return p.error(ParsingErrorType::Custom("name cannot start with a number"))
-        }
-        _ => {}
-      }
-    }
+/// If a item can be used outside of the file it's defined in, public items start with `pub`
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub enum Visibility {
+  #[default]
+  Private,
+  Public,
+}
 
-    match String::from_utf8(self.0.clone()) {
-      Ok(parsed_string) => Ok(parsed_string),
-      Err(_) => p.error(ParsingErrorType::Custom("Invalid utf8 string")),
-    }
-  }
-  pub fn len(&self) -> usize {
-    self.0.len()
-  }
-  pub fn push(&mut self, value: char) {
-    self.0.push(value as u8);
+impl Visibility {
+  pub fn is_public(self) -> bool {
+    self == Self::Public
   }
 }
 
@@ -55,35 +30,65 @@ pub fn legal_name_char(c: char) -> bool {
 
 #[derive(Clone, Copy)]
 pub enum Keywords {
+  If,
   Fn,
+  Do,
+  As,
+  Try,
   Let,
+  Pub,
   For,
   Loop,
+  Else,
   Enum,
+  Impl,
+  Import,
   Type,
+  True,
+  Null,
   Const,
+  Catch,
+  False,
+  Match,
+  Trait,
   While,
   Break,
+  Defer,
   Return,
   Struct,
   Continue,
 }
 
-impl Into<&'static str> for Keywords {
-  fn into(self) -> &'static str {
-    match self {
-      Self::Fn => "fn",
-      Self::Let => "let",
-      Self::For => "for",
-      Self::Loop => "loop",
-      Self::Type => "type",
-      Self::Enum => "enum",
-      Self::Const => "const",
-      Self::While => "while",
-      Self::Break => "break",
-      Self::Struct => "struct",
-      Self::Return => "return",
-      Self::Continue => "continue",
+impl From<Keywords> for &'static str {
+  fn from(value: Keywords) -> Self {
+    match value {
+      Keywords::If => "if",
+      Keywords::Fn => "fn",
+      Keywords::Do => "do",
+      Keywords::As => "as",
+      Keywords::Try => "try",
+      Keywords::Let => "let",
+      Keywords::Pub => "pub",
+      Keywords::For => "for",
+      Keywords::Loop => "loop",
+      Keywords::Else => "else",
+      Keywords::Type => "type",
+      Keywords::Enum => "enum",
+      Keywords::Impl => "impl",
+      Keywords::Import => "import",
+      Keywords::True => "true",
+      Keywords::Null => "null",
+      Keywords::Const => "const",
+      Keywords::Catch => "catch",
+      Keywords::False => "false",
+      Keywords::Match => "match",
+      Keywords::Trait => "trait",
+      Keywords::While => "while",
+      Keywords::Break => "break",
+      Keywords::Defer => "defer",
+      Keywords::Struct => "struct",
+      Keywords::Return => "return",
+      Keywords::Continue => "continue",
     }
   }
 }
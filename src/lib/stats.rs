@@ -0,0 +1,96 @@
+use super::*;
+
+/// Size and complexity metrics of a parsed program
+#[derive(Debug)]
+pub struct Stats {
+  pub lines: usize,
+  pub functions: Vec<FunctionStats>,
+}
+
+#[derive(Debug)]
+pub struct FunctionStats {
+  pub name: Option<String>,
+  /// The length of the function, there are no spans yet so this is the amount of statements
+  /// including the statements inside of nested bodies
+  pub statements: usize,
+  /// 1 plus the amount of branches, like `if`, loops, match arms, `&&` and `||`
+  pub complexity: usize,
+  /// The deepest amount of nested `if`, `for`, `while`, `loop` and match arm bodies
+  pub max_nesting: usize,
+}
+
+impl Stats {
+  pub fn longest_function(&self) -> Option<&FunctionStats> {
+    self
+      .functions
+      .iter()
+      .max_by_key(|function| function.statements)
+  }
+}
+
+/// Collects the metrics of a program
+pub fn stats(p: &Parser) -> Stats {
+  Stats {
+    lines: String::from_utf8_lossy(&p.contents).lines().count(),
+    functions: p.functions.iter().map(function_stats).collect(),
+  }
+}
+
+pub fn function_stats(function: &Function) -> FunctionStats {
+  let mut complexity = 1;
+  function.body.visit(&mut |action| {
+    complexity += match action {
+      Action::If(meta) => 1 + meta.else_ifs.len(),
+      Action::For(_)
+      | Action::ForClassic(_)
+      | Action::While(_)
+      | Action::DoWhile(_)
+      | Action::Loop(_) => 1,
+      // The catch body is a extra path through the function
+      Action::Try(_) => 1,
+      Action::Logical(_) | Action::NullCoalesce(_) => 1,
+      Action::Match(meta) => meta.arms.len().saturating_sub(1),
+      _ => 0,
+    };
+  });
+
+  let (statements, max_nesting) = block_stats(&function.body);
+  FunctionStats {
+    name: function.name.clone(),
+    statements,
+    complexity,
+    max_nesting,
+  }
+}
+
+/// Returns the amount of statements and the max nesting inside of a body
+fn block_stats(block: &Block) -> (usize, usize) {
+  let mut statements = 0;
+  let mut max_nesting = 0;
+
+  for action in &block.actions {
+    statements += 1;
+    let bodies: Vec<&Block> = match action {
+      Action::For(meta) => vec![&meta.body],
+      Action::ForClassic(meta) => vec![&meta.body],
+      Action::While(meta) | Action::DoWhile(meta) => vec![&meta.body],
+      Action::Loop(body) => vec![body],
+      Action::Try(meta) => vec![&meta.body, &meta.catch_body],
+      Action::If(meta) => {
+        let mut bodies = vec![&meta.body];
+        bodies.extend(meta.else_ifs.iter().map(|(_, body)| body));
+        bodies.extend(&meta.else_body);
+        bodies
+      }
+      Action::Match(meta) => meta.arms.iter().map(|arm| &arm.body).collect(),
+      _ => vec![],
+    };
+    for body in bodies {
+      let (body_statements, body_nesting) = block_stats(body);
+      statements += body_statements;
+      max_nesting = max_nesting.max(body_nesting + 1);
+    }
+  }
+
+  (statements, max_nesting)
+}
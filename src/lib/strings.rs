@@ -2,36 +2,177 @@ use super::*;
 
 #[derive(Debug)]
 pub struct String_ {
+  /// The cooked value of the string, escape sequences like `\n` are already replaced
   pub content: String,
+  /// A raw string like `` `C:\path` `` can span multiple lines and its content is used as is
+  pub raw: bool,
+  /// The string exactly as written in the code including the quotes,
+  /// a formatter can print this to keep the escape sequences as they are
+  pub source: String,
+  pub span: Span,
 }
 
-impl Into<Action> for String_ {
-  fn into(self) -> Action {
-    Action::StaticString(self)
+impl String_ {
+  /// Returns the content as a double quoted string with escape sequences,
+  /// for backends that output code in a language that uses C like string escapes
+  pub fn quoted(&self) -> String {
+    let mut res = String::from("\"");
+    for c in self.content.chars() {
+      match c {
+        '"' => res.push_str("\\\""),
+        '\\' => res.push_str("\\\\"),
+        '\n' => res.push_str("\\n"),
+        '\t' => res.push_str("\\t"),
+        '\r' => res.push_str("\\r"),
+        '\0' => res.push_str("\\0"),
+        c if c.is_control() => res.push_str(&format!("\\u{{{:x}}}", c as u32)),
+        c => res.push(c),
+      }
+    }
+    res.push('"');
+    res
+  }
+  /// Returns the utf8 bytes of the content ending with a nul byte,
+  /// for backends that put strings in a data section like C and wasm
+  pub fn nul_terminated(&self) -> Vec<u8> {
+    let mut res = Vec::with_capacity(self.content.len() + 1);
+    res.extend_from_slice(self.content.as_bytes());
+    res.push(0);
+    res
   }
 }
 
-pub fn parse_static_str<'a>(p: &'a mut Parser) -> Result<String_, ParsingError> {
-  let mut res = String_ {
-    content: String::new(),
-  };
+impl From<String_> for Action {
+  fn from(value: String_) -> Self {
+    Action::StaticString(value)
+  }
+}
+
+pub fn parse_static_str(p: &mut Parser) -> Result<String_, Box<ParsingError>> {
+  let start = p.index;
   let mut string_content: Vec<u8> = vec![];
 
-  let mut escaped = false;
-  while let Some(c) = p.next_char() {
+  while let Some(c) = p.next_raw_char() {
     match c {
-      '\\' if !escaped => escaped = true,
-      '"' if !escaped => {
-        res.content = String::from_utf8(string_content).unwrap();
-        return Ok(res);
-      }
-      _ => {
-        string_content.push(c as u8);
-        if escaped {
-          escaped = false;
+      '"' => {
+        p.use_memory(string_content.len())?;
+        let content = content_to_string(p, string_content)?;
+        p.trace_consumed("string", start);
+        return Ok(new_string(p, content, false, start));
+      }
+      '\\' => {
+        let decoded = parse_escape(p)?;
+        let mut buffer = [0; 4];
+        string_content.extend_from_slice(decoded.encode_utf8(&mut buffer).as_bytes());
+      }
+      _ => string_content.push(c as u8),
+    }
+    p.check_limit(
+      p.options.max_string_len,
+      string_content.len(),
+      "string length",
+    )?;
+  }
+
+  p.unexpected_eof()
+}
+
+/// Parses the escape sequence after a `\` like the `n` of `\n` and returns the char it represents
+fn parse_escape(p: &mut Parser) -> Result<char, Box<ParsingError>> {
+  let c = match p.next_raw_char() {
+    Some(c) => c,
+    None => return p.unexpected_eof(),
+  };
+  Ok(match c {
+    'n' => '\n',
+    't' => '\t',
+    'r' => '\r',
+    '0' => '\0',
+    '\\' => '\\',
+    '"' => '"',
+    'x' => {
+      // `\x41`, only ascii chars are allowed so the string stays valid utf8
+      let mut code = 0;
+      for _ in 0..2 {
+        code = code * 16 + parse_hex_digit(p)?;
+      }
+      if code > 0x7f {
+        return p.error(ParsingErrorType::InvalidEscape);
+      }
+      code as u8 as char
+    }
+    'u' => {
+      // `\u{1F600}` with 1 up to 6 hex digits
+      match p.next_raw_char() {
+        Some('{') => {}
+        Some(_) => return p.error(ParsingErrorType::InvalidEscape),
+        None => return p.unexpected_eof(),
+      }
+      let mut code = 0;
+      let mut digits = 0;
+      while p.seek_next_char() != Some('}') || digits == 0 {
+        if digits == 6 {
+          return p.error(ParsingErrorType::InvalidEscape);
         }
+        code = code * 16 + parse_hex_digit(p)?;
+        digits += 1;
+      }
+      p.index += 1;
+      match char::from_u32(code) {
+        Some(c) => c,
+        None => return p.error(ParsingErrorType::InvalidEscape),
       }
     }
+    _ => return p.error(ParsingErrorType::InvalidEscape),
+  })
+}
+
+fn parse_hex_digit(p: &mut Parser) -> Result<u32, Box<ParsingError>> {
+  match p.next_raw_char() {
+    Some(c) => match c.to_digit(16) {
+      Some(digit) => Ok(digit),
+      None => p.error(ParsingErrorType::InvalidEscape),
+    },
+    None => p.unexpected_eof(),
+  }
+}
+
+/// Creates a string that started at start, this is the index after the opening quote
+fn new_string(p: &Parser, content: String, raw: bool, start: usize) -> String_ {
+  let span = Span::new(start - 1, p.index);
+  String_ {
+    content,
+    raw,
+    source: p.span_text(span),
+    span,
+  }
+}
+
+fn content_to_string(p: &Parser, content: Vec<u8>) -> Result<String, Box<ParsingError>> {
+  match String::from_utf8(content) {
+    Ok(content) => Ok(content),
+    Err(_) => p.error(ParsingErrorType::Custom("Invalid utf8 string")),
+  }
+}
+
+/// Parses a raw string like `` `C:\path` ``, the content is not checked for escape sequences
+pub fn parse_raw_str(p: &mut Parser) -> Result<String_, Box<ParsingError>> {
+  let start = p.index;
+  let mut string_content: Vec<u8> = vec![];
+
+  while let Some(c) = p.next_raw_char() {
+    if c == '`' {
+      p.use_memory(string_content.len())?;
+      p.trace_consumed("raw string", start);
+      let content = content_to_string(p, string_content)?;
+      return Ok(new_string(p, content, true, start));
+    }
+    string_content.push(c as u8);
+    p.check_limit(
+      p.options.max_string_len,
+      string_content.len(),
+      "string length",
+    )?;
   }
 
   p.unexpected_eof()
@@ -0,0 +1,92 @@
+use super::*;
+
+#[derive(Debug)]
+pub struct Struct {
+  pub name: String,
+  pub fields: Vec<(String, Type)>,
+  pub visibility: Visibility,
+  /// From the `struct` (or the `pub` before it) up to and including the `}`
+  pub span: Span,
+}
+
+#[derive(Debug)]
+pub struct ActionStructInit {
+  pub name: String,
+  pub fields: Vec<(String, Action)>,
+}
+
+impl From<ActionStructInit> for Action {
+  fn from(value: ActionStructInit) -> Self {
+    Action::StructInit(value)
+  }
+}
+
+pub struct ParseStruct<'a> {
+  p: &'a mut Parser,
+  res: Struct,
+}
+
+impl<'a> ParseStruct<'a> {
+  pub fn start(p: &'a mut Parser) -> Result<Struct, Box<ParsingError>> {
+    let start = p.index;
+    let mut s = Self {
+      p,
+      res: Struct {
+        name: String::new(),
+        fields: vec![],
+        visibility: Visibility::Private,
+        span: Span::default(),
+      },
+    };
+    s.parse()?;
+    s.p.trace_consumed("struct", start);
+    Ok(s.res)
+  }
+  fn parse(&mut self) -> Result<(), Box<ParsingError>> {
+    let name = self.p.next_ident()?.name;
+    if name.is_empty() {
+      return self
+        .p
+        .error(ParsingErrorType::Custom("Missing struct name"));
+    }
+    self.res.name = name;
+
+    match self.p.next_while(" \t\n") {
+      Some('{') => {}
+      Some(c) => return self.p.unexpected_char(c),
+      None => return self.p.unexpected_eof(),
+    }
+
+    loop {
+      match self.p.next_while(" \t\n") {
+        Some('}') => return Ok(()),
+        Some(c) if legal_name_char(c) => self.p.index -= 1,
+        Some(c) => return self.p.unexpected_char(c),
+        None => return self.p.unexpected_eof(),
+      }
+
+      let field_name = self.p.next_ident()?.name;
+      if self.res.fields.iter().any(|(name, _)| *name == field_name) {
+        return self
+          .p
+          .error(ParsingErrorType::Custom("Duplicate field name"));
+      }
+
+      match self.p.next_while(" \t") {
+        Some(':') => {}
+        Some(c) => return self.p.unexpected_char(c),
+        None => return self.p.unexpected_eof(),
+      }
+      let field_type = ParseType::start(self.p, false)?;
+      self.res.fields.push((field_name, field_type));
+
+      // Fields are separated by a comma or a newline
+      match self.p.next_while(" \t") {
+        Some(',') | Some('\n') => {}
+        Some('}') => return Ok(()),
+        Some(c) => return self.p.unexpected_char(c),
+        None => return self.p.unexpected_eof(),
+      }
+    }
+  }
+}
@@ -0,0 +1,123 @@
+use super::*;
+
+/// The kind of definition a tag points to
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum TagKind {
+  Function,
+  Struct,
+  Enum,
+  Global,
+}
+
+impl TagKind {
+  /// The single letter kind used inside of a ctags file
+  pub fn letter(self) -> char {
+    match self {
+      Self::Function => 'f',
+      Self::Struct => 's',
+      Self::Enum => 'e',
+      Self::Global => 'v',
+    }
+  }
+}
+
+/// A definition editors can jump to
+#[derive(Debug, Clone, PartialEq)]
+pub struct Tag {
+  pub name: String,
+  pub file: String,
+  pub kind: TagKind,
+  /// The line the definition starts at, starting at 1
+  pub line: usize,
+  /// The byte offset of the start of that line
+  pub line_start: usize,
+  /// The text of the line without the newline, etags uses this to find the definition when the file changed
+  pub line_text: String,
+}
+
+/// Collects the tags of the functions, structs, enums and globals of the parsed file
+pub fn tags(p: &Parser, file: &str) -> Vec<Tag> {
+  let mut definitions: Vec<(&str, TagKind, Span)> = vec![];
+  for function in &p.functions {
+    if let Some(name) = &function.name {
+      definitions.push((name, TagKind::Function, function.span));
+    }
+  }
+  for struct_ in &p.structs {
+    definitions.push((&struct_.name, TagKind::Struct, struct_.span));
+  }
+  for enum_ in &p.enums {
+    definitions.push((&enum_.name, TagKind::Enum, enum_.span));
+  }
+  for var in &p.globals {
+    definitions.push((&var.name, TagKind::Global, var.name_span));
+  }
+
+  let source_map = p.source_map();
+  let mut res: Vec<Tag> = definitions
+    .into_iter()
+    .map(|(name, kind, span)| {
+      let line = source_map.line_of(span.start);
+      let line_start = p.contents[..span.start]
+        .iter()
+        .rposition(|c| *c == b'\n')
+        .map_or(0, |newline| newline + 1);
+      let line_end = p.contents[line_start..]
+        .iter()
+        .position(|c| *c == b'\n')
+        .map_or(p.contents.len(), |newline| line_start + newline);
+      Tag {
+        name: String::from(name),
+        file: String::from(file),
+        kind,
+        line: line + 1,
+        line_start,
+        line_text: p.span_text(Span::new(line_start, line_end)),
+      }
+    })
+    .collect();
+  res.sort_by_key(|tag| tag.line);
+  res
+}
+
+/// Creates a ctags `tags` file, the tags are sorted by name as editors do a binary search over the lines
+pub fn ctags(tags: &[Tag]) -> String {
+  let mut sorted: Vec<&Tag> = tags.iter().collect();
+  sorted.sort_by(|a, b| (&a.name, &a.file, a.line).cmp(&(&b.name, &b.file, b.line)));
+
+  let mut res = String::from("!_TAG_FILE_FORMAT\t2\t/extended format/\n");
+  res += "!_TAG_FILE_SORTED\t1\t/0=unsorted, 1=sorted/\n";
+  for tag in sorted {
+    res += &format!(
+      "{}\t{}\t{};\"\t{}\n",
+      tag.name,
+      tag.file,
+      tag.line,
+      tag.kind.letter()
+    );
+  }
+  res
+}
+
+/// Creates a etags `TAGS` file, this has a section for every file with the tags in the order they are defined
+pub fn etags(tags: &[Tag]) -> String {
+  let mut files: Vec<&str> = vec![];
+  for tag in tags {
+    if !files.contains(&tag.file.as_str()) {
+      files.push(&tag.file);
+    }
+  }
+
+  let mut res = String::new();
+  for file in files {
+    let mut section = String::new();
+    for tag in tags.iter().filter(|tag| tag.file == file) {
+      section += &format!(
+        "{}\u{7f}{}\u{1}{},{}\n",
+        tag.line_text, tag.name, tag.line, tag.line_start
+      );
+    }
+    res += &format!("\u{c}\n{},{}\n{}", file, section.len(), section);
+  }
+  res
+}
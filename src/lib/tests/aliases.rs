@@ -0,0 +1,58 @@
+use super::*;
+
+fn parse_alias(contents: &str) -> TypeAlias {
+  parse_str(contents).type_aliases.remove(0)
+}
+
+#[test]
+fn test_alias() {
+  let res = parse_alias("type Names = []string");
+  assert_eq!(res.name, "Names");
+  assert_eq!(res.type_.name, "[]string");
+}
+
+#[test]
+fn test_alias_full_type_grammar() {
+  let res = parse_alias("type Scores = map[string]?int");
+  assert_eq!(res.type_.name, "map");
+  let (_, value) = *res.type_.map.unwrap();
+  assert!(value.optional);
+
+  let res = parse_alias("type Pair = (int, string)");
+  assert_eq!(res.type_.tuple.unwrap().len(), 2);
+
+  assert_eq!(parse_alias("type Ids = list<int>").type_.name, "list<int>");
+}
+
+#[test]
+fn test_alias_multiple() {
+  let res = parse_str(
+    r#"
+      type Names = []string
+      type Id =
+        int
+
+      fn test(names Names) {}
+    "#,
+  );
+  assert_eq!(res.type_aliases.len(), 2);
+  assert_eq!(res.type_aliases[1].type_.name, "int");
+}
+
+#[test]
+fn test_alias_invalid() {
+  parse_str_fail("type");
+  parse_str_fail("type = int");
+  parse_str_fail("type Foo int");
+  parse_str_fail("type Foo =");
+  parse_str_fail("type Foo = int string");
+  parse_str_fail("pub type Foo = int");
+  parse_str_fail("fn test() { type Foo = int }");
+}
+
+#[test]
+fn test_alias_invalid_type_chars() {
+  parse_str_fail("type Id = in$t");
+  parse_str_fail("type Id = list<int");
+  parse_str_fail("type Id = [5int");
+}
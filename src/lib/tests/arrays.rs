@@ -0,0 +1,71 @@
+use super::*;
+
+fn parse_value(value: &str) -> Vec<Action> {
+  let mut res = parse_str(format!("const foo = {}", value));
+  match *res.globals.remove(0).action {
+    Action::ArrayInit(items) => items,
+    action => panic!("expected a inline array, got {:?}", action),
+  }
+}
+
+#[test]
+fn test_array_init_empty() {
+  assert_eq!(parse_value("[]").len(), 0);
+  assert_eq!(parse_value("[ ]").len(), 0);
+}
+
+#[test]
+fn test_array_init_items() {
+  let res = parse_value(r#"[1, "foo", bar, compute(), a + b]"#);
+  assert_eq!(res.len(), 5);
+  match &res[3] {
+    Action::FunctionCall(meta) => assert_eq!(meta.name, "compute"),
+    action => panic!("expected a function call, got {:?}", action),
+  }
+  match &res[4] {
+    Action::BinaryOp(_) => {}
+    action => panic!("expected a binary operator, got {:?}", action),
+  }
+}
+
+#[test]
+fn test_array_init_nested_and_trailing_comma() {
+  let res = parse_value(
+    r#"[
+      [1, 2],
+      [],
+    ]"#,
+  );
+  assert_eq!(res.len(), 2);
+  match &res[0] {
+    Action::ArrayInit(items) => assert_eq!(items.len(), 2),
+    action => panic!("expected a inline array, got {:?}", action),
+  }
+}
+
+#[test]
+fn test_array_init_in_function() {
+  parse_str(
+    r#"
+      fn test() {
+        let list = [1, 2, compute()]
+        print([list, [3]])
+        return []
+      }
+    "#,
+  );
+}
+
+#[test]
+fn test_array_init_invalid() {
+  parse_str_fail("const foo = [1 2]");
+  parse_str_fail("const foo = [1,, 2]");
+  parse_str_fail("const foo = [1, 2");
+  parse_str_fail(
+    r#"
+      fn test() {
+        [1, 2]
+      }
+    "#,
+  );
+}
@@ -0,0 +1,43 @@
+use super::*;
+
+#[test]
+fn test_block_span() {
+  let res = parse_str("fn foo() {\n  if a {\n    b()\n  }\n}\nfn bar() {}");
+  let body = &res.functions[0].body;
+  assert_eq!(res.span_text(body.span), "{\n  if a {\n    b()\n  }\n}");
+  match &body.actions[0] {
+    Action::If(meta) => assert_eq!(res.span_text(meta.body.span), "{\n    b()\n  }"),
+    action => panic!("expected a if, got {:?}", action),
+  }
+  assert_eq!(res.span_text(res.functions[1].body.span), "{}");
+  assert_eq!(body.scope_id, None);
+}
+
+#[test]
+fn test_block_span_without_braces() {
+  let res = parse_str("const foo = |x| x + 1");
+  match &*res.globals[0].action {
+    Action::Closure(function) => assert_eq!(res.span_text(function.body.span), "x + 1"),
+    action => panic!("expected a closure, got {:?}", action),
+  }
+
+  let res = parse_str("const foo = match a { 1 => b, _ => { c() } }");
+  match &*res.globals[0].action {
+    Action::Match(meta) => {
+      assert_eq!(res.span_text(meta.arms[0].body.span), "b");
+      assert_eq!(res.span_text(meta.arms[1].body.span), "{ c() }");
+    }
+    action => panic!("expected a match, got {:?}", action),
+  }
+}
+
+#[test]
+fn test_block_span_lazy() {
+  let options = ParserOptions {
+    lazy_bodies: true,
+    ..ParserOptions::default()
+  };
+  let mut res = Parser::parse_with_options("fn foo() { a() }".as_bytes(), options).unwrap();
+  res.parse_body(0).unwrap();
+  assert_eq!(res.span_text(res.functions[0].body.span), "{ a() }");
+}
@@ -0,0 +1,83 @@
+use super::*;
+use std::thread;
+use std::time::Duration;
+
+const CODE: &str = r#"
+  fn foo() {
+    print("a")
+  }
+  fn bar() {
+    foo()
+  }
+"#;
+
+fn options_with(token: &CancellationToken) -> ParserOptions {
+  ParserOptions {
+    cancellation: Some(token.clone()),
+    ..ParserOptions::default()
+  }
+}
+
+fn is_cancelled(res: Result<Parser, Box<ParsingError>>) -> bool {
+  matches!(res, Err(err) if matches!(err.error_type, ParsingErrorType::Cancelled))
+}
+
+#[test]
+fn test_cancel_not_cancelled() {
+  let token = CancellationToken::new();
+  assert!(Parser::parse_with_options(CODE, options_with(&token)).is_ok());
+  assert!(!token.is_cancelled());
+}
+
+#[test]
+fn test_cancel_before_parse() {
+  let token = CancellationToken::new();
+  token.cancel();
+  assert!(is_cancelled(Parser::parse_with_options(
+    CODE,
+    options_with(&token)
+  )));
+}
+
+#[test]
+fn test_cancel_from_other_thread() {
+  let token = CancellationToken::new();
+  let other = token.clone();
+  thread::spawn(move || other.cancel()).join().unwrap();
+  assert!(token.is_cancelled());
+  assert!(is_cancelled(Parser::parse_with_options(
+    CODE,
+    options_with(&token)
+  )));
+}
+
+#[test]
+fn test_cancel_timeout() {
+  let token = CancellationToken::with_timeout(Duration::from_secs(0));
+  assert!(token.is_cancelled());
+  assert!(is_cancelled(Parser::parse_with_options(
+    CODE,
+    options_with(&token)
+  )));
+
+  let token = CancellationToken::with_timeout(Duration::from_secs(3600));
+  assert!(Parser::parse_with_options(CODE, options_with(&token)).is_ok());
+}
+
+#[test]
+fn test_cancel_lazy_bodies() {
+  let token = CancellationToken::new();
+  let mut res = Parser::parse_with_options(
+    CODE,
+    ParserOptions {
+      lazy_bodies: true,
+      ..options_with(&token)
+    },
+  )
+  .unwrap();
+  token.cancel();
+  match res.parse_bodies() {
+    Err(err) => assert!(matches!(err.error_type, ParsingErrorType::Cancelled)),
+    Ok(_) => panic!("expected the lazy bodies to be cancelled"),
+  }
+}
@@ -0,0 +1,110 @@
+use super::*;
+
+fn parse_value(value: &str) -> Function {
+  let mut res = parse_str(format!("const foo = {}", value));
+  match *res.globals.remove(0).action {
+    Action::Closure(meta) => meta,
+    action => panic!("expected a closure, got {:?}", action),
+  }
+}
+
+#[test]
+fn test_closure_shorthand() {
+  let res = parse_value("|x| x + 1");
+  assert_eq!(res.name, None);
+  assert_eq!(res.args.len(), 1);
+  assert_eq!(res.args[0].0, "x");
+  assert_eq!(res.body.actions.len(), 1);
+  match &res.body.actions[0] {
+    Action::Return(Some(action)) => match **action {
+      Action::BinaryOp(_) => {}
+      ref action => panic!("expected a binary operator, got {:?}", action),
+    },
+    action => panic!("expected a return, got {:?}", action),
+  }
+}
+
+#[test]
+fn test_closure_shorthand_arguments() {
+  assert_eq!(parse_value("|| foo()").args.len(), 0);
+  assert_eq!(parse_value("| | foo()").args.len(), 0);
+  let res = parse_value("|a, b| a + b");
+  assert_eq!(res.args.len(), 2);
+  assert_eq!(res.args[1].0, "b");
+}
+
+#[test]
+fn test_closure_shorthand_block() {
+  let res = parse_value(
+    r#"|x| {
+      print(x)
+      return x
+    }"#,
+  );
+  assert_eq!(res.body.actions.len(), 2);
+}
+
+#[test]
+fn test_closure_shorthand_as_argument() {
+  parse_str_with_pipes(
+    r#"
+      fn test(items []int) {
+        let res = items
+          |> filter(|x| x > 1)
+          |> map(|x| x * 2)
+        sort_by(res, |a, b| a < b)
+      }
+    "#,
+  );
+}
+
+#[test]
+fn test_closure_shorthand_invalid() {
+  parse_str_fail("const foo = |x x + 1");
+  parse_str_fail("const foo = |x, x| x");
+  parse_str_fail("const foo = |x,| x");
+  parse_str_fail("const foo = |x|");
+}
+
+#[test]
+fn test_anonymous_function() {
+  let res = parse_value("fn(a int, b int) int { return a + b }");
+  assert_eq!(res.name, None);
+  assert_eq!(res.args.len(), 2);
+  assert_eq!(res.args[0].0, "a");
+  assert_eq!(res.args[0].1.name, "int");
+  assert_eq!(res.return_type.unwrap().name, "int");
+  assert_eq!(res.body.actions.len(), 1);
+
+  let res = parse_value("fn () {}");
+  assert_eq!(res.args.len(), 0);
+  assert_eq!(res.body.actions.len(), 0);
+}
+
+#[test]
+fn test_anonymous_function_as_argument() {
+  parse_str(
+    r#"
+      fn test(items []int) {
+        let res = map(items, fn(x int) int {
+          return x * 2
+        })
+        sort_by(res, fn(a int, b int) bool { return a < b })
+      }
+    "#,
+  );
+}
+
+#[test]
+fn test_anonymous_function_invalid() {
+  parse_str_fail("const foo = fn foo() {}");
+  parse_str_fail("const foo = fn(a) {}");
+  parse_str_fail("const foo = fn(a int {}");
+  parse_str_fail(
+    r#"
+      fn test() {
+        fn() {}
+      }
+    "#,
+  );
+}
@@ -48,3 +48,97 @@ fn test_comments_inside_of_function() {
     "#,
   );
 }
+
+#[test]
+fn test_comments_between_top_level_items() {
+  let res = parse_str(
+    r#"
+      // a constant
+      const a = 1 // trailing comment
+      /* a function */ fn foo() {} /* trailing comment */
+      fn bar() {}// comment without space
+    "#,
+  );
+  assert_eq!(res.functions.len(), 2);
+  assert_eq!(res.globals.len(), 1);
+}
+
+#[test]
+fn test_comments_in_function_arguments() {
+  let res = parse_str(
+    r#"
+      fn foo(
+        // the first argument
+        a int, /* the second argument */ b int // trailing comment
+      ) {}
+    "#,
+  );
+  assert_eq!(res.functions[0].args.len(), 2);
+}
+
+#[test]
+fn test_comments_in_function_call_arguments() {
+  let res = parse_str(
+    r#"
+      fn foo() {
+        bar(
+          a, // first
+          /* second */ b
+        )
+      }
+    "#,
+  );
+  match &res.functions[0].body.actions[0] {
+    Action::FunctionCall(meta) => assert_eq!(meta.arguments.len(), 2),
+    action => panic!("expected a function call, got {:?}", action),
+  }
+}
+
+#[test]
+fn test_comments_in_function_body() {
+  let res = parse_str(
+    r#"
+      fn foo() {
+        // comment
+        let a = 1 // comment
+        loop { // comment
+          break // comment
+        }
+        return a /* comment */
+      }
+    "#,
+  );
+  assert_eq!(res.functions[0].body.actions.len(), 3);
+}
+
+#[test]
+fn test_comments_directly_after_keyword() {
+  parse_str(
+    r#"
+      fn foo() {
+        loop/* comment */{}
+        return// comment
+      }
+    "#,
+  );
+}
+
+#[test]
+fn test_comment_chars_inside_string() {
+  let res = parse_str(
+    r#"
+      const a = "http://example.com /* not a comment */"
+    "#,
+  );
+  match &*res.globals[0].action {
+    Action::StaticString(string) => {
+      assert_eq!(string.content, "http://example.com /* not a comment */")
+    }
+    action => panic!("expected a string, got {:?}", action),
+  }
+}
+
+#[test]
+fn test_comment_at_end_of_file() {
+  parse_str("fn foo() {}\n// comment without newline");
+}
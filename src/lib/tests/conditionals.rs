@@ -0,0 +1,211 @@
+use super::*;
+
+#[test]
+fn test_simple_if() {
+  parse_str(
+    r#"
+      fn test(a bool) {
+        if a {}
+      }
+    "#,
+  );
+}
+
+#[test]
+fn test_if_with_body() {
+  parse_str(
+    r#"
+      fn test(a bool) {
+        if a {
+          foo()
+          return
+        }
+      }
+    "#,
+  );
+}
+
+#[test]
+fn test_if_function_call_condition() {
+  parse_str(
+    r#"
+      fn test() {
+        if foo(bar) {}
+      }
+    "#,
+  );
+}
+
+#[test]
+fn test_if_else() {
+  let res = parse_str(
+    r#"
+      fn test(a bool) {
+        if a {
+          foo()
+        } else {
+          bar()
+        }
+      }
+    "#,
+  );
+  match &res.functions[0].body.actions[0] {
+    Action::If(meta) => {
+      assert_eq!(meta.else_ifs.len(), 0);
+      assert!(meta.else_body.is_some());
+    }
+    action => panic!("expected an if, got {:?}", action),
+  }
+}
+
+#[test]
+fn test_if_else_if_chain() {
+  let res = parse_str(
+    r#"
+      fn test(a bool, b bool) {
+        if a {
+        } else if b {
+        } else if c {
+        } else {
+        }
+        foo()
+      }
+    "#,
+  );
+  let body = &res.functions[0].body.actions;
+  assert_eq!(body.len(), 2);
+  match &body[0] {
+    Action::If(meta) => {
+      assert_eq!(meta.else_ifs.len(), 2);
+      assert!(meta.else_body.is_some());
+    }
+    action => panic!("expected an if, got {:?}", action),
+  }
+}
+
+#[test]
+fn test_multiple_ifs() {
+  let res = parse_str(
+    r#"
+      fn test(a bool) {
+        if a {}
+        if a {}
+      }
+    "#,
+  );
+  assert_eq!(res.functions[0].body.actions.len(), 2);
+}
+
+#[test]
+fn test_if_inside_loop() {
+  parse_str(
+    r#"
+      fn test(items []string) {
+        for item in items {
+          if item {
+            break
+          }
+        }
+      }
+    "#,
+  );
+}
+
+#[test]
+fn test_if_no_condition() {
+  parse_str_fail(
+    r#"
+      fn test() {
+        if {}
+      }
+    "#,
+  );
+}
+
+#[test]
+fn test_if_no_body() {
+  parse_str_fail(
+    r#"
+      fn test(a bool) {
+        if a
+      }
+    "#,
+  );
+}
+
+#[test]
+fn test_if_else_without_body() {
+  parse_str_fail(
+    r#"
+      fn test(a bool) {
+        if a {} else
+      }
+    "#,
+  );
+}
+
+#[test]
+fn test_if_else_if_without_condition() {
+  parse_str_fail(
+    r#"
+      fn test(a bool) {
+        if a {} else if {}
+      }
+    "#,
+  );
+}
+
+#[test]
+fn test_else_without_if() {
+  parse_str_fail(
+    r#"
+      fn test(a bool) {
+        else {}
+      }
+    "#,
+  );
+}
+
+#[test]
+fn test_if_as_value() {
+  let res = parse_str(
+    r#"
+      const size = if big { 10 } else { 1 }
+      fn test(a int) {
+        let b = if a > 1 { "many" } else if a == 1 { "one" } else { "none" }
+        let c = if a > 1 {
+          a * 2
+        } else {
+          a
+        }
+        print(if c { 1 } else { 2 })
+      }
+    "#,
+  );
+  match &*res.globals[0].action {
+    Action::If(meta) => {
+      assert_eq!(meta.body.actions.len(), 1);
+      assert!(matches!(meta.body.actions[0], Action::StaticNumber(_)));
+      assert!(meta.else_body.is_some());
+    }
+    action => panic!("expected a if, got {:?}", action),
+  }
+  match &res.functions[0].body.actions[0] {
+    Action::Variable(var) => match &*var.action {
+      Action::If(meta) => assert_eq!(meta.else_ifs.len(), 1),
+      action => panic!("expected a if, got {:?}", action),
+    },
+    action => panic!("expected a variable, got {:?}", action),
+  }
+}
+
+#[test]
+fn test_if_as_value_invalid() {
+  // The else is required
+  parse_str_fail("const a = if b { 1 }");
+  parse_str_fail("const a = if b { 1 } else if c { 2 }");
+  // The bodies contain a single value
+  parse_str_fail("const a = if b { } else { 2 }");
+  parse_str_fail("const a = if b { 1 2 } else { 2 }");
+  parse_str_fail("const a = if b { let c = 1 } else { 2 }");
+}
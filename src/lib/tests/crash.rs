@@ -0,0 +1,55 @@
+use crate::crash::{catch, catch_panic};
+use std::fs;
+
+#[test]
+fn test_crash_no_panic() {
+  assert_eq!(catch("parse", b"fn foo() {}", false, || 1).unwrap(), 1);
+}
+
+#[test]
+fn test_crash_report() {
+  let report = catch("parse", b"fn foo() {}", false, || panic!("boom")).unwrap_err();
+  assert_eq!(report.stage, "parse");
+  assert_eq!(report.version, env!("CARGO_PKG_VERSION"));
+  assert!(report.message.contains("boom"));
+  assert_eq!(report.source, None);
+
+  let text = report.to_string();
+  assert!(text.contains("stage: parse"));
+  assert!(text.contains("source: not included"));
+  assert!(!text.contains("fn foo() {}"));
+}
+
+#[test]
+fn test_crash_report_input_hash() {
+  let report = |input: &[u8]| catch("parse", input, false, || panic!("boom")).unwrap_err();
+  assert_eq!(report(b"foo").input_hash, report(b"foo").input_hash);
+  assert_ne!(report(b"foo").input_hash, report(b"bar").input_hash);
+}
+
+#[test]
+fn test_crash_report_include_source() {
+  let report = catch("parse", b"fn foo() {}", true, || panic!("boom")).unwrap_err();
+  assert_eq!(report.source.as_deref(), Some("fn foo() {}"));
+  assert!(report.to_string().contains("fn foo() {}"));
+}
+
+#[test]
+fn test_crash_report_write() {
+  let report = catch("parse", b"test_crash_report_write", false, || {
+    panic!("boom")
+  })
+  .unwrap_err();
+  let path = report.write_to(&std::env::temp_dir()).unwrap();
+  let written = fs::read_to_string(&path).unwrap();
+  fs::remove_file(&path).unwrap();
+  assert_eq!(written, report.to_string());
+}
+
+#[test]
+fn test_catch_panic() {
+  assert_eq!(catch_panic(|| 1).unwrap(), 1);
+  let panic = catch_panic(|| panic!("boom")).unwrap_err();
+  assert!(panic.message.contains("boom"));
+  assert!(panic.message.contains("crash.rs"));
+}
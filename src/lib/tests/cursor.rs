@@ -0,0 +1,53 @@
+use super::*;
+
+fn parser(contents: &str) -> Parser {
+  Parser::new(contents, ParserOptions::default())
+}
+
+#[test]
+fn test_cursor_skip_whitespace() {
+  let mut p = parser("  \n\t// comment\n  foo");
+  assert_eq!(p.skip_whitespace(), 17);
+  assert_eq!(p.seek_next_char(), Some('f'));
+  // Nothing to skip
+  assert_eq!(p.skip_whitespace(), 17);
+
+  let mut p = parser("   ");
+  assert_eq!(p.skip_whitespace(), 3);
+  assert_eq!(p.seek_next_char(), None);
+}
+
+#[test]
+fn test_cursor_peek_non_whitespace() {
+  let mut p = parser(" /* a */ )");
+  assert_eq!(p.peek_non_whitespace(), Some(')'));
+  assert_eq!(p.index, 0);
+  assert_eq!(parser(" \n ").peek_non_whitespace(), None);
+}
+
+#[test]
+fn test_cursor_eat() {
+  let mut p = parser(", )");
+  assert!(!p.eat(')'));
+  assert!(p.eat(','));
+  assert_eq!(p.index, 1);
+  // Whitespace is not skipped
+  assert!(!p.eat(')'));
+}
+
+#[test]
+fn test_cursor_next_ident() {
+  let mut p = parser("  foo_1 bar");
+  let ident = p.next_ident().unwrap();
+  assert_eq!(ident.name, "foo_1");
+  assert_eq!(ident.span, Span::new(2, 7));
+  assert_eq!(p.seek_next_char(), Some(' '));
+
+  // There is no name here
+  let mut p = parser(" (");
+  let ident = p.next_ident().unwrap();
+  assert_eq!(ident.name, "");
+  assert_eq!(ident.span.len(), 0);
+
+  assert!(parser("1abc").next_ident().is_err());
+}
@@ -0,0 +1,129 @@
+use super::*;
+use crate::edit::*;
+
+const CODE: &str = "fn main() {
+  let a = 1
+  print(a)
+}
+
+fn unused() {}
+
+struct Foo {
+  bar: string
+}
+";
+
+fn code_of(p: &Parser) -> String {
+  String::from_utf8(p.contents.clone()).unwrap()
+}
+
+#[test]
+fn test_edit_item_spans() {
+  let res = parse_str("pub fn foo() {}\n  struct Bar { a: int }  \nenum Baz { A }");
+  assert_eq!(res.span_text(res.functions[0].span), "pub fn foo() {}");
+  assert_eq!(res.span_text(res.structs[0].span), "struct Bar { a: int }");
+  assert_eq!(res.span_text(res.enums[0].span), "enum Baz { A }");
+}
+
+#[test]
+fn test_edit_insert_statement() {
+  let res = parse_str(CODE);
+  let mut edit = AstEdit::new(&res);
+  edit
+    .insert_statement(&res.functions[0].body, "print(a + 1)")
+    .unwrap();
+  edit
+    .insert_statement(&res.functions[1].body, "main()")
+    .unwrap();
+  let edited = edit.commit().unwrap();
+
+  assert_eq!(edited.program.functions[0].body.actions.len(), 3);
+  assert_eq!(edited.program.functions[1].body.actions.len(), 1);
+  assert!(code_of(&edited.program).contains("  print(a)\n  print(a + 1)\n}"));
+  assert!(code_of(&edited.program).contains("fn unused() {\n  main()\n}"));
+}
+
+#[test]
+fn test_edit_replace_expression() {
+  let res = parse_str(CODE);
+  let var = match &res.functions[0].body.actions[0] {
+    Action::Variable(var) => var,
+    action => panic!("expected a variable, got {:?}", action),
+  };
+  let mut edit = AstEdit::new(&res);
+  edit.replace_expression(var.action_span, "\"a\"");
+  let edited = edit.commit().unwrap();
+
+  match &edited.program.functions[0].body.actions[0] {
+    Action::Variable(var) => assert!(matches!(*var.action, Action::StaticString(_))),
+    action => panic!("expected a variable, got {:?}", action),
+  }
+  assert_eq!(
+    edited.edits,
+    vec![TextEdit {
+      span: var.action_span,
+      new_text: String::from("\"a\""),
+    }]
+  );
+}
+
+#[test]
+fn test_edit_delete_item() {
+  let res = parse_str(CODE);
+  let mut edit = AstEdit::new(&res);
+  edit.delete_item(res.functions[1].span).unwrap();
+  edit.delete_item(res.structs[0].span).unwrap();
+  let edited = edit.commit().unwrap();
+
+  assert_eq!(edited.program.functions.len(), 1);
+  assert_eq!(edited.program.structs.len(), 0);
+  assert_eq!(
+    code_of(&edited.program),
+    "fn main() {\n  let a = 1\n  print(a)\n}\n\n\n"
+  );
+}
+
+#[test]
+fn test_edit_undo() {
+  let res = parse_str(CODE);
+  let mut edit = AstEdit::new(&res);
+  edit
+    .insert_statement(&res.functions[0].body, "let b = 2")
+    .unwrap();
+  edit.delete_item(res.functions[1].span).unwrap();
+  edit.replace_expression(res.structs[0].span, "struct Foo {}");
+  let edited = edit.commit().unwrap();
+
+  // The old program is left as it was
+  assert_eq!(res.functions.len(), 2);
+  let undone = apply_edits(&edited.program.contents, &edited.undo);
+  assert_eq!(String::from_utf8(undone).unwrap(), CODE);
+}
+
+#[test]
+fn test_edit_invalid() {
+  let res = parse_str(CODE);
+
+  // The edits overlap
+  let mut edit = AstEdit::new(&res);
+  edit.delete_item(res.functions[0].span).unwrap();
+  edit
+    .insert_statement(&res.functions[0].body, "foo()")
+    .unwrap();
+  assert!(edit.commit().is_err());
+
+  // The edited code doesn't parse
+  let mut edit = AstEdit::new(&res);
+  edit
+    .insert_statement(&res.functions[0].body, "let")
+    .unwrap();
+  assert!(edit.commit().is_err());
+
+  // Nested functions have no span
+  let res = parse_str("fn main() {\n  fn inner() {}\n}");
+  let inner = match &res.functions[0].body.actions[0] {
+    Action::FunctionDecl(function) => function,
+    action => panic!("expected a function, got {:?}", action),
+  };
+  assert!(AstEdit::new(&res).delete_item(inner.span).is_err());
+}
@@ -0,0 +1,107 @@
+use super::*;
+
+#[test]
+fn test_enum_empty() {
+  let res = parse_str(
+    r#"
+      enum Foo {}
+    "#,
+  );
+  assert_eq!(res.enums[0].name, "Foo");
+  assert_eq!(res.enums[0].variants.len(), 0);
+}
+
+#[test]
+fn test_enum_variants() {
+  let res = parse_str(
+    r#"
+      enum Color { Red, Green, Blue }
+    "#,
+  );
+  let names: Vec<&str> = res.enums[0]
+    .variants
+    .iter()
+    .map(|variant| variant.name.as_str())
+    .collect();
+  assert_eq!(names, vec!["Red", "Green", "Blue"]);
+}
+
+#[test]
+fn test_enum_variants_with_payload() {
+  let res = parse_str(
+    r#"
+      enum Color {
+        Red
+        Green,
+        Custom(string),
+        Rgb(int, int, int),
+      }
+    "#,
+  );
+  let variants = &res.enums[0].variants;
+  assert_eq!(variants.len(), 4);
+  assert_eq!(variants[0].payload.len(), 0);
+  assert_eq!(variants[1].payload.len(), 0);
+  assert_eq!(variants[2].payload[0].name, "string");
+  assert_eq!(variants[3].payload.len(), 3);
+}
+
+#[test]
+fn test_enum_with_other_items() {
+  let res = parse_str(
+    r#"
+      enum Foo { A, B }
+      struct Bar {}
+      fn baz() {}
+      enum Qux {
+        A
+      }
+    "#,
+  );
+  assert_eq!(res.enums.len(), 2);
+}
+
+#[test]
+fn test_enum_duplicate_variants() {
+  parse_str_fail(
+    r#"
+      enum Foo { A, B, A }
+    "#,
+  );
+}
+
+#[test]
+fn test_enum_duplicate_variants_with_payload() {
+  parse_str_fail(
+    r#"
+      enum Foo { A(string), A(int) }
+    "#,
+  );
+}
+
+#[test]
+fn test_enum_empty_payload() {
+  parse_str_fail(
+    r#"
+      enum Foo { A() }
+    "#,
+  );
+}
+
+#[test]
+fn test_enum_unclosed_payload() {
+  parse_str_fail(
+    r#"
+      enum Foo { A(string }
+    "#,
+  );
+}
+
+#[test]
+fn test_enum_no_name() {
+  parse_str_fail(
+    r#"
+      enum { A }
+    "#,
+  );
+}
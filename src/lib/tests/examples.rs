@@ -0,0 +1,26 @@
+use super::*;
+use std::fs;
+
+// Parse all programs inside of the examples directory
+// Every program has a .out file next to it with the output of running the program
+// Once there is a interpreter the programs should also be ran and compared to their .out file
+#[test]
+fn test_examples() {
+  let dir = concat!(env!("CARGO_MANIFEST_DIR"), "/examples");
+  let mut parsed = 0;
+  for entry in fs::read_dir(dir).unwrap() {
+    let path = entry.unwrap().path();
+    if path.extension().and_then(|ext| ext.to_str()) != Some("tp") {
+      continue;
+    }
+    let contents = fs::read(&path).unwrap();
+    if let Err(err) = Parser::parse(contents) {
+      panic!("{}:\n{}", path.display(), err);
+    }
+    let expected_output = fs::read_to_string(path.with_extension("out"))
+      .unwrap_or_else(|_| panic!("{}: missing the expected output file", path.display()));
+    assert!(!expected_output.is_empty(), "{}", path.display());
+    parsed += 1;
+  }
+  assert!(parsed > 0);
+}
@@ -0,0 +1,451 @@
+use super::*;
+
+fn parse_value(value: &str) -> Action {
+  let mut res = parse_str(format!("const foo = {}", value));
+  *res.globals.remove(0).action
+}
+
+fn parse_piped_value(value: &str) -> Action {
+  let mut res = parse_str_with_pipes(format!("const foo = {}", value));
+  *res.globals.remove(0).action
+}
+
+// Formats the action with parentheses around every binary operator to make the precedence visible
+fn fmt(action: &Action) -> String {
+  match action {
+    Action::BinaryOp(meta) => {
+      let operator: &str = meta.operator.into();
+      format!("({} {} {})", fmt(&meta.left), operator, fmt(&meta.right))
+    }
+    Action::Comparison(meta) => {
+      let operator: &str = meta.operator.into();
+      format!("({} {} {})", fmt(&meta.left), operator, fmt(&meta.right))
+    }
+    Action::Logical(meta) => {
+      let operator: &str = meta.operator.into();
+      format!("({} {} {})", fmt(&meta.left), operator, fmt(&meta.right))
+    }
+    Action::Range(meta) => {
+      let operator = if meta.inclusive { "..=" } else { ".." };
+      format!("({}{}{})", fmt(&meta.start), operator, fmt(&meta.end))
+    }
+    Action::UnaryOp(meta) => {
+      let operator: &str = meta.operator.into();
+      format!("{}{}", operator, fmt(&meta.action))
+    }
+    Action::VarRef(name) => name.clone(),
+    Action::StaticNumber(NumberLiteral {
+      value: NumberValue::Int(number, _),
+      ..
+    }) => number.to_string(),
+    Action::FunctionCall(meta) => format!("{}()", meta.name),
+    Action::Cast(meta) => format!("({} as {})", fmt(&meta.value), meta.target_type.name),
+    action => format!("{:?}", action),
+  }
+}
+
+#[test]
+fn test_expression_simple() {
+  assert_eq!(fmt(&parse_value("a + b")), "(a + b)");
+  assert_eq!(fmt(&parse_value("a+b")), "(a + b)");
+  assert_eq!(fmt(&parse_value("1 % 2")), "(1 % 2)");
+}
+
+#[test]
+fn test_expression_precedence() {
+  assert_eq!(fmt(&parse_value("a + b * 2")), "(a + (b * 2))");
+  assert_eq!(fmt(&parse_value("a * b + 2")), "((a * b) + 2)");
+  assert_eq!(
+    fmt(&parse_value("a + b * c - d / e")),
+    "((a + (b * c)) - (d / e))"
+  );
+}
+
+#[test]
+fn test_expression_left_associative() {
+  assert_eq!(fmt(&parse_value("a - b - c")), "((a - b) - c)");
+  assert_eq!(fmt(&parse_value("a / b * c")), "((a / b) * c)");
+}
+
+#[test]
+fn test_expression_parentheses() {
+  assert_eq!(fmt(&parse_value("(a + b) * 2")), "((a + b) * 2)");
+  assert_eq!(
+    fmt(&parse_value("a * (b + (c - d))")),
+    "(a * (b + (c - d)))"
+  );
+}
+
+#[test]
+fn test_expression_function_calls() {
+  assert_eq!(fmt(&parse_value("foo() + bar(a * b)")), "(foo() + bar())");
+}
+
+#[test]
+fn test_expression_in_function() {
+  parse_str(
+    r#"
+      fn add(a int, b int) int {
+        let c = a + b * 2
+        c = c - 1
+        foo(a + b, c * 2)
+        while a % 2 {}
+        return a + c
+      }
+    "#,
+  );
+}
+
+#[test]
+fn test_expression_multi_line() {
+  assert_eq!(fmt(&parse_value("a +\n  b")), "(a + b)");
+}
+
+#[test]
+fn test_expression_missing_right_side() {
+  parse_str_fail(
+    r#"
+      const foo = a +
+    "#,
+  );
+}
+
+#[test]
+fn test_expression_unclosed_parentheses() {
+  parse_str_fail(
+    r#"
+      const foo = (a + b
+    "#,
+  );
+}
+
+#[test]
+fn test_expression_followed_by_comment() {
+  let res = parse_str(
+    r#"
+      const foo = a // a comment
+      const bar = b /* another comment */
+    "#,
+  );
+  assert_eq!(res.globals.len(), 2);
+}
+
+#[test]
+fn test_expression_comparisons() {
+  assert_eq!(fmt(&parse_value("a == b")), "(a == b)");
+  assert_eq!(fmt(&parse_value("a==b")), "(a == b)");
+  assert_eq!(fmt(&parse_value("a != b")), "(a != b)");
+  assert_eq!(fmt(&parse_value("a<b")), "(a < b)");
+  assert_eq!(fmt(&parse_value("a <= b")), "(a <= b)");
+  assert_eq!(fmt(&parse_value("a > b")), "(a > b)");
+  assert_eq!(fmt(&parse_value("a >= b")), "(a >= b)");
+}
+
+#[test]
+fn test_expression_chained_comparisons() {
+  parse_str_fail("const foo = a == b == c");
+  parse_str_fail("const foo = a < b < c");
+  parse_str_fail("const foo = a < b == c");
+  parse_str_fail("const foo = a + 1 < b * 2 > c");
+  assert_eq!(fmt(&parse_value("(a == b) == c")), "((a == b) == c)");
+  assert_eq!(fmt(&parse_value("a == (b < c)")), "(a == (b < c))");
+  assert_eq!(fmt(&parse_value("a < b && b < c")), "((a < b) && (b < c))");
+}
+
+#[test]
+fn test_expression_logical_precedence() {
+  assert_eq!(
+    fmt(&parse_value("a + 1 < b * 2 && c == d || e")),
+    "((((a + 1) < (b * 2)) && (c == d)) || e)"
+  );
+  assert_eq!(fmt(&parse_value("a || b && c")), "(a || (b && c))");
+}
+
+#[test]
+fn test_expression_not() {
+  assert_eq!(fmt(&parse_value("!a")), "!a");
+  assert_eq!(fmt(&parse_value("!a && b")), "(!a && b)");
+  assert_eq!(fmt(&parse_value("!(a && b)")), "!(a && b)");
+  assert_eq!(fmt(&parse_value("!foo()")), "!foo()");
+}
+
+#[test]
+fn test_expression_loop_conditions() {
+  parse_str(
+    r#"
+      fn test(a int, b int) {
+        while a < b {}
+        while a && b {}
+        while !a || b >= 10 {}
+      }
+    "#,
+  );
+}
+
+#[test]
+fn test_expression_incomplete_operators() {
+  parse_str_fail(
+    r#"
+      const foo = a & b
+    "#,
+  );
+  parse_str_fail(
+    r#"
+      const foo = a ! b
+    "#,
+  );
+  parse_str_fail(
+    r#"
+      const foo = a ==
+    "#,
+  );
+}
+
+#[test]
+fn test_expression_pipe() {
+  let res = parse_piped_value("items |> filter(pred) |> map(f)");
+  match res {
+    Action::FunctionCall(map) => {
+      assert_eq!(map.name, "map");
+      assert_eq!(map.arguments.len(), 2);
+      match &map.arguments[0] {
+        Action::FunctionCall(filter) => {
+          assert_eq!(filter.name, "filter");
+          assert_eq!(fmt(&filter.arguments[0]), "items");
+          assert_eq!(fmt(&filter.arguments[1]), "pred");
+        }
+        action => panic!("expected a function call, got {:?}", action),
+      }
+    }
+    action => panic!("expected a function call, got {:?}", action),
+  }
+}
+
+#[test]
+fn test_expression_pipe_precedence() {
+  match parse_piped_value("a + 1 |> foo()") {
+    Action::FunctionCall(meta) => assert_eq!(fmt(&meta.arguments[0]), "(a + 1)"),
+    action => panic!("expected a function call, got {:?}", action),
+  }
+}
+
+#[test]
+fn test_expression_pipe_multi_line() {
+  parse_str_with_pipes(
+    r#"
+      fn test(items []int) {
+        let res = items
+          |> filter(is_even)
+          |> sum()
+        print(res)
+      }
+    "#,
+  );
+}
+
+#[test]
+fn test_expression_pipe_without_call() {
+  let err = Parser::parse_with_options("const foo = a |> b".as_bytes(), pipes()).unwrap_err();
+  assert_eq!(
+    err.error_type,
+    ParsingErrorType::Custom("Expected a function call after |>")
+  );
+}
+
+#[test]
+fn test_expression_null_coalesce() {
+  match parse_value("a ?? b") {
+    Action::NullCoalesce(meta) => {
+      assert_eq!(fmt(&meta.left), "a");
+      assert_eq!(fmt(&meta.right), "b");
+    }
+    action => panic!("expected a null coalesce, got {:?}", action),
+  }
+  match parse_value("a??b + 1 ?? c") {
+    Action::NullCoalesce(meta) => {
+      assert_eq!(fmt(&meta.right), "c");
+      match *meta.left {
+        Action::NullCoalesce(ref left) => assert_eq!(fmt(&left.right), "(b + 1)"),
+        ref action => panic!("expected a null coalesce, got {:?}", action),
+      }
+    }
+    action => panic!("expected a null coalesce, got {:?}", action),
+  }
+}
+
+#[test]
+fn test_expression_null_assert() {
+  match parse_value("foo!") {
+    Action::NullAssert(action) => assert_eq!(fmt(&action), "foo"),
+    action => panic!("expected a null assertion, got {:?}", action),
+  }
+}
+
+#[test]
+fn test_expression_boolean() {
+  match parse_value("true") {
+    Action::StaticBoolean(true) => {}
+    action => panic!("expected true, got {:?}", action),
+  }
+  match parse_value("!false && trueish") {
+    Action::Logical(meta) => {
+      match *meta.left {
+        Action::UnaryOp(ref meta) => match *meta.action {
+          Action::StaticBoolean(false) => {}
+          ref action => panic!("expected false, got {:?}", action),
+        },
+        ref action => panic!("expected a unary operator, got {:?}", action),
+      }
+      assert_eq!(fmt(&meta.right), "trueish");
+    }
+    action => panic!("expected a logical operator, got {:?}", action),
+  }
+}
+
+#[test]
+fn test_expression_boolean_in_loop() {
+  let res = parse_str(
+    r#"
+      fn test() {
+        while true {}
+      }
+    "#,
+  );
+  match &res.functions[0].body.actions[0] {
+    Action::While(meta) => match *meta.true_value {
+      Action::StaticBoolean(true) => {}
+      ref action => panic!("expected true, got {:?}", action),
+    },
+    action => panic!("expected a while loop, got {:?}", action),
+  }
+}
+
+#[test]
+fn test_expression_boolean_is_not_a_name() {
+  parse_str_fail(
+    r#"
+      fn test() {
+        true = false
+      }
+    "#,
+  );
+  parse_str_fail(
+    r#"
+      fn test() {
+        false()
+      }
+    "#,
+  );
+}
+
+#[test]
+fn test_expression_negate() {
+  assert_eq!(fmt(&parse_value("-5")), "-5");
+  assert_eq!(fmt(&parse_value("-foo()")), "-foo()");
+  assert_eq!(fmt(&parse_value("-(a + b)")), "-(a + b)");
+  assert_eq!(fmt(&parse_value("-a * b")), "(-a * b)");
+  assert_eq!(fmt(&parse_value("a - -b")), "(a - -b)");
+  assert_eq!(fmt(&parse_value("a--b")), "(a - -b)");
+  assert_eq!(fmt(&parse_value("!-a")), "!-a");
+}
+
+#[test]
+fn test_expression_negate_in_body() {
+  parse_str(
+    r#"
+      fn test() {
+        let x = -5
+        print(-x, -1.5)
+        return -x
+      }
+    "#,
+  );
+  parse_str_fail(
+    r#"
+      fn test() {
+        -x
+      }
+    "#,
+  );
+}
+
+#[test]
+fn test_expression_range() {
+  assert_eq!(fmt(&parse_value("0..10")), "(0..10)");
+  assert_eq!(fmt(&parse_value("0..=10")), "(0..=10)");
+  assert_eq!(fmt(&parse_value("a..b")), "(a..b)");
+  assert_eq!(fmt(&parse_value("a .. b")), "(a..b)");
+  assert_eq!(fmt(&parse_value("0..n + 1")), "(0..(n + 1))");
+  assert_eq!(fmt(&parse_value("-5..len()")), "(-5..len())");
+  match parse_value("1.5..2.5") {
+    Action::Range(meta) => match (*meta.start, *meta.end) {
+      (
+        Action::StaticNumber(NumberLiteral {
+          value: NumberValue::Float(start),
+          ..
+        }),
+        Action::StaticNumber(NumberLiteral {
+          value: NumberValue::Float(end),
+          ..
+        }),
+      ) => {
+        assert_eq!(start, 1.5);
+        assert_eq!(end, 2.5);
+      }
+      actions => panic!("expected two floats, got {:?}", actions),
+    },
+    action => panic!("expected a range, got {:?}", action),
+  }
+}
+
+#[test]
+fn test_expression_range_invalid() {
+  parse_str_fail("const foo = 0..");
+  parse_str_fail("const foo = 0..=");
+  parse_str_fail("const foo = 0...10");
+  parse_str_fail("const foo = 0..5..10");
+}
+
+#[test]
+fn test_expression_cast() {
+  assert_eq!(fmt(&parse_value("a as u8")), "(a as u8)");
+  assert_eq!(fmt(&parse_value("a as int + 1")), "((a as int) + 1)");
+  assert_eq!(
+    fmt(&parse_value("1 + a as f64 * 2")),
+    "(1 + ((a as f64) * 2))"
+  );
+  assert_eq!(fmt(&parse_value("a as i64 as u8")), "((a as i64) as u8)");
+  assert_eq!(
+    fmt(&parse_value("foo() as []string")),
+    "(foo() as []string)"
+  );
+  assert_eq!(fmt(&parse_value("-a as int")), "-(a as int)");
+  assert_eq!(fmt(&parse_value("a as int+1")), "((a as int) + 1)");
+  assert_eq!(
+    fmt(&parse_value("a as u8*b as u8")),
+    "((a as u8) * (b as u8))"
+  );
+  assert_eq!(fmt(&parse_value("a as []int==b")), "((a as []int) == b)");
+  assert_eq!(fmt(&parse_value("a as int>=b")), "((a as int) >= b)");
+
+  // The type grammar is the same as for function arguments
+  match parse_value("a as map[string]?int") {
+    Action::Cast(meta) => {
+      let (_, value) = *meta.target_type.map.unwrap();
+      assert!(value.optional);
+    }
+    action => panic!("expected a cast, got {:?}", action),
+  }
+  parse_str("fn test(a int) { print(a as f32, b as u8) }");
+  parse_str("fn test(a int) { return a as u8 }");
+  parse_str("fn test(as_ int) { let b = as_ }");
+}
+
+#[test]
+fn test_expression_cast_invalid() {
+  parse_str_fail("const foo = a as");
+  // The `<` directly after a type starts its generic arguments like `list<int>`
+  parse_str_fail("const foo = a as int<b");
+  // A cast does nothing on its own
+  parse_str_fail("fn test(a int) { a as u8 }");
+}
@@ -0,0 +1,35 @@
+use super::*;
+
+fn parse_with_features(
+  contents: &str,
+  experimental: ExperimentalFeatures,
+) -> Result<Parser, Box<ParsingError>> {
+  let options = ParserOptions {
+    experimental,
+    ..ParserOptions::default()
+  };
+  Parser::parse_with_options(contents.as_bytes(), options)
+}
+
+#[test]
+fn test_features_default() {
+  let features = ExperimentalFeatures::default();
+  assert_eq!(features, ExperimentalFeatures::NONE);
+  assert!(!features.contains(ExperimentalFeatures::PIPES));
+
+  let features = ExperimentalFeatures::NONE | ExperimentalFeatures::PIPES;
+  assert!(features.contains(ExperimentalFeatures::PIPES));
+}
+
+#[test]
+fn test_features_pipes_disabled() {
+  let code = "const foo = items |> filter(pred)";
+  parse_with_features(code, ExperimentalFeatures::PIPES).unwrap();
+  let err = parse_with_features(code, ExperimentalFeatures::NONE).unwrap_err();
+  assert_eq!(
+    err.error_type,
+    ParsingErrorType::ExperimentalFeature("pipes")
+  );
+  // Code without pipes doesn't need the feature
+  parse_with_features("const foo = a || b", ExperimentalFeatures::NONE).unwrap();
+}
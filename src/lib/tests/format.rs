@@ -0,0 +1,123 @@
+use super::*;
+use crate::format::{format_number, sorted_items, FormatOptions, Item, ItemOrder};
+
+fn format(value: &str, options: &FormatOptions) -> String {
+  let mut res = parse_str(format!("const foo = {}", value));
+  match *res.globals.remove(0).action {
+    Action::StaticNumber(number) => format_number(&number, options),
+    action => panic!("expected a number, got {:?}", action),
+  }
+}
+
+#[test]
+fn test_format_number_hex_casing() {
+  let options = FormatOptions::default();
+  assert_eq!(format("0xffu8", &options), "0xFFu8");
+  assert_eq!(format("0xAb_cd", &options), "0xAB_CD");
+
+  let options = FormatOptions {
+    uppercase_hex: false,
+    ..FormatOptions::default()
+  };
+  assert_eq!(format("0xFF", &options), "0xff");
+}
+
+#[test]
+fn test_format_number_group_digits() {
+  let options = FormatOptions {
+    group_digits: Some(5),
+    ..FormatOptions::default()
+  };
+  assert_eq!(format("1000000", &options), "1_000_000");
+  assert_eq!(format("10_00_000u64", &options), "1_000_000u64");
+  assert_eq!(format("12345.6789e3", &options), "12_345.6789e3");
+  assert_eq!(format("1000", &options), "1000");
+  assert_eq!(format("1_000", &options), "1000");
+  assert_eq!(format(".5", &options), ".5");
+  // Only decimal numbers are grouped
+  assert_eq!(format("0b1111_0000", &options), "0b1111_0000");
+
+  // Without grouping the number is kept as written
+  assert_eq!(format("1_0000", &FormatOptions::default()), "1_0000");
+}
+
+fn item_names(items: Vec<Item>) -> Vec<String> {
+  items
+    .into_iter()
+    .map(|item| match item {
+      Item::Const(name) => format!("const {}", name),
+      Item::TypeAlias(name) => format!("type {}", name),
+      Item::Struct(name) => format!("struct {}", name),
+      Item::Enum(name) => format!("enum {}", name),
+      Item::Trait(name) => format!("trait {}", name),
+      Item::Impl(name, _) => format!("impl {}", name),
+      Item::Function(name) => format!("fn {}", name),
+    })
+    .collect()
+}
+
+const ITEMS_CODE: &str = r#"
+  fn unused() {}
+  fn main() {
+    b()
+    a()
+  }
+  impl Foo {}
+  const b = a + 1
+  fn c() {}
+  struct Foo {}
+  type Id = int
+  fn a() {
+    c()
+    print()
+  }
+  const a = 1
+  fn b() {}
+"#;
+
+#[test]
+fn test_sorted_items_alphabetical() {
+  let res = parse_str(ITEMS_CODE);
+  assert_eq!(
+    item_names(sorted_items(&res, ItemOrder::Alphabetical)),
+    vec![
+      "const a",
+      "const b",
+      "type Id",
+      "struct Foo",
+      "impl Foo",
+      "fn a",
+      "fn b",
+      "fn c",
+      "fn main",
+      "fn unused",
+    ]
+  );
+}
+
+#[test]
+fn test_sorted_items_dependencies() {
+  let res = parse_str(ITEMS_CODE);
+  assert_eq!(
+    item_names(sorted_items(&res, ItemOrder::Dependencies)),
+    vec![
+      "const a",
+      "const b",
+      "type Id",
+      "struct Foo",
+      "impl Foo",
+      "fn main",
+      "fn a",
+      "fn c",
+      "fn b",
+      "fn unused",
+    ]
+  );
+
+  // Recursive functions don't loop forever
+  let res = parse_str("fn b() { a() }\nfn a() { b()\na() }");
+  assert_eq!(
+    item_names(sorted_items(&res, ItemOrder::Dependencies)),
+    vec!["fn b", "fn a"]
+  );
+}
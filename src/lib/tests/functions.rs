@@ -85,3 +85,139 @@ fn test_function_call_with_args() {
     "#,
   );
 }
+
+#[test]
+fn test_function_with_args_are_split() {
+  let res = parse_str(
+    r#"
+      fn test(foo string, bar string, baz string) {}
+    "#,
+  );
+  assert_eq!(res.functions[0].args.len(), 3);
+}
+
+#[test]
+fn test_function_without_name() {
+  parse_str_fail(
+    r#"
+      fn () {}
+    "#,
+  );
+}
+
+#[test]
+fn test_function_return_type() {
+  let res = parse_str(
+    r#"
+      fn none() {}
+      fn plain() string {
+        return "foo"
+      }
+      fn arrow(a int) -> int {
+        return a
+      }
+      fn map() map[string]int{
+        return {}
+      }
+    "#,
+  );
+  let return_types: Vec<Option<&str>> = res
+    .functions
+    .iter()
+    .map(|function| {
+      function
+        .return_type
+        .as_ref()
+        .map(|type_| type_.name.as_str())
+    })
+    .collect();
+  assert_eq!(
+    return_types,
+    vec![None, Some("string"), Some("int"), Some("map")]
+  );
+  match &res.functions[1].body.actions[0] {
+    Action::Return(Some(_)) => {}
+    action => panic!("expected a return with a value, got {:?}", action),
+  }
+}
+
+#[test]
+fn test_function_invalid_return_type() {
+  parse_str_fail("fn test() -> {}");
+  parse_str_fail("fn test() - int {}");
+  parse_str_fail("fn test() string int {}");
+}
+
+#[test]
+fn test_nested_function() {
+  let res = parse_str(
+    r#"
+      fn outer(a int) int {
+        fn double(b int) int {
+          return b * 2
+        }
+        fn nothing() {}
+        return double(a)
+      }
+    "#,
+  );
+  assert_eq!(res.functions.len(), 1);
+  let body = &res.functions[0].body.actions;
+  assert_eq!(body.len(), 3);
+  match &body[0] {
+    Action::FunctionDecl(meta) => {
+      assert_eq!(meta.name, Some(String::from("double")));
+      assert_eq!(meta.args.len(), 1);
+      assert_eq!(meta.body.actions.len(), 1);
+    }
+    action => panic!("expected a function declaration, got {:?}", action),
+  }
+  match &body[1] {
+    Action::FunctionDecl(meta) => assert_eq!(meta.name, Some(String::from("nothing"))),
+    action => panic!("expected a function declaration, got {:?}", action),
+  }
+}
+
+#[test]
+fn test_nested_function_invalid() {
+  parse_str_fail(
+    r#"
+      fn outer() {
+        fn () {}
+      }
+    "#,
+  );
+  parse_str_fail(
+    r#"
+      fn outer() {
+        fn inner( {}
+      }
+    "#,
+  );
+}
+
+#[test]
+fn test_function_call_argument_spans() {
+  let code = r#"
+    fn test() {
+      foo(a,  b + 1 , bar(c) // comment
+      )
+      let e = 1 + 2 |> baz(d)
+    }
+  "#;
+  let res = parse_str_with_pipes(code);
+  let argument_spans = |action: &Action| match action {
+    Action::FunctionCall(meta) => meta
+      .argument_spans
+      .iter()
+      .map(|span| &code[span.start..span.end])
+      .collect::<Vec<&str>>(),
+    action => panic!("expected a function call, got {:?}", action),
+  };
+  let body = &res.functions[0].body.actions;
+  assert_eq!(argument_spans(&body[0]), vec!["a", "b + 1", "bar(c)"]);
+  match &body[1] {
+    Action::Variable(meta) => assert_eq!(argument_spans(&meta.action), vec!["1 + 2", "d"]),
+    action => panic!("expected a variable, got {:?}", action),
+  }
+}
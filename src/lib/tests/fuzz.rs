@@ -0,0 +1,136 @@
+use super::*;
+use std::panic;
+
+static FRAGMENTS: &[&str] = &[
+  "fn",
+  " ",
+  "\n",
+  "\t",
+  "foo",
+  "bar",
+  "1",
+  "0x",
+  "1.5",
+  "e",
+  "u8",
+  "\"",
+  "\"a\"",
+  "`",
+  "(",
+  ")",
+  "{",
+  "}",
+  "[",
+  "]",
+  ",",
+  ".",
+  "..",
+  "=",
+  "==",
+  "=>",
+  "!",
+  "-",
+  "+",
+  "*",
+  "/",
+  "%",
+  "<",
+  ">",
+  "&&",
+  "||",
+  "|",
+  "|>",
+  "??",
+  "?",
+  ":",
+  ";",
+  "_",
+  "let",
+  "const",
+  "pub",
+  "return",
+  "if",
+  "else",
+  "for",
+  "in",
+  "while",
+  "do",
+  "as",
+  "loop",
+  "break",
+  "continue",
+  "defer",
+  "try",
+  "catch",
+  "match",
+  "struct",
+  "enum",
+  "impl",
+  "import",
+  "trait",
+  "self",
+  "type",
+  "true",
+  "false",
+  "null",
+  "map[",
+  "//",
+  "/*",
+  "*/",
+  "#version 0.1",
+  "#",
+  "->",
+  "'",
+  "\\",
+  "é",
+];
+
+static PREFIXES: &[&str] = &["", "fn foo() {", "const foo = ", "fn foo(a int) int {\n"];
+
+/// A xorshift generator so every run tests the same inputs
+struct Random(u64);
+
+impl Random {
+  fn next(&mut self, max: usize) -> usize {
+    self.0 ^= self.0 << 13;
+    self.0 ^= self.0 >> 7;
+    self.0 ^= self.0 << 17;
+    (self.0 % max as u64) as usize
+  }
+}
+
+// Parsing must never unwind into the caller and a bug in the parser shows up as a InternalError
+fn assert_no_internal_error(input: &str) {
+  let res = panic::catch_unwind(|| Parser::parse_with_options(input.as_bytes(), pipes()));
+  match res {
+    Ok(Err(err)) => {
+      if let ParsingErrorType::InternalError(_) = err.error_type {
+        panic!("input {:?} results in a internal error:\n{}", input, err);
+      }
+    }
+    Ok(Ok(_)) => {}
+    Err(_) => panic!("parsing {:?} unwinded into the caller", input),
+  }
+}
+
+#[test]
+fn test_fuzz_parse() {
+  let mut random = Random(0x2545F4914F6CDD1D);
+  for _ in 0..20000 {
+    let mut input = String::from(PREFIXES[random.next(PREFIXES.len())]);
+    for _ in 0..random.next(40) {
+      input.push_str(FRAGMENTS[random.next(FRAGMENTS.len())]);
+    }
+    assert_no_internal_error(&input);
+  }
+}
+
+#[test]
+fn test_fuzz_regressions() {
+  // Found by test_fuzz_parse
+  assert_no_internal_error("const foo = !");
+  assert_no_internal_error("const foo = -");
+  assert_no_internal_error("fn//#");
+  assert_no_internal_error("fn/*0x");
+  assert_no_internal_error("fn foo(/*");
+}
@@ -4,3 +4,35 @@ use super::*;
 fn test_empty() {
   parse_str(r#""#);
 }
+
+fn assert_send_sync<T: Send + Sync>() {}
+
+#[test]
+fn test_parsed_program_is_send_sync() {
+  assert_send_sync::<Parser>();
+  assert_send_sync::<ParsingError>();
+}
+
+#[test]
+fn test_parsed_program_shared_between_threads() {
+  use std::sync::Arc;
+  use std::thread;
+
+  let res = Arc::new(parse_str(
+    r#"
+      fn foo() {}
+      fn bar() {}
+    "#,
+  ));
+  let handles: Vec<_> = (0..2)
+    .map(|i| {
+      let res = Arc::clone(&res);
+      thread::spawn(move || res.functions[i].name.clone())
+    })
+    .collect();
+  let names: Vec<_> = handles.into_iter().map(|h| h.join().unwrap()).collect();
+  assert_eq!(
+    names,
+    vec![Some(String::from("foo")), Some(String::from("bar"))]
+  );
+}
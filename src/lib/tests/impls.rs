@@ -0,0 +1,68 @@
+use super::*;
+
+#[test]
+fn test_impl_empty() {
+  let res = parse_str("impl Foo {}");
+  assert_eq!(res.impls.len(), 1);
+  assert_eq!(res.impls[0].struct_name, "Foo");
+  assert_eq!(res.impls[0].methods.len(), 0);
+}
+
+#[test]
+fn test_impl_methods() {
+  let res = parse_str(
+    r#"
+      struct Foo {
+        bar: int
+      }
+
+      impl Foo {
+        fn bar(self) int {
+          return self.bar
+        }
+
+        fn set_bar(self, bar int) {
+          self.bar = bar
+        }
+
+        fn new() Foo {
+          return Foo{bar: 1}
+        }
+      }
+    "#,
+  );
+  assert_eq!(res.functions.len(), 0);
+  let methods = &res.impls[0].methods;
+  assert_eq!(methods.len(), 3);
+
+  assert_eq!(methods[0].name.as_deref(), Some("bar"));
+  assert_eq!(methods[0].args.len(), 1);
+  assert_eq!(methods[0].args[0].0, "self");
+  assert_eq!(methods[0].args[0].1.name, "Foo");
+
+  assert_eq!(methods[1].args.len(), 2);
+  assert_eq!(methods[1].args[1].0, "bar");
+
+  // A method without self
+  assert_eq!(methods[2].args.len(), 0);
+}
+
+#[test]
+fn test_impl_self_spacing() {
+  let res = parse_str("impl Foo {\n  fn a( self ) {}\n  fn b(self , c int) {}\n}");
+  assert_eq!(res.impls[0].methods[0].args.len(), 1);
+  assert_eq!(res.impls[0].methods[1].args.len(), 2);
+}
+
+#[test]
+fn test_impl_invalid() {
+  parse_str_fail("impl {}");
+  parse_str_fail("impl Foo");
+  parse_str_fail("impl Foo {");
+  parse_str_fail("impl Foo { const a = 1 }");
+  parse_str_fail("impl Foo { fn (self) {} }");
+  parse_str_fail("impl Foo { fn a() {} fn a() {} }");
+  // self must be the first argument and is only allowed in methods
+  parse_str_fail("impl Foo { fn a(b int, self) {} }");
+  parse_str_fail("fn a(self) {}");
+}
@@ -0,0 +1,135 @@
+use super::*;
+use program::{parse_program, resolve_import};
+use std::collections::HashMap;
+
+fn load_from<'a>(files: &'a [(&str, &str)]) -> impl FnMut(&str) -> Option<Vec<u8>> + 'a {
+  let files: HashMap<&str, &str> = files.iter().cloned().collect();
+  move |path| files.get(path).map(|contents| contents.as_bytes().to_vec())
+}
+
+#[test]
+fn test_import_parse() {
+  let res = parse_str("import \"utils\"\nimport foo.bar\nimport\tbaz\n\nfn main() {}");
+  assert_eq!(res.imports.len(), 3);
+  assert_eq!(res.imports[0].path, ImportPath::File(String::from("utils")));
+  assert_eq!(res.span_text(res.imports[0].span), "\"utils\"");
+  assert_eq!(
+    res.imports[1].path,
+    ImportPath::Module(vec![String::from("foo"), String::from("bar")])
+  );
+  assert_eq!(res.span_text(res.imports[1].span), "foo.bar");
+  assert_eq!(
+    res.imports[2].path,
+    ImportPath::Module(vec![String::from("baz")])
+  );
+}
+
+#[test]
+fn test_import_invalid() {
+  parse_str_fail("import");
+  parse_str_fail("import \"\"");
+  parse_str_fail("import foo.");
+  parse_str_fail("import foo bar");
+  parse_str_fail("import \"foo\" fn main() {}");
+  parse_str_fail("import\n\"foo\"");
+}
+
+#[test]
+fn test_import_resolve() {
+  let file = |path: &str| ImportPath::File(String::from(path));
+  assert_eq!(resolve_import("", "main.tp", &file("utils")), "utils.tp");
+  assert_eq!(
+    resolve_import("src", "src/a/b.tp", &file("../c.tp")),
+    "src/c.tp"
+  );
+  assert_eq!(
+    resolve_import("/src", "/src/main.tp", &file("./d")),
+    "/src/d.tp"
+  );
+  let module = ImportPath::Module(vec![String::from("foo"), String::from("bar")]);
+  assert_eq!(
+    resolve_import("src", "src/a/b.tp", &module),
+    "src/foo/bar.tp"
+  );
+}
+
+#[test]
+fn test_import_program() {
+  let files = [
+    (
+      "src/main.tp",
+      "import \"utils\"\nimport lib.math\nfn main() {}",
+    ),
+    ("src/utils.tp", "import lib.math\nfn print() {}"),
+    (
+      "src/lib/math.tp",
+      "import \"../utils\"\nfn add(a int, b int) int {}",
+    ),
+  ];
+  let program =
+    parse_program("./src/main.tp", ParserOptions::default(), load_from(&files)).unwrap();
+  assert_eq!(program.files.len(), 3);
+  assert_eq!(program.files[0].0, "src/main.tp");
+  assert!(program.file("src/lib/math.tp").is_some());
+  assert!(program.function("add").is_some());
+  assert!(program.function("print").is_some());
+  assert!(program.function("missing").is_none());
+}
+
+#[test]
+fn test_import_program_errors() {
+  let files = [
+    ("main.tp", "import \"utils\"\nimport \"missing\"\n"),
+    ("utils.tp", "fn print() {}"),
+  ];
+  let err = parse_program("main.tp", ParserOptions::default(), load_from(&files)).unwrap_err();
+  assert_eq!(err.location.file_name.as_deref(), Some("main.tp"));
+  assert_eq!(err.location.y, 2);
+
+  let files = [
+    ("main.tp", "import \"utils\""),
+    ("utils.tp", "fn print( {}"),
+  ];
+  let err = parse_program("main.tp", ParserOptions::default(), load_from(&files)).unwrap_err();
+  assert_eq!(err.location.file_name.as_deref(), Some("utils.tp"));
+
+  assert!(parse_program("other.tp", ParserOptions::default(), load_from(&files)).is_err());
+}
+
+#[test]
+fn test_import_cycle() {
+  let files = [
+    ("main.tp", "import \"a\"\nimport \"c\""),
+    ("a.tp", "import \"b\""),
+    ("b.tp", "import \"c\""),
+    ("c.tp", "import \"a\""),
+  ];
+  let program = parse_program("main.tp", ParserOptions::default(), load_from(&files)).unwrap();
+  assert_eq!(
+    program.import_cycle().unwrap(),
+    vec!["a.tp", "b.tp", "c.tp", "a.tp"]
+  );
+
+  // Importing the same file twice is not a cycle
+  let files = [
+    ("main.tp", "import \"a\"\nimport \"b\""),
+    ("a.tp", "import \"b\""),
+    ("b.tp", ""),
+  ];
+  let program = parse_program("main.tp", ParserOptions::default(), load_from(&files)).unwrap();
+  assert_eq!(program.import_cycle(), None);
+}
+
+#[test]
+fn test_import_graph_output() {
+  let files = [("main.tp", "import \"utils\""), ("utils.tp", "")];
+  let program = parse_program("main.tp", ParserOptions::default(), load_from(&files)).unwrap();
+  assert_eq!(
+    program.graph_dot(),
+    "digraph imports {\n  \"main.tp\";\n  \"main.tp\" -> \"utils.tp\";\n  \"utils.tp\";\n}"
+  );
+  assert_eq!(
+    program.graph_json(),
+    r#"{"imports": {"main.tp": ["utils.tp"], "utils.tp": []}, "cycle": null}"#
+  );
+}
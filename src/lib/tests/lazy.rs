@@ -0,0 +1,85 @@
+use super::*;
+
+fn parse_lazy(contents: &str) -> Parser {
+  let options = ParserOptions {
+    lazy_bodies: true,
+    ..ParserOptions::default()
+  };
+  Parser::parse_with_options(contents.as_bytes(), options).unwrap()
+}
+
+#[test]
+fn test_lazy_bodies_are_skipped() {
+  let res = parse_lazy(
+    r#"
+      fn foo(a int) string {
+        if a > 1 {
+          return "}"
+        }
+        // }
+        return `{`
+      }
+      fn bar() {}
+    "#,
+  );
+  assert_eq!(res.functions.len(), 2);
+  assert_eq!(res.functions[0].args.len(), 1);
+  assert!(res.functions[0].return_type.is_some());
+  assert_eq!(res.functions[0].body.actions.len(), 0);
+  assert!(res.functions[0].lazy_body.is_some());
+}
+
+#[test]
+fn test_lazy_bodies_parse_body() {
+  let mut res = parse_lazy(
+    r#"
+      fn foo() {
+        let a = "\"{"
+        fn nested() {}
+        return a
+      }
+      const bar = 1
+    "#,
+  );
+  assert_eq!(res.globals.len(), 1);
+  let index = res.function_index("foo").unwrap();
+  res.parse_body(index).unwrap();
+  assert_eq!(res.functions[index].body.actions.len(), 3);
+  assert!(res.functions[index].lazy_body.is_none());
+  match &res.functions[index].body.actions[1] {
+    Action::FunctionDecl(function) => assert!(function.lazy_body.is_none()),
+    action => panic!("expected a function, got {:?}", action),
+  }
+  // Parsing it again does nothing
+  res.parse_body(index).unwrap();
+  assert_eq!(res.functions[index].body.actions.len(), 3);
+}
+
+#[test]
+fn test_lazy_bodies_errors() {
+  let mut res = parse_lazy(
+    r#"
+      fn foo() {}
+      fn bar() {
+        let = 1
+      }
+    "#,
+  );
+  // Only the parsed bodies can contain errors
+  res.parse_body(0).unwrap();
+  let err = res.parse_body(1).unwrap_err();
+  assert_eq!(err.location.y, 4);
+  assert!(res.functions[1].lazy_body.is_some());
+  assert!(res.parse_bodies().is_err());
+}
+
+#[test]
+fn test_lazy_bodies_unclosed() {
+  let options = ParserOptions {
+    lazy_bodies: true,
+    ..ParserOptions::default()
+  };
+  assert!(Parser::parse_with_options("fn foo() { {}".as_bytes(), options.clone()).is_err());
+  assert!(Parser::parse_with_options("fn foo() { \"}".as_bytes(), options.clone()).is_err());
+  assert!(Parser::parse_with_options("fn foo() { /* } */".as_bytes(), options).is_err());
+}
@@ -0,0 +1,87 @@
+use super::*;
+
+fn parse_str_with_options(
+  contents: &str,
+  options: ParserOptions,
+) -> Result<Parser, Box<ParsingError>> {
+  Parser::parse_with_options(contents.as_bytes(), options)
+}
+
+#[test]
+fn test_limits_string_len() {
+  let options = ParserOptions {
+    max_string_len: Some(5),
+    ..ParserOptions::default()
+  };
+  assert!(parse_str_with_options(r#"const foo = "abcde""#, options.clone()).is_ok());
+  assert!(parse_str_with_options(r#"const foo = "abcdef""#, options).is_err());
+}
+
+#[test]
+fn test_limits_name_len() {
+  let options = ParserOptions {
+    max_name_len: Some(3),
+    ..ParserOptions::default()
+  };
+  assert!(parse_str_with_options("fn foo() {}", options.clone()).is_ok());
+  assert!(parse_str_with_options("fn fooo() {}", options).is_err());
+}
+
+#[test]
+fn test_limits_function_arguments() {
+  let options = ParserOptions {
+    max_arguments: Some(2),
+    ..ParserOptions::default()
+  };
+  assert!(parse_str_with_options("fn foo(a int, b int) {}", options.clone()).is_ok());
+  assert!(parse_str_with_options("fn foo(a int, b int, c int) {}", options).is_err());
+}
+
+#[test]
+fn test_limits_call_arguments() {
+  let options = ParserOptions {
+    max_arguments: Some(2),
+    ..ParserOptions::default()
+  };
+  assert!(parse_str_with_options("fn foo() {\n  bar(1, 2)\n}", options.clone()).is_ok());
+  assert!(parse_str_with_options("fn foo() {\n  bar(1, 2, 3)\n}", options).is_err());
+}
+
+#[test]
+fn test_limits_memory() {
+  let options = ParserOptions {
+    max_memory: Some(4096),
+    ..ParserOptions::default()
+  };
+  let small = "fn foo() {\n  bar(1, 2)\n}";
+  assert!(parse_str_with_options(small, options.clone()).is_ok());
+
+  let many_calls = format!("fn foo() {{\n{}}}", "  bar(1, 2)\n".repeat(100));
+  let err = parse_str_with_options(&many_calls, options.clone()).unwrap_err();
+  assert_eq!(
+    err.error_type,
+    ParsingErrorType::ResourceExhausted("memory")
+  );
+
+  let long_string = format!("const foo = \"{}\"", "a".repeat(5000));
+  let err = parse_str_with_options(&long_string, options.clone()).unwrap_err();
+  assert_eq!(
+    err.error_type,
+    ParsingErrorType::ResourceExhausted("memory")
+  );
+
+  // The contents itself also count
+  let long_comment = format!("// {}", "a".repeat(5000));
+  let err = parse_str_with_options(&long_comment, options).unwrap_err();
+  assert_eq!(
+    err.error_type,
+    ParsingErrorType::ResourceExhausted("memory")
+  );
+}
+
+#[test]
+fn test_limits_memory_used() {
+  let res = parse_str("fn foo() {\n  bar(1, 2)\n}");
+  assert!(res.memory_used > res.contents.len());
+  assert_eq!(parse_str("").memory_used, 0);
+}
@@ -145,3 +145,263 @@ fn test_while_incorrect_args() {
     "#,
   );
 }
+
+#[test]
+fn test_loop_has_no_condition() {
+  let res = parse_str(
+    r#"
+      fn test() {
+        loop {
+          foo()
+        }
+      }
+    "#,
+  );
+  match &res.functions[0].body.actions[0] {
+    Action::Loop(body) => assert_eq!(body.actions.len(), 1),
+    action => panic!("expected a loop, got {:?}", action),
+  }
+}
+
+#[test]
+fn test_for_range() {
+  let res = parse_str(
+    r#"
+      fn test(n int) {
+        for i in 0..10 {}
+        for i in 0..=n {}
+        for i in 1..n+1 {
+          print(i)
+        }
+      }
+    "#,
+  );
+  let body = &res.functions[0].body.actions;
+  assert_eq!(body.len(), 3);
+  for (action, inclusive) in body.iter().zip([false, true, false].iter()) {
+    match action {
+      Action::For(meta) => {
+        assert_eq!(meta.item_name, "i");
+        match *meta.list {
+          Action::Range(ref range) => assert_eq!(range.inclusive, *inclusive),
+          ref action => panic!("expected a range, got {:?}", action),
+        }
+      }
+      action => panic!("expected a for loop, got {:?}", action),
+    }
+  }
+}
+
+#[test]
+fn test_loop_as_value() {
+  let res = parse_str(
+    r#"
+      fn test() {
+        let x = loop {
+          break 5
+        }
+        let y = loop { break x + 1 }
+        return loop { break }
+      }
+    "#,
+  );
+  match &res.functions[0].body.actions[0] {
+    Action::Variable(meta) => match *meta.action {
+      Action::Loop(ref body) => match body.actions[0] {
+        Action::Break(Some(ref action)) => match **action {
+          Action::StaticNumber(NumberLiteral {
+            value: NumberValue::Int(5, _),
+            ..
+          }) => {}
+          ref action => panic!("expected the number 5, got {:?}", action),
+        },
+        ref action => panic!("expected a break with a value, got {:?}", action),
+      },
+      ref action => panic!("expected a loop, got {:?}", action),
+    },
+    action => panic!("expected a variable, got {:?}", action),
+  }
+}
+
+#[test]
+fn test_break_value_same_line() {
+  let res = parse_str(
+    r#"
+      fn test() {
+        loop {
+          break
+          foo()
+        }
+        loop {
+          break // done
+          foo()
+        }
+      }
+    "#,
+  );
+  for action in &res.functions[0].body.actions {
+    match action {
+      Action::Loop(body) => {
+        assert_eq!(body.actions.len(), 2);
+        match body.actions[0] {
+          Action::Break(None) => {}
+          ref action => panic!("expected a break without a value, got {:?}", action),
+        }
+      }
+      action => panic!("expected a loop, got {:?}", action),
+    }
+  }
+}
+
+#[test]
+fn test_continue() {
+  let res = parse_str(
+    r#"
+      fn test(items []int) {
+        loop { continue }
+        for item in items {
+          if item > 2 {
+            continue
+          }
+          continue // skip the rest
+          foo(item)
+        }
+      }
+    "#,
+  );
+  match &res.functions[0].body.actions[0] {
+    Action::Loop(body) => match body.actions[..] {
+      [Action::Continue] => {}
+      ref actions => panic!("expected a continue, got {:?}", actions),
+    },
+    action => panic!("expected a loop, got {:?}", action),
+  }
+  match &res.functions[0].body.actions[1] {
+    Action::For(meta) => {
+      assert_eq!(meta.body.actions.len(), 3);
+      match meta.body.actions[1] {
+        Action::Continue => {}
+        ref action => panic!("expected a continue, got {:?}", action),
+      }
+    }
+    action => panic!("expected a for loop, got {:?}", action),
+  }
+  parse_str_fail("fn test() { loop { continue 5 } }");
+}
+
+#[test]
+fn test_only_loop_as_value() {
+  parse_str_fail("const foo = while true {}");
+  parse_str_fail("const foo = for i in items {}");
+  parse_str("const foo = loops");
+}
+
+#[test]
+fn test_for_classic() {
+  let res = parse_str(
+    r#"
+      fn test(n int) {
+        for let i = 0; i < n; i += 1 {
+          print(i)
+        }
+        for let i: int = n;i > 0;i = i - 1 {}
+        for let letter = first(); letter != null; letter = next(letter) {}
+      }
+    "#,
+  );
+  let body = &res.functions[0].body.actions;
+  assert_eq!(body.len(), 3);
+  match &body[0] {
+    Action::ForClassic(meta) => {
+      assert_eq!(meta.init.name, "i");
+      assert!(matches!(*meta.condition, Action::Comparison(_)));
+      match *meta.update {
+        Action::Assigment(ref assignment) => assert_eq!(assignment.name, "i"),
+        ref action => panic!("expected a assignment, got {:?}", action),
+      }
+      assert_eq!(meta.body.actions.len(), 1);
+    }
+    action => panic!("expected a classic for loop, got {:?}", action),
+  }
+  assert!(matches!(body[2], Action::ForClassic(_)));
+}
+
+#[test]
+fn test_for_classic_invalid() {
+  parse_str_fail("fn test() { for let i = 0 {} }");
+  parse_str_fail("fn test() { for let i = 0; i < 10 {} }");
+  parse_str_fail("fn test() { for let i = 0; i < 10; i {} }");
+  parse_str_fail("fn test() { for let i = 0; i < 10; i += 1 }");
+  parse_str_fail("fn test() { for let = 0; i < 10; i += 1 {} }");
+  parse_str_fail("fn test() { for let i = 0; ; i += 1 {} }");
+}
+
+#[test]
+fn test_for_with_index() {
+  let res = parse_str(
+    r#"
+      fn test(items []string) {
+        for i, item in items {
+          print(i, item)
+        }
+        for i ,item in items {}
+        for item in items {}
+      }
+    "#,
+  );
+  let body = &res.functions[0].body.actions;
+  for (action, index_name) in body.iter().zip([Some("i"), Some("i"), None].iter()) {
+    match action {
+      Action::For(meta) => {
+        assert_eq!(meta.item_name, "item");
+        assert_eq!(meta.index_name.as_deref(), *index_name);
+      }
+      action => panic!("expected a for loop, got {:?}", action),
+    }
+  }
+}
+
+#[test]
+fn test_for_with_index_invalid() {
+  parse_str_fail("fn test() { for i, in items {} }");
+  parse_str_fail("fn test() { for , item in items {} }");
+  parse_str_fail("fn test() { for i, item, other in items {} }");
+  parse_str_fail("fn test() { for i item in items {} }");
+}
+
+#[test]
+fn test_do_while() {
+  let res = parse_str(
+    r#"
+      fn test() {
+        let i = 0
+        do {
+          i += 1
+        } while i < 10
+        do { read() }
+        while more()
+        fn inline() { do {} while false }
+        let done = true
+      }
+    "#,
+  );
+  let body = &res.functions[0].body.actions;
+  assert_eq!(body.len(), 5);
+  match &body[1] {
+    Action::DoWhile(meta) => {
+      assert_eq!(meta.body.actions.len(), 1);
+      assert!(matches!(*meta.true_value, Action::Comparison(_)));
+    }
+    action => panic!("expected a do while loop, got {:?}", action),
+  }
+  assert!(matches!(body[2], Action::DoWhile(_)));
+}
+
+#[test]
+fn test_do_while_invalid() {
+  parse_str_fail("fn test() { do {} }");
+  parse_str_fail("fn test() { do {} while }");
+  parse_str_fail("fn test() { do while true }");
+  parse_str_fail("fn test() { do {} until true }");
+  parse_str_fail("fn test() { let x = do {} while true }");
+}
@@ -0,0 +1,104 @@
+use super::*;
+
+fn parse_value(value: &str) -> Vec<(Action, Action)> {
+  let mut res = parse_str(format!("const foo = {}", value));
+  match *res.globals.remove(0).action {
+    Action::MapInit(entries) => entries,
+    action => panic!("expected a inline map, got {:?}", action),
+  }
+}
+
+fn parse_type(type_: &str) -> Type {
+  let mut res = parse_str(format!("fn test(foo {}) {{}}", type_));
+  res.functions.remove(0).args.remove(0).1
+}
+
+#[test]
+fn test_map_init_empty() {
+  assert_eq!(parse_value("{}").len(), 0);
+  assert_eq!(parse_value("{ }").len(), 0);
+}
+
+#[test]
+fn test_map_init_entries() {
+  let res = parse_value(r#"{"key": value, other: 1, "sum": a + b}"#);
+  assert_eq!(res.len(), 3);
+  match &res[0].0 {
+    Action::StaticString(_) => {}
+    action => panic!("expected a string, got {:?}", action),
+  }
+  match &res[1].0 {
+    Action::VarRef(name) => assert_eq!(name, "other"),
+    action => panic!("expected a variable reference, got {:?}", action),
+  }
+}
+
+#[test]
+fn test_map_init_nested_and_trailing_comma() {
+  let res = parse_value(
+    r#"{
+      "a": {"b": 1},
+      "c": [1, 2],
+    }"#,
+  );
+  assert_eq!(res.len(), 2);
+  match &res[0].1 {
+    Action::MapInit(entries) => assert_eq!(entries.len(), 1),
+    action => panic!("expected a inline map, got {:?}", action),
+  }
+}
+
+#[test]
+fn test_map_init_invalid() {
+  parse_str_fail(r#"const foo = {"a" 1}"#);
+  parse_str_fail(r#"const foo = {"a": 1 "b": 2}"#);
+  parse_str_fail(r#"const foo = {"a": 1"#);
+  parse_str_fail(r#"const foo = {"a":}"#);
+}
+
+#[test]
+fn test_map_init_not_in_condition() {
+  // The `{` is the start of the if body and not a map
+  parse_str_fail(
+    r#"
+      fn test() {
+        if {"a": 1} {}
+      }
+    "#,
+  );
+}
+
+#[test]
+fn test_map_type() {
+  let res = parse_type("map[string]int");
+  assert_eq!(res.name, "map");
+  let (key, value) = *res.map.unwrap();
+  assert_eq!(key.name, "string");
+  assert_eq!(value.name, "int");
+}
+
+#[test]
+fn test_map_type_nested() {
+  let res = parse_type("map[[]string]map[string][]int");
+  let (key, value) = *res.map.unwrap();
+  assert_eq!(key.name, "[]string");
+  assert_eq!(value.name, "map");
+  let (key, value) = *value.map.unwrap();
+  assert_eq!(key.name, "string");
+  assert_eq!(value.name, "[]int");
+}
+
+#[test]
+fn test_map_type_in_variable() {
+  parse_str(
+    r#"
+      const config: map[string]int = {"retries": 3}
+    "#,
+  );
+}
+
+#[test]
+fn test_map_type_invalid() {
+  parse_str_fail("fn test(foo map[]int) {}");
+  parse_str_fail("fn test(foo map[string]) {}");
+}
@@ -0,0 +1,134 @@
+use super::*;
+
+fn parse_value(value: &str) -> ActionMatch {
+  let mut res = parse_str(format!("const foo = {}", value));
+  match *res.globals.remove(0).action {
+    Action::Match(meta) => meta,
+    action => panic!("expected a match, got {:?}", action),
+  }
+}
+
+#[test]
+fn test_match_expression() {
+  let res = parse_value("match x { 1 => foo(), _ => bar() }");
+  match *res.subject {
+    Action::VarRef(ref name) => assert_eq!(name, "x"),
+    ref action => panic!("expected a var ref, got {:?}", action),
+  }
+  assert_eq!(res.arms.len(), 2);
+  match &res.arms[0].pattern {
+    MatchPattern::Literal(Action::StaticNumber(NumberLiteral {
+      value: NumberValue::Int(1, _),
+      ..
+    })) => {}
+    pattern => panic!("expected the literal 1, got {:?}", pattern),
+  }
+  match &res.arms[1].pattern {
+    MatchPattern::Wildcard => {}
+    pattern => panic!("expected a wildcard, got {:?}", pattern),
+  }
+  for arm in &res.arms {
+    assert_eq!(arm.body.actions.len(), 1);
+    match arm.body.actions[0] {
+      Action::FunctionCall(_) => {}
+      ref action => panic!("expected a function call, got {:?}", action),
+    }
+  }
+}
+
+#[test]
+fn test_match_literal_patterns() {
+  let res = parse_value(r#"match x { "a" => 1, -1 => 2, true => 3, 1.5 => 4, _=>5 }"#);
+  assert_eq!(res.arms.len(), 5);
+  for arm in &res.arms[..4] {
+    match arm.pattern {
+      MatchPattern::Literal(_) => {}
+      ref pattern => panic!("expected a literal, got {:?}", pattern),
+    }
+  }
+}
+
+#[test]
+fn test_match_enum_variant_patterns() {
+  let res = parse_value("match shape { Shape.Empty => 0, Shape.Rect(w, h) => w * h }");
+  match &res.arms[0].pattern {
+    MatchPattern::EnumVariant {
+      enum_name,
+      variant,
+      bindings,
+    } => {
+      assert_eq!(enum_name, "Shape");
+      assert_eq!(variant, "Empty");
+      assert!(bindings.is_empty());
+    }
+    pattern => panic!("expected an enum variant, got {:?}", pattern),
+  }
+  match &res.arms[1].pattern {
+    MatchPattern::EnumVariant {
+      variant, bindings, ..
+    } => {
+      assert_eq!(variant, "Rect");
+      assert_eq!(bindings, &vec!["w".to_string(), "h".to_string()]);
+    }
+    pattern => panic!("expected an enum variant, got {:?}", pattern),
+  }
+}
+
+#[test]
+fn test_match_statement() {
+  let res = parse_str(
+    r#"
+      fn test() {
+        match x {
+          1 => {
+            foo()
+            bar()
+          }
+          2 => baz
+          _ => {}
+        }
+        print(x)
+      }
+    "#,
+  );
+  let body = &res.functions[0].body.actions;
+  assert_eq!(body.len(), 2);
+  match &body[0] {
+    Action::Match(meta) => {
+      assert_eq!(meta.arms.len(), 3);
+      assert_eq!(meta.arms[0].body.actions.len(), 2);
+      assert_eq!(meta.arms[1].body.actions.len(), 1);
+      assert_eq!(meta.arms[2].body.actions.len(), 0);
+    }
+    action => panic!("expected a match, got {:?}", action),
+  }
+}
+
+#[test]
+fn test_match_empty() {
+  assert_eq!(parse_value("match x {}").arms.len(), 0);
+  assert_eq!(parse_value("match x {\n}").arms.len(), 0);
+}
+
+#[test]
+fn test_match_keyword_prefix_is_a_name() {
+  parse_str("const foo = matches");
+  parse_str("fn test() { matched = 1 }");
+}
+
+#[test]
+fn test_match_invalid() {
+  // Missing braces
+  parse_str_fail("const foo = match x 1 => foo()");
+  parse_str_fail("const foo = match x { 1 => foo()");
+  parse_str_fail("fn test() { match x }");
+  // Empty arm bodies
+  parse_str_fail("const foo = match x { 1 => }");
+  parse_str_fail("const foo = match x { 1 =>, _ => 2 }");
+  // Missing arrow
+  parse_str_fail("const foo = match x { 1 foo() }");
+  parse_str_fail("const foo = match x { 1 = foo() }");
+  // Invalid patterns
+  parse_str_fail("const foo = match x { a + b => 1 }");
+  parse_str_fail("const foo = match x { Shape.Rect(1) => 1 }");
+}
@@ -0,0 +1,283 @@
+use super::*;
+
+fn parse_value(value: &str) -> Action {
+  let mut res = parse_str(format!("const foo = {}", value));
+  *res.globals.remove(0).action
+}
+
+// Formats a member chain like the source code to make the structure visible
+fn fmt(action: &Action) -> String {
+  match action {
+    Action::VarRef(name) => name.clone(),
+    Action::FunctionCall(meta) => format!("{}({})", meta.name, meta.arguments.len()),
+    Action::FieldAccess(meta) => format!(
+      "[{}]{}{}",
+      fmt(&meta.target),
+      if meta.optional { "?." } else { "." },
+      meta.field
+    ),
+    Action::MethodCall(meta) => format!(
+      "[{}]{}{}({})",
+      fmt(&meta.target),
+      if meta.optional { "?." } else { "." },
+      meta.name,
+      meta.arguments.len()
+    ),
+    Action::Index(meta) => format!("[{}][{}]", fmt(&meta.target), fmt(&meta.index)),
+    Action::StaticNumber(NumberLiteral {
+      value: NumberValue::Int(number, _),
+      ..
+    }) => number.to_string(),
+    action => format!("{:?}", action),
+  }
+}
+
+#[test]
+fn test_member_field_access() {
+  assert_eq!(fmt(&parse_value("foo.bar")), "[foo].bar");
+  assert_eq!(fmt(&parse_value("foo.bar.baz")), "[[foo].bar].baz");
+}
+
+#[test]
+fn test_member_method_call() {
+  assert_eq!(fmt(&parse_value("foo.bar()")), "[foo].bar(0)");
+  assert_eq!(fmt(&parse_value("foo.bar(a, 1)")), "[foo].bar(2)");
+}
+
+#[test]
+fn test_member_chain() {
+  assert_eq!(fmt(&parse_value("foo.bar().baz")), "[[foo].bar(0)].baz");
+  assert_eq!(fmt(&parse_value("foo(1).bar")), "[foo(1)].bar");
+  assert_eq!(fmt(&parse_value("foo(a.b).c()")), "[foo(1)].c(0)");
+}
+
+#[test]
+fn test_member_on_other_values() {
+  match parse_value(r#""foo".len()"#) {
+    Action::MethodCall(meta) => match *meta.target {
+      Action::StaticString(_) => {}
+      action => panic!("expected a string, got {:?}", action),
+    },
+    action => panic!("expected a method call, got {:?}", action),
+  }
+  match parse_value("(a + b).c") {
+    Action::FieldAccess(meta) => match *meta.target {
+      Action::BinaryOp(_) => {}
+      action => panic!("expected a binary operator, got {:?}", action),
+    },
+    action => panic!("expected a field access, got {:?}", action),
+  }
+}
+
+#[test]
+fn test_member_in_expression() {
+  match parse_value("foo.bar + baz.len()") {
+    Action::BinaryOp(meta) => {
+      assert_eq!(fmt(&meta.left), "[foo].bar");
+      assert_eq!(fmt(&meta.right), "[baz].len(0)");
+    }
+    action => panic!("expected a binary operator, got {:?}", action),
+  }
+}
+
+#[test]
+fn test_member_float_is_not_a_member() {
+  match parse_value("1.5") {
+    Action::StaticNumber(NumberLiteral {
+      value: NumberValue::Float(_),
+      ..
+    }) => {}
+    action => panic!("expected a float, got {:?}", action),
+  }
+}
+
+#[test]
+fn test_member_in_body() {
+  let res = parse_str(
+    r#"
+      fn test() {
+        foo.bar()
+        foo.bar.baz(1)
+        foo.bar = 2
+        foo.bar().baz = 3
+      }
+    "#,
+  );
+  let list = &res.functions[0].body.actions;
+  assert_eq!(list.len(), 4);
+  match &list[2] {
+    Action::FieldAssigment(meta) => {
+      assert_eq!(fmt(&meta.target), "foo");
+      assert_eq!(meta.field, "bar");
+    }
+    action => panic!("expected a field assignment, got {:?}", action),
+  }
+  match &list[3] {
+    Action::FieldAssigment(meta) => assert_eq!(fmt(&meta.target), "[foo].bar(0)"),
+    action => panic!("expected a field assignment, got {:?}", action),
+  }
+}
+
+#[test]
+fn test_member_invalid() {
+  parse_str_fail("const foo = foo.");
+  parse_str_fail("const foo = foo...bar");
+  parse_str_fail("const foo = foo.bar(");
+  parse_str_fail(
+    r#"
+      fn test() {
+        foo.bar
+      }
+    "#,
+  );
+}
+
+#[test]
+fn test_index() {
+  assert_eq!(fmt(&parse_value("items[0]")), "[items][0]");
+  assert_eq!(fmt(&parse_value("items[ i ]")), "[items][i]");
+  match parse_value("items[i + 1]") {
+    Action::Index(meta) => match *meta.index {
+      Action::BinaryOp(_) => {}
+      action => panic!("expected a binary operator, got {:?}", action),
+    },
+    action => panic!("expected a index, got {:?}", action),
+  }
+}
+
+#[test]
+fn test_index_nested() {
+  assert_eq!(fmt(&parse_value("matrix[i][j]")), "[[matrix][i]][j]");
+  assert_eq!(fmt(&parse_value("a[b[0]]")), "[a][[b][0]]");
+}
+
+#[test]
+fn test_index_in_chain() {
+  assert_eq!(fmt(&parse_value("foo()[0]")), "[foo(0)][0]");
+  assert_eq!(
+    fmt(&parse_value("foo.bar[0].baz()")),
+    "[[[foo].bar][0]].baz(0)"
+  );
+  match parse_value("[1, 2][0]") {
+    Action::Index(meta) => match *meta.target {
+      Action::ArrayInit(_) => {}
+      action => panic!("expected a inline array, got {:?}", action),
+    },
+    action => panic!("expected a index, got {:?}", action),
+  }
+}
+
+#[test]
+fn test_index_in_body() {
+  parse_str(
+    r#"
+      fn test(items []int) {
+        let first = items[0]
+        print(items[first + 1])
+        items[0].bar = 1
+      }
+    "#,
+  );
+}
+
+#[test]
+fn test_index_unclosed() {
+  parse_str_fail("const foo = items[0");
+  parse_str_fail("const foo = items[0 1]");
+  parse_str_fail("const foo = items[]");
+  parse_str_fail("const foo = matrix[0][1");
+  parse_str_fail(
+    r#"
+      fn test() {
+        items[0]
+      }
+    "#,
+  );
+}
+
+#[test]
+fn test_null_assert_in_chain() {
+  match parse_value("foo.bar()!.baz") {
+    Action::FieldAccess(meta) => match *meta.target {
+      Action::NullAssert(action) => assert_eq!(fmt(&action), "[foo].bar(0)"),
+      action => panic!("expected a null assertion, got {:?}", action),
+    },
+    action => panic!("expected a field access, got {:?}", action),
+  }
+  match parse_value("foo! != bar") {
+    Action::Comparison(meta) => match *meta.left {
+      Action::NullAssert(_) => {}
+      action => panic!("expected a null assertion, got {:?}", action),
+    },
+    action => panic!("expected a comparison, got {:?}", action),
+  }
+  match parse_value("foo!=bar") {
+    Action::Comparison(meta) => assert_eq!(fmt(&meta.left), "foo"),
+    action => panic!("expected a comparison, got {:?}", action),
+  }
+}
+
+#[test]
+fn test_null_assert_in_body() {
+  parse_str(
+    r#"
+      fn test() {
+        foo!.bar()
+        let a = foo()! ?? 1
+        items[0]!.bar = 2
+      }
+    "#,
+  );
+  parse_str_fail(
+    r#"
+      fn test() {
+        foo!
+      }
+    "#,
+  );
+}
+
+#[test]
+fn test_optional_chain() {
+  assert_eq!(fmt(&parse_value("foo?.bar")), "[foo]?.bar");
+  assert_eq!(fmt(&parse_value("foo?.bar()")), "[foo]?.bar(0)");
+  assert_eq!(
+    fmt(&parse_value("foo.bar()?.baz.qux")),
+    "[[[foo].bar(0)]?.baz].qux"
+  );
+  assert_eq!(fmt(&parse_value("foo(1)?.bar")), "[foo(1)]?.bar");
+  match parse_value("foo?.bar ?? baz") {
+    Action::NullCoalesce(meta) => {
+      assert_eq!(fmt(&meta.left), "[foo]?.bar");
+      assert_eq!(fmt(&meta.right), "baz");
+    }
+    action => panic!("expected a null coalesce, got {:?}", action),
+  }
+  match parse_value("foo??bar") {
+    Action::NullCoalesce(meta) => assert_eq!(fmt(&meta.left), "foo"),
+    action => panic!("expected a null coalesce, got {:?}", action),
+  }
+}
+
+#[test]
+fn test_optional_chain_in_body() {
+  parse_str(
+    r#"
+      fn test() {
+        foo?.bar()
+        let a = foo?.bar ?? 1
+        foo?.bar.baz = 2
+      }
+    "#,
+  );
+  parse_str_fail(
+    r#"
+      fn test() {
+        foo?.bar = 1
+      }
+    "#,
+  );
+  parse_str_fail("const foo = bar?.0");
+  parse_str_fail("const foo = bar?..baz");
+  parse_str_fail("const foo = bar?");
+}
@@ -1,8 +1,51 @@
+mod aliases;
+mod arrays;
+mod blocks;
+mod cancel;
+mod closures;
 mod comments;
+mod conditionals;
+mod crash;
+mod cursor;
+mod edit;
+mod enums;
+mod examples;
+mod expressions;
+mod features;
+mod format;
 mod functions;
+mod fuzz;
 mod general;
+mod impls;
+mod imports;
+mod lazy;
+mod limits;
 mod loops;
+mod maps;
+mod matches;
+mod members;
+mod numbers;
+mod optionals;
+mod performance;
+mod plugins;
+mod query;
+mod reachability;
+mod reduce;
+mod source_map;
+mod spec;
+mod statements;
+mod stats;
+mod strings;
+mod structs;
+mod tags;
+#[cfg(feature = "trace-parse")]
+mod trace;
+mod traits;
+mod tuples;
+mod validate;
 mod variables;
+mod versions;
+mod visibility;
 
 use super::*;
 
@@ -11,14 +54,31 @@ pub fn parse_str(contents: impl Into<String>) -> Parser {
   Parser::parse(contents.into().as_bytes()).unwrap()
 }
 
+// Options with the experimental pipes enabled
+pub fn pipes() -> ParserOptions {
+  ParserOptions {
+    experimental: ExperimentalFeatures::PIPES,
+    ..ParserOptions::default()
+  }
+}
+
+// Parse a string of code that uses pipes
+pub fn parse_str_with_pipes(contents: impl Into<String>) -> Parser {
+  Parser::parse_with_options(contents.into().as_bytes(), pipes()).unwrap()
+}
+
 // Parse a string of code that is meant to fail
 pub fn parse_str_fail(contents: impl Into<String>) {
   // Parse the code
   let res = Parser::parse(contents.into().as_bytes());
-  // If the code parsed without error
-  if let Ok(parsed_content) = res {
-    // There is a problem with the parser
+  match res {
+    // If the code parsed without error there is a problem with the parser
     // Output the result in an error (failing the test)
-    panic!("{:?}", parsed_content);
+    Ok(parsed_content) => panic!("{:?}", parsed_content),
+    // A bug in the parser is not the error we are looking for
+    Err(err) if matches!(err.error_type, ParsingErrorType::InternalError(_)) => {
+      panic!("{}", err)
+    }
+    Err(_) => {}
   }
 }
@@ -0,0 +1,253 @@
+use super::*;
+
+fn parse_value(value: &str) -> Action {
+  let mut res = parse_str(format!("const foo = {}", value));
+  *res.globals.remove(0).action
+}
+
+fn parse_float(value: &str) -> f64 {
+  match parse_value(value) {
+    Action::StaticNumber(NumberLiteral {
+      value: NumberValue::Float(number),
+      ..
+    }) => number,
+    action => panic!("expected a float, got {:?}", action),
+  }
+}
+
+#[test]
+fn test_number_int() {
+  match parse_value("123") {
+    Action::StaticNumber(NumberLiteral {
+      value: NumberValue::Int(123, Radix::Decimal),
+      ..
+    }) => {}
+    action => panic!("expected a int, got {:?}", action),
+  }
+}
+
+#[test]
+fn test_number_float() {
+  assert_eq!(parse_float("1.5"), 1.5);
+  assert_eq!(parse_float(".5"), 0.5);
+  assert_eq!(parse_float("2e10"), 2e10);
+  assert_eq!(parse_float("2E-3"), 2e-3);
+  assert_eq!(parse_float("1.5e+3"), 1.5e3);
+}
+
+#[test]
+fn test_number_float_in_expression() {
+  match parse_value("a-2e-1 - .5") {
+    Action::BinaryOp(meta) => {
+      match *meta.right {
+        Action::StaticNumber(NumberLiteral {
+          value: NumberValue::Float(number),
+          ..
+        }) => assert_eq!(number, 0.5),
+        ref action => panic!("expected a float, got {:?}", action),
+      }
+      match *meta.left {
+        Action::BinaryOp(ref left) => match *left.right {
+          Action::StaticNumber(NumberLiteral {
+            value: NumberValue::Float(number),
+            ..
+          }) => assert_eq!(number, 2e-1),
+          ref action => panic!("expected a float, got {:?}", action),
+        },
+        ref action => panic!("expected a binary operator, got {:?}", action),
+      }
+    }
+    action => panic!("expected a binary operator, got {:?}", action),
+  }
+  match parse_value("1-2") {
+    Action::BinaryOp(_) => {}
+    action => panic!("expected a binary operator, got {:?}", action),
+  }
+}
+
+#[test]
+fn test_number_float_invalid() {
+  parse_str_fail("const foo = 1.2.3");
+  parse_str_fail("const foo = 1e");
+  parse_str_fail("const foo = 1e5e5");
+  parse_str_fail("const foo = 1.5a");
+  parse_str_fail("const foo = .");
+}
+
+#[test]
+fn test_number_not_a_name() {
+  parse_str_fail("const foo = 1abc");
+  parse_str_fail("const foo = 0xffz");
+  parse_str_fail("const 1foo = 1");
+}
+
+fn parse_int(value: &str) -> (i64, Radix) {
+  match parse_value(value) {
+    Action::StaticNumber(NumberLiteral {
+      value: NumberValue::Int(number, radix),
+      ..
+    }) => (number, radix),
+    action => panic!("expected a int, got {:?}", action),
+  }
+}
+
+#[test]
+fn test_number_radix() {
+  assert_eq!(parse_int("0xFF"), (255, Radix::Hexadecimal));
+  assert_eq!(parse_int("0xe"), (14, Radix::Hexadecimal));
+  assert_eq!(parse_int("0o755"), (493, Radix::Octal));
+  assert_eq!(parse_int("0b1010"), (10, Radix::Binary));
+  assert_eq!(parse_int("10"), (10, Radix::Decimal));
+}
+
+#[test]
+fn test_number_radix_in_expression() {
+  // The `e` of a hexadecimal number is not a exponent
+  match parse_value("0xE-1") {
+    Action::BinaryOp(meta) => match *meta.left {
+      Action::StaticNumber(NumberLiteral {
+        value: NumberValue::Int(14, Radix::Hexadecimal),
+        ..
+      }) => {}
+      ref action => panic!("expected a hexadecimal int, got {:?}", action),
+    },
+    action => panic!("expected a binary operator, got {:?}", action),
+  }
+}
+
+#[test]
+fn test_number_radix_invalid_digits() {
+  parse_str_fail("const foo = 0xZZ");
+  parse_str_fail("const foo = 0o8");
+  parse_str_fail("const foo = 0b102");
+  parse_str_fail("const foo = 0x");
+  parse_str_fail("const foo = 0x1.5");
+  parse_str_fail("const foo = 0x+1");
+}
+
+fn parse_literal(value: &str) -> NumberLiteral {
+  match parse_value(value) {
+    Action::StaticNumber(literal) => literal,
+    action => panic!("expected a number, got {:?}", action),
+  }
+}
+
+#[test]
+fn test_number_literal() {
+  let res = parse_literal("0xFF");
+  assert_eq!(res.raw, "0xFF");
+  assert_eq!(res.suffix, None);
+  assert_eq!(res.span, Span::new(12, 16));
+}
+
+#[test]
+fn test_number_suffix() {
+  let res = parse_literal("10u8");
+  assert_eq!(res.value, NumberValue::Int(10, Radix::Decimal));
+  assert_eq!(res.suffix, Some(NumberType::U8));
+
+  let res = parse_literal("1.5f32");
+  assert_eq!(res.value, NumberValue::Float(1.5));
+  assert_eq!(res.suffix, Some(NumberType::F32));
+
+  // A int with a float suffix is a float
+  assert_eq!(parse_literal("2f64").value, NumberValue::Float(2.0));
+
+  // The `f` is a digit of the hexadecimal number
+  assert_eq!(
+    parse_literal("0xf32").value,
+    NumberValue::Int(0xf32, Radix::Hexadecimal)
+  );
+  assert_eq!(parse_literal("0xffi16").suffix, Some(NumberType::I16));
+}
+
+#[test]
+fn test_number_suffix_invalid() {
+  parse_str_fail("const foo = 10u7");
+  parse_str_fail("const foo = 10if");
+  parse_str_fail("const foo = 1.5i32");
+  parse_str_fail("const foo = 0x");
+  parse_str_fail("const foo = 0xu8");
+}
+
+#[test]
+fn test_number_big() {
+  assert_eq!(
+    parse_literal("0xffffffffffffffffu64").value,
+    NumberValue::Big(String::from("ffffffffffffffff"), Radix::Hexadecimal)
+  );
+  assert_eq!(
+    parse_literal("9223372036854775808").value,
+    NumberValue::Big(String::from("9223372036854775808"), Radix::Decimal)
+  );
+}
+
+#[test]
+fn test_number_overflow() {
+  parse_str_fail("const foo = 256u8");
+  parse_str_fail("const foo = 128i8");
+  parse_str_fail("const foo = 0x1ffffffffffffffffu64");
+  parse_str_fail("const foo = 1e39f32");
+  parse_str_fail("const foo = 1e999");
+  parse_str_fail("const foo = 999999999999999999999999999999999999999999");
+  parse_literal("255u8");
+  parse_literal("3e38f32");
+}
+
+#[test]
+fn test_number_negative_minimum() {
+  let negated = |value: &str| match parse_value(value) {
+    Action::UnaryOp(ActionUnaryOp {
+      operator: UnaryOperator::Negate,
+      action,
+    }) => match *action {
+      Action::StaticNumber(literal) => literal.value,
+      action => panic!("expected a number, got {:?}", action),
+    },
+    action => panic!("expected a negation, got {:?}", action),
+  };
+  assert_eq!(negated("-128i8"), NumberValue::Int(128, Radix::Decimal));
+  assert_eq!(
+    negated("-0x8000i16"),
+    NumberValue::Int(0x8000, Radix::Hexadecimal)
+  );
+  assert_eq!(
+    negated("-9223372036854775808i64"),
+    NumberValue::Big(String::from("9223372036854775808"), Radix::Decimal)
+  );
+  parse_str_fail("const foo = -129i8");
+  parse_str_fail("const foo = -256u8");
+  // Only the minus directly in front of the number negates it
+  parse_str_fail("const foo = a - 128i8");
+  parse_str_fail("const foo = - 128i8");
+
+  match parse_value("-128i8 + 1") {
+    Action::BinaryOp(meta) => assert!(matches!(*meta.left, Action::UnaryOp(_))),
+    action => panic!("expected a binary operator, got {:?}", action),
+  }
+}
+
+#[test]
+fn test_number_digit_separators() {
+  assert_eq!(
+    parse_literal("1_000_000").value,
+    NumberValue::Int(1000000, Radix::Decimal)
+  );
+  assert_eq!(parse_literal("1_000_000").raw, "1_000_000");
+  assert_eq!(
+    parse_literal("0xff_ffu16").value,
+    NumberValue::Int(0xffff, Radix::Hexadecimal)
+  );
+  assert_eq!(parse_float("1_000.000_1"), 1000.0001);
+}
+
+#[test]
+fn test_number_digit_separators_invalid() {
+  parse_str_fail("const foo = 1_");
+  parse_str_fail("const foo = 1__0");
+  parse_str_fail("const foo = 1_.5");
+  parse_str_fail("const foo = 1._5");
+  parse_str_fail("const foo = 0x_ff");
+  parse_str_fail("const foo = 1_u8");
+  parse_str_fail("const foo = 1e_5");
+}
@@ -0,0 +1,94 @@
+use super::*;
+
+fn parse_value(value: &str) -> Action {
+  let mut res = parse_str(format!("const foo = {}", value));
+  *res.globals.remove(0).action
+}
+
+fn parse_type(type_: &str) -> Type {
+  let mut res = parse_str(format!("fn test(foo {}) {{}}", type_));
+  res.functions.remove(0).args.remove(0).1
+}
+
+#[test]
+fn test_optional_type() {
+  let res = parse_type("?string");
+  assert_eq!(res.name, "string");
+  assert!(res.optional);
+  assert!(!parse_type("string").optional);
+
+  let res = parse_type("?map[string]?int");
+  assert!(res.optional);
+  let (_, value) = *res.map.unwrap();
+  assert!(value.optional);
+  assert_eq!(value.name, "int");
+
+  assert!(parse_type("?(int, string)").optional);
+}
+
+#[test]
+fn test_optional_type_in_signature_and_variable() {
+  let res = parse_str(
+    r#"
+      fn find(name ?string) ?int {
+        let res: ?int = null
+        let other ?int = res ?? 1
+        return res
+      }
+    "#,
+  );
+  let function = &res.functions[0];
+  assert!(function.args[0].1.optional);
+  assert!(function.return_type.as_ref().unwrap().optional);
+  match &function.body.actions[0] {
+    Action::Variable(var) => {
+      assert!(var.data_type.as_ref().unwrap().optional);
+      match *var.action {
+        Action::StaticNull => {}
+        ref action => panic!("expected null, got {:?}", action),
+      }
+    }
+    action => panic!("expected a variable, got {:?}", action),
+  }
+}
+
+#[test]
+fn test_optional_type_invalid() {
+  parse_str_fail("fn test(foo ??string) {}");
+  parse_str_fail("fn test(foo ?) {}");
+  parse_str_fail("fn test() ? {}");
+  parse_str_fail("const foo: ? = null");
+}
+
+#[test]
+fn test_null_literal() {
+  match parse_value("null") {
+    Action::StaticNull => {}
+    action => panic!("expected null, got {:?}", action),
+  }
+  match parse_value("foo ?? null") {
+    Action::NullCoalesce(meta) => match *meta.right {
+      Action::StaticNull => {}
+      ref action => panic!("expected null, got {:?}", action),
+    },
+    action => panic!("expected a null coalesce, got {:?}", action),
+  }
+}
+
+#[test]
+fn test_null_is_a_keyword() {
+  parse_str_fail(
+    r#"
+      fn test() {
+        null = 1
+      }
+    "#,
+  );
+  parse_str_fail(
+    r#"
+      fn test() {
+        null
+      }
+    "#,
+  );
+}
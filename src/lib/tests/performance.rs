@@ -0,0 +1,81 @@
+use super::*;
+use std::time::{Duration, Instant};
+
+// These tests parse pathological inputs of two sizes and fail if the bigger input takes way longer
+// than its size explains, this guards against accidentally introducing quadratic behavior.
+// Comparing two sizes instead of using a fixed time budget keeps the tests stable on slow machines
+
+/// How many times bigger the big input is than the small input
+const SIZE_RATIO: usize = 8;
+
+/// The fastest of a few parses, this filters out most of the noise of other work on the machine
+fn parse_time(contents: &str) -> Duration {
+  (0..3)
+    .map(|_| {
+      let start = Instant::now();
+      parse_str(contents);
+      start.elapsed()
+    })
+    .min()
+    .unwrap()
+}
+
+/// Parses the input created for `size` and `SIZE_RATIO` times `size`,
+/// linear parsing makes the big input about 8 times slower and quadratic parsing about 64 times
+fn assert_linear(size: usize, input: impl Fn(usize) -> String) {
+  let small = parse_time(&input(size));
+  let big = parse_time(&input(size * SIZE_RATIO));
+  // Never compare against a zero duration, that would make every big input fail
+  let small = small.max(Duration::from_micros(100));
+  assert!(
+    big < small * (SIZE_RATIO as u32 * 3),
+    "parsing {} times more input took {:?} instead of {:?}",
+    SIZE_RATIO,
+    big,
+    small
+  );
+}
+
+#[test]
+fn test_performance_many_arguments() {
+  assert_linear(1_250, |size| {
+    let args = vec!["a"; size].join(", ");
+    format!("fn test() {{\n  foo({})\n}}", args)
+  });
+}
+
+#[test]
+fn test_performance_deep_member_chain() {
+  assert_linear(625, |size| {
+    let chain = vec!["foo"; size].join(".");
+    format!("fn test() {{\n  {}()\n}}", chain)
+  });
+}
+
+#[test]
+fn test_performance_big_string() {
+  assert_linear(125_000, |size| {
+    format!("const foo = \"{}\"", "a".repeat(size))
+  });
+}
+
+#[test]
+fn test_performance_many_functions() {
+  assert_linear(625, |size| {
+    "fn foo(a int, b int) {\n  foo(a, b)\n}\n".repeat(size)
+  });
+}
+
+#[test]
+fn test_performance_carriage_returns() {
+  assert_linear(12_500, |size| "fn foo() {}\r\n".repeat(size));
+}
+
+#[test]
+fn test_performance_many_statements() {
+  // Every statement goes through the keyword matcher
+  assert_linear(3_125, |size| {
+    let statements = "  let a = 1\n  if a {}\n  foo(a)\n  return a\n".repeat(size);
+    format!("fn test() {{\n{}}}", statements)
+  });
+}
@@ -0,0 +1,91 @@
+use super::*;
+
+struct RenameMain;
+
+impl ParserPlugin for RenameMain {
+  fn post_parse(&self, p: &mut Parser) -> Result<(), Box<ParsingError>> {
+    for function in p.functions.iter_mut() {
+      if function.name == Some(String::from("main")) {
+        function.name = Some(String::from("start"));
+      }
+    }
+    Ok(())
+  }
+}
+
+struct NoGlobals;
+
+impl ParserPlugin for NoGlobals {
+  fn post_parse(&self, p: &mut Parser) -> Result<(), Box<ParsingError>> {
+    if !p.globals.is_empty() {
+      return p.error(ParsingErrorType::Custom("Global variables are not allowed"));
+    }
+    Ok(())
+  }
+}
+
+struct AppendFunction;
+
+impl ParserPlugin for AppendFunction {
+  fn pre_parse(&self, contents: &mut Vec<u8>) {
+    contents.extend_from_slice(b"\nfn appended() {}\n");
+  }
+}
+
+fn parse_with_plugin(
+  code: &str,
+  plugin: impl ParserPlugin + 'static,
+) -> Result<Parser, Box<ParsingError>> {
+  let mut plugins = Plugins::new();
+  plugins.add_parser_plugin(plugin);
+  Parser::parse_with_options(
+    code,
+    ParserOptions {
+      plugins,
+      ..Default::default()
+    },
+  )
+}
+
+#[test]
+fn test_plugin_rewrite() {
+  let res = parse_with_plugin(
+    r#"
+      fn main() {}
+    "#,
+    RenameMain,
+  )
+  .unwrap();
+  assert_eq!(res.functions[0].name, Some(String::from("start")));
+}
+
+#[test]
+fn test_plugin_lint() {
+  let res = parse_with_plugin(
+    r#"
+      const foo = 1
+    "#,
+    NoGlobals,
+  );
+  assert!(res.is_err());
+}
+
+#[test]
+fn test_plugin_pre_parse() {
+  let res = parse_with_plugin("fn main() {}", AppendFunction).unwrap();
+  assert_eq!(res.functions.len(), 2);
+}
+
+#[test]
+fn test_plugin_used_by_every_parse() {
+  let mut plugins = Plugins::new();
+  plugins.add_parser_plugin(RenameMain);
+  let options = ParserOptions {
+    plugins,
+    ..Default::default()
+  };
+  let first = Parser::parse_with_options("fn main() {}", options.clone()).unwrap();
+  let second = Parser::parse_unguarded("fn main() {}", options).unwrap();
+  assert_eq!(first.functions[0].name, Some(String::from("start")));
+  assert_eq!(second.functions[0].name, Some(String::from("start")));
+}
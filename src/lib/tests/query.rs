@@ -0,0 +1,250 @@
+use super::*;
+use crate::query::*;
+
+#[test]
+fn test_query_call_by_name() {
+  let res = parse_str(
+    r#"
+      fn main() {
+        print("a")
+        foo()
+        loop {
+          print(bar())
+        }
+      }
+    "#,
+  );
+  let matches = find(&res, r#"call(name="print")"#).unwrap();
+  assert_eq!(matches.len(), 2);
+}
+
+#[test]
+fn test_query_capture() {
+  let res = parse_str(
+    r#"
+      fn main() {
+        let a = 1
+        a = 2
+        a = "b"
+      }
+    "#,
+  );
+  let matches = find(&res, "assignment(target=$x, value=number)").unwrap();
+  assert_eq!(matches.len(), 1);
+  match matches[0].captures[0] {
+    (ref name, Captured::Name(value)) => assert_eq!((name.as_str(), value), ("x", "a")),
+    _ => panic!("expected a captured name"),
+  }
+}
+
+#[test]
+fn test_query_nested_pattern() {
+  let res = parse_str(
+    r#"
+      fn main() {
+        let foo = bar(baz())
+        return bar()
+      }
+    "#,
+  );
+  assert_eq!(find(&res, "return(value=call)").unwrap().len(), 1);
+  assert_eq!(
+    find(&res, r#"variable(value=call(name="bar"))"#)
+      .unwrap()
+      .len(),
+    1
+  );
+}
+
+#[test]
+fn test_query_invalid_patterns() {
+  assert!(parse_pattern("").is_err());
+  assert!(parse_pattern("unknown").is_err());
+  assert!(parse_pattern("call(name=").is_err());
+  assert!(parse_pattern("call(name \"foo\")").is_err());
+  assert!(parse_pattern("call() call()").is_err());
+}
+
+#[test]
+fn test_query_boolean() {
+  let res = parse_str(
+    r#"
+      fn main() {
+        let a = true
+        let b = false
+        while true {}
+      }
+    "#,
+  );
+  assert_eq!(find(&res, "boolean").unwrap().len(), 3);
+  assert_eq!(find(&res, r#"while(condition="true")"#).unwrap().len(), 1);
+}
+
+#[test]
+fn test_query_defer() {
+  let res = parse_str(
+    r#"
+      fn main() {
+        defer close()
+        close()
+        do {} while false
+      }
+    "#,
+  );
+  assert_eq!(
+    find(&res, r#"defer(value=call(name="close"))"#)
+      .unwrap()
+      .len(),
+    1
+  );
+  assert_eq!(find(&res, "do_while").unwrap().len(), 1);
+}
+
+#[test]
+fn test_query_spans() {
+  let code = r#"
+    const foo = compute(1)
+    fn main() {
+      print("a")
+      let b = 1 + bar()
+      loop {
+        baz()
+      }
+    }
+  "#;
+  let res = parse_str(code);
+  let spans: Vec<&str> = find(&res, "call")
+    .unwrap()
+    .iter()
+    .map(|found| &code[found.span.start..found.span.end])
+    .collect();
+  // bar() has no span of its own so it gets the span of the value of b
+  assert_eq!(
+    spans,
+    vec!["compute(1)", r#"print("a")"#, "1 + bar()", "baz()"]
+  );
+}
+
+#[test]
+fn test_query_expression_kinds() {
+  let res = parse_str(
+    r#"
+      fn main() {
+        let a = b + c * 2
+        let d = e == f && !g
+        let h = Foo{bar: 1}
+        let i = h.bar
+        h.bar = h.compute(1)
+        let j = items[0] as u8
+        let k = 0..10
+        let l = |x| x
+        let m = match a {
+          1 => 2,
+          _ => 3,
+        }
+      }
+    "#,
+  );
+  let count = |pattern: &str| find(&res, pattern).unwrap().len();
+  assert_eq!(count("binary_op"), 2);
+  assert_eq!(
+    count(r#"binary_op(operator="*", left=var_ref(name="c"))"#),
+    1
+  );
+  assert_eq!(count(r#"comparison(operator="==")"#), 1);
+  assert_eq!(count("logical(left=comparison, right=unary_op)"), 1);
+  assert_eq!(count(r#"struct_init(name="Foo")"#), 1);
+  assert_eq!(count(r#"field_access(name="bar", target=var_ref)"#), 1);
+  assert_eq!(
+    count(r#"field_assignment(value=method_call(name="compute"))"#),
+    1
+  );
+  assert_eq!(
+    count(r#"cast(type="u8", value=index(target=var_ref(name="items")))"#),
+    1
+  );
+  assert_eq!(count("range(start=number, end=number)"), 1);
+  assert_eq!(count("closure"), 1);
+  assert_eq!(count("match(subject=var_ref)"), 1);
+}
+
+#[test]
+fn test_query_null() {
+  let res = parse_str(
+    r#"
+      fn main() {
+        let a ?string = null
+        foo(null, b)
+      }
+    "#,
+  );
+  assert_eq!(find(&res, "null").unwrap().len(), 2);
+  assert_eq!(find(&res, "variable(value=null)").unwrap().len(), 1);
+}
+
+#[test]
+fn test_query_every_action_has_a_kind() {
+  let res = parse_str(
+    r#"
+      fn main() {
+        let a, b = foo()
+        a = [1, 2.5]
+        let c = {"d": true, "e": null}
+        let f = (a, "g").0 as u8
+        _ = c?.d! ?? h.i()
+        j.k = -a + 1 == 2 && false
+        for item in 0..10 {
+          continue
+        }
+        for let l = 0; l < 10; l += 1 {}
+        while true {
+          break
+        }
+        do {} while false
+        try {} catch err {}
+        let m = loop {
+          break 1
+        }
+        defer if a {} else {}
+        let n = Foo{o: |x| x}
+        fn nested() {}
+        return match items[0] {
+          _ => 1,
+        }
+      }
+    "#,
+  );
+  let mut kinds = vec![];
+  res.functions[0].body.visit(&mut |action| {
+    let kind = kind(action);
+    if !kinds.contains(&kind) {
+      kinds.push(kind);
+    }
+  });
+  for kind in KINDS {
+    assert!(kinds.contains(kind), "no action found for {}", kind);
+    assert!(!find(&res, kind).unwrap().is_empty());
+  }
+  assert_eq!(kinds.len(), KINDS.len());
+}
+
+#[test]
+fn test_query_operator_kinds() {
+  let res = parse_str(
+    r#"
+      fn main() {
+        let a = b ?? c
+        _ = d!
+        let e, f = (1, 2)
+        fn g() {}
+        let h = e.0
+      }
+    "#,
+  );
+  let count = |pattern: &str| find(&res, pattern).unwrap().len();
+  assert_eq!(count("null_coalesce(left=var_ref, right=var_ref)"), 1);
+  assert_eq!(count("discard(value=null_assert(value=var_ref))"), 1);
+  assert_eq!(count("destructure(value=tuple)"), 1);
+  assert_eq!(count(r#"function_decl(name="g")"#), 1);
+  assert_eq!(count("tuple_index(target=var_ref)"), 1);
+}
@@ -0,0 +1,241 @@
+use super::*;
+use crate::reachability::*;
+use program::{parse_program, Program};
+
+fn function_names(functions: &[&Function]) -> Vec<String> {
+  functions
+    .iter()
+    .map(|function| function.name.clone().unwrap())
+    .collect()
+}
+
+fn parse_files(files: &[(&str, &str)]) -> Program {
+  let load = |path: &str| {
+    files
+      .iter()
+      .find(|(file, _)| *file == path)
+      .map(|(_, contents)| contents.as_bytes().to_vec())
+  };
+  parse_program("main.tp", ParserOptions::default(), load).unwrap()
+}
+
+#[test]
+fn test_reachability_removes_unused() {
+  let mut res = parse_str(
+    r#"
+      fn unused() {
+        used()
+      }
+      fn used() {}
+      fn nested() {}
+      fn main() {
+        loop {
+          used()
+          for item in items {
+            print(nested())
+          }
+        }
+      }
+    "#,
+  );
+  let removed = remove_unreachable(&mut res, "main");
+  assert_eq!(removed.functions.len(), 1);
+  assert_eq!(removed.functions[0].name, Some(String::from("unused")));
+  assert_eq!(res.functions.len(), 3);
+}
+
+#[test]
+fn test_reachability_globals() {
+  let mut res = parse_str(
+    r#"
+      const foo = bar()
+      fn bar() {}
+      fn main() {}
+    "#,
+  );
+  let removed = remove_unreachable(&mut res, "main");
+  assert_eq!(removed.functions.len(), 0);
+}
+
+#[test]
+fn test_reachability_recursion() {
+  let res = parse_str(
+    r#"
+      fn main() {
+        main()
+      }
+    "#,
+  );
+  assert!(unreachable(&res, "main").is_empty());
+}
+
+#[test]
+fn test_reachability_entry() {
+  let res = parse_str(
+    r#"
+      fn main() {
+        helper()
+      }
+      fn start() {}
+      fn helper() {}
+    "#,
+  );
+  assert_eq!(
+    function_names(&unreachable(&res, "start").functions),
+    vec!["main", "helper"]
+  );
+  assert_eq!(
+    function_names(&unreachable(&res, "main").functions),
+    vec!["start"]
+  );
+}
+
+#[test]
+fn test_reachability_methods() {
+  let mut res = parse_str(
+    r#"
+      struct Foo {
+        bar: int
+      }
+      impl Foo {
+        fn compute() {
+          helper()
+        }
+        fn unused_method() {
+          unused()
+        }
+      }
+      fn helper() {}
+      fn unused() {}
+      fn main() {
+        let foo = Foo{bar: 1}
+        foo.compute()
+      }
+    "#,
+  );
+  let unreachable = unreachable(&res, "main");
+  assert_eq!(function_names(&unreachable.functions), vec!["unused"]);
+  assert_eq!(unreachable.methods.len(), 1);
+  assert_eq!(unreachable.methods[0].0.struct_name, "Foo");
+  assert_eq!(
+    unreachable.methods[0].1.name,
+    Some(String::from("unused_method"))
+  );
+  assert_eq!(
+    res.span_text(unreachable.methods[0].1.span),
+    "fn unused_method() {\n          unused()\n        }"
+  );
+
+  let removed = remove_unreachable(&mut res, "main");
+  assert_eq!(removed.functions.len(), 1);
+  assert_eq!(removed.methods.len(), 1);
+  assert_eq!(res.impls[0].methods.len(), 1);
+}
+
+#[test]
+fn test_reachability_structs() {
+  let mut res = parse_str(
+    r#"
+      struct Created {}
+      struct Argument {
+        field: Field
+      }
+      struct Field {}
+      struct Returned {}
+      struct Declared {}
+      struct Casted {}
+      struct Unused {
+        field: Field
+      }
+      impl Unused {
+        fn method() {}
+      }
+      fn helper(a []Argument) ?Returned {}
+      fn main() {
+        let a = Created{}
+        let b: map[string]Declared = {}
+        let c = b as (Casted, int)
+        helper(a)
+        a.method()
+      }
+    "#,
+  );
+  let unreachable = unreachable(&res, "main");
+  let names: Vec<&str> = unreachable
+    .structs
+    .iter()
+    .map(|struct_| struct_.name.as_str())
+    .collect();
+  assert_eq!(names, vec!["Unused"]);
+  assert_eq!(unreachable.methods.len(), 1);
+
+  let removed = remove_unreachable(&mut res, "main");
+  assert_eq!(removed.structs.len(), 1);
+  assert_eq!(removed.methods.len(), 1);
+  assert_eq!(res.structs.len(), 6);
+  assert_eq!(res.impls.len(), 0);
+}
+
+#[test]
+fn test_reachability_closures_and_values() {
+  let res = parse_str_with_pipes(
+    r#"
+      fn in_closure() {}
+      fn in_nested() {}
+      fn as_value() {}
+      fn piped(a int) {}
+      fn unused() {}
+      fn main() {
+        let f = fn() {
+          in_closure()
+        }
+        fn nested() {
+          in_nested()
+        }
+        map(items, as_value)
+        let piped_value = 1 |> piped()
+      }
+    "#,
+  );
+  let unreachable = unreachable(&res, "main");
+  assert_eq!(function_names(&unreachable.functions), vec!["unused"]);
+}
+
+#[test]
+fn test_reachability_program() {
+  let mut program = parse_files(&[
+    (
+      "main.tp",
+      "import \"utils\"\nfn main() {\n  let a = Point{}\n  used()\n}\nfn unused() {}",
+    ),
+    (
+      "utils.tp",
+      "pub struct Point {}\npub struct Line {}\npub fn used() {\n  private()\n}\npub fn unused() {}\nfn private() {}",
+    ),
+  ]);
+  let unreachable = unreachable_in_program(&program, "main");
+  assert_eq!(unreachable.len(), 2);
+  let (path, main) = &unreachable[0];
+  assert_eq!(*path, "main.tp");
+  assert_eq!(function_names(&main.functions), vec!["unused"]);
+  let (path, utils) = &unreachable[1];
+  assert_eq!(*path, "utils.tp");
+  assert_eq!(function_names(&utils.functions), vec!["unused"]);
+  assert_eq!(utils.structs.len(), 1);
+  assert_eq!(utils.structs[0].name, "Line");
+
+  let removed = remove_unreachable_from_program(&mut program, "main");
+  assert_eq!(removed[1].1.functions.len(), 1);
+  assert_eq!(program.files[1].1.functions.len(), 2);
+}
+
+#[test]
+fn test_reachability_program_visibility() {
+  let program = parse_files(&[
+    ("main.tp", "import \"utils\"\nfn main() {\n  private()\n}"),
+    ("utils.tp", "fn private() {}"),
+  ]);
+  // A private function can't be called from another file
+  let unreachable = unreachable_in_program(&program, "main");
+  assert_eq!(function_names(&unreachable[1].1.functions), vec!["private"]);
+}
@@ -0,0 +1,27 @@
+use crate::reduce::{failure, reduce};
+
+#[test]
+fn test_reduce_keeps_error() {
+  let input = r#"
+    fn foo() {}
+    const a = 1
+    fn bar(a int, b int) {
+      foo(a, b)
+    }
+    fn baz() {
+      let c = 1
+      foo(a, b
+    }
+  "#;
+  let reduced = reduce(input.as_bytes()).unwrap();
+  assert!(reduced.len() < input.len());
+  assert_eq!(failure(&reduced), failure(input.as_bytes()));
+}
+
+#[test]
+fn test_reduce_valid_input() {
+  let input = r#"
+    fn foo() {}
+  "#;
+  assert!(reduce(input.as_bytes()).is_none());
+}
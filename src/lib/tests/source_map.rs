@@ -0,0 +1,91 @@
+use super::*;
+
+const FIXTURES: &[&str] = &[
+  "",
+  "fn main() {}",
+  "const a = \"héllo\"\nconst b = \"wörld\"",
+  "// 日本語のコメント\nfn test() {\n  print(\"ok\")\n}\n",
+  "const emoji = \"😀 and 🎉\"\n\nconst c = 1",
+  "\n\n\"ü😀日\"\n",
+];
+
+fn pos(line: usize, character: usize) -> Utf16Position {
+  Utf16Position { line, character }
+}
+
+#[test]
+fn test_source_map_ascii() {
+  let map = SourceMap::new("fn a() {}\nfn b() {}");
+  assert_eq!(map.line_count(), 2);
+  assert_eq!(map.byte_to_utf16(0), pos(0, 0));
+  assert_eq!(map.byte_to_utf16(13), pos(1, 3));
+  assert_eq!(map.utf16_to_byte(pos(1, 3)), Some(13));
+  assert_eq!(map.byte_to_char(13), 13);
+  assert_eq!(map.char_to_byte(13), Some(13));
+}
+
+#[test]
+fn test_source_map_multibyte() {
+  // é is 2 bytes and 1 UTF-16 unit, 😀 is 4 bytes and 2 UTF-16 units
+  let map = SourceMap::new("é😀a\nb");
+  assert_eq!(map.byte_to_utf16(2), pos(0, 1));
+  assert_eq!(map.byte_to_utf16(6), pos(0, 3));
+  assert_eq!(map.byte_to_utf16(8), pos(1, 0));
+  assert_eq!(map.byte_to_char(6), 2);
+  assert_eq!(map.char_to_byte(2), Some(6));
+
+  // Offsets inside of a char are moved back to the start of the char
+  assert_eq!(map.byte_to_utf16(4), pos(0, 1));
+  assert_eq!(map.byte_to_char(4), 1);
+
+  // Inside of a surrogate pair, past the end of the line and a line that doesn't exist
+  assert_eq!(map.utf16_to_byte(pos(0, 2)), None);
+  assert_eq!(map.utf16_to_byte(pos(0, 5)), None);
+  assert_eq!(map.utf16_to_byte(pos(2, 0)), None);
+  assert_eq!(map.char_to_byte(9), None);
+}
+
+#[test]
+fn test_source_map_invalid_utf8() {
+  // The first 2 bytes of 日 without the last byte
+  let map = SourceMap::new(vec![b'a', 0xe6, 0x97, b'b']);
+  assert_eq!(map.byte_to_char(4), 3);
+  assert_eq!(map.byte_to_utf16(3), pos(0, 2));
+  assert_eq!(map.utf16_to_byte(pos(0, 2)), Some(3));
+}
+
+#[test]
+fn test_source_map_properties() {
+  for fixture in FIXTURES {
+    let map = SourceMap::new(*fixture);
+    for (char_offset, (offset, _)) in fixture
+      .char_indices()
+      .chain(Some((fixture.len(), ' ')))
+      .enumerate()
+    {
+      // Every char boundary survives a round trip
+      let position = map.byte_to_utf16(offset);
+      assert_eq!(map.utf16_to_byte(position), Some(offset), "{:?}", fixture);
+      assert_eq!(map.byte_to_char(offset), char_offset);
+      assert_eq!(map.char_to_byte(char_offset), Some(offset));
+
+      // The position matches what std computes
+      let before = &fixture[..offset];
+      let line_start = before.rfind('\n').map(|i| i + 1).unwrap_or(0);
+      assert_eq!(position.line, before.matches('\n').count());
+      assert_eq!(
+        position.character,
+        before[line_start..].encode_utf16().count()
+      );
+    }
+  }
+}
+
+#[test]
+fn test_source_map_parser_spans() {
+  let res = parse_str("const a = \"日本\"\nconst b = 1");
+  let map = res.source_map();
+  let (start, end) = map.span_to_utf16(res.globals[1].name_span);
+  assert_eq!(start, pos(1, 6));
+  assert_eq!(end, pos(1, 7));
+}
@@ -0,0 +1,55 @@
+use super::*;
+use std::fs;
+
+struct ExpectedError {
+  message: String,
+  y: usize,
+  x: usize,
+}
+
+// Parses the `// expect-error: <message> at <line>:<column>` directive
+fn expected_error(contents: &str) -> Option<ExpectedError> {
+  for line in contents.lines() {
+    let directive = match line.trim().strip_prefix("// expect-error:") {
+      Some(directive) => directive.trim(),
+      None => continue,
+    };
+    let at = directive
+      .rfind(" at ")
+      .expect("expect-error without location");
+    let mut location = directive[at + 4..].split(':');
+    let mut number = || location.next().unwrap().trim().parse::<usize>().unwrap();
+    return Some(ExpectedError {
+      message: directive[..at].to_string(),
+      y: number(),
+      x: number(),
+    });
+  }
+  None
+}
+
+// Run all spec files inside of the spec directory
+// The `// expect-output:` directive is reserved for when there is a interpreter
+#[test]
+fn test_spec() {
+  let dir = concat!(env!("CARGO_MANIFEST_DIR"), "/spec");
+  for entry in fs::read_dir(dir).unwrap() {
+    let path = entry.unwrap().path();
+    let contents = fs::read_to_string(&path).unwrap();
+    let res = Parser::parse(contents.as_bytes());
+
+    match (expected_error(&contents), res) {
+      (None, Err(err)) => panic!("{}:\n{}", path.display(), err),
+      (Some(_), Ok(_)) => panic!("{}: expected an error", path.display()),
+      (Some(expected), Err(err)) => {
+        assert_eq!(
+          (expected.message, expected.y, expected.x),
+          (err.error_type.to_string(), err.location.y, err.location.x),
+          "{}",
+          path.display()
+        );
+      }
+      (None, Ok(_)) => {}
+    }
+  }
+}
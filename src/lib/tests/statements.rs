@@ -0,0 +1,138 @@
+use super::*;
+
+#[test]
+fn test_statement_function_call() {
+  parse_str(
+    r#"
+      fn test() {
+        foo()
+      }
+    "#,
+  );
+}
+
+#[test]
+fn test_statement_discard() {
+  let res = parse_str(
+    r#"
+      fn test(a int) {
+        _ = a
+        _ = foo()
+        _ = a + 1
+      }
+    "#,
+  );
+  for action in &res.functions[0].body.actions {
+    match action {
+      Action::Discard(_) => {}
+      action => panic!("expected a discard, got {:?}", action),
+    }
+  }
+}
+
+#[test]
+fn test_statement_unused_var_ref() {
+  parse_str_fail(
+    r#"
+      fn test(a int) {
+        a
+      }
+    "#,
+  );
+}
+
+#[test]
+fn test_statement_unused_string() {
+  parse_str_fail(
+    r#"
+      fn test() {
+        "foo"
+      }
+    "#,
+  );
+}
+
+#[test]
+fn test_statement_unused_number() {
+  parse_str_fail(
+    r#"
+      fn test() {
+        1
+      }
+    "#,
+  );
+}
+
+#[test]
+fn test_statement_defer() {
+  let res = parse_str(
+    r#"
+      fn test(file File) {
+        defer file.close()
+        defer print("done")
+        defer if a {
+          cleanup()
+        }
+        return
+      }
+    "#,
+  );
+  let body = &res.functions[0].body.actions;
+  assert_eq!(body.len(), 4);
+  match &body[1] {
+    Action::Defer(action) => assert!(matches!(**action, Action::FunctionCall(_))),
+    action => panic!("expected a defer, got {:?}", action),
+  }
+  assert!(matches!(body[2], Action::Defer(_)));
+}
+
+#[test]
+fn test_statement_defer_invalid() {
+  parse_str_fail("fn test() { defer }");
+  parse_str_fail("fn test() { defer return }");
+  parse_str_fail("fn test() { loop { defer break } }");
+}
+
+#[test]
+fn test_statement_try_catch() {
+  let res = parse_str(
+    r#"
+      fn test() {
+        try {
+          let file = open("a.txt")
+          read(file)
+        } catch err {
+          print(err)
+        }
+        try { write() }
+        catch {}
+        try {} catch{}
+      }
+    "#,
+  );
+  let body = &res.functions[0].body.actions;
+  assert_eq!(body.len(), 3);
+  match &body[0] {
+    Action::Try(meta) => {
+      assert_eq!(meta.body.actions.len(), 2);
+      assert_eq!(meta.error_name.as_deref(), Some("err"));
+      assert_eq!(meta.catch_body.actions.len(), 1);
+    }
+    action => panic!("expected a try, got {:?}", action),
+  }
+  match &body[1] {
+    Action::Try(meta) => assert_eq!(meta.error_name, None),
+    action => panic!("expected a try, got {:?}", action),
+  }
+}
+
+#[test]
+fn test_statement_try_catch_invalid() {
+  parse_str_fail("fn test() { try {} }");
+  parse_str_fail("fn test() { try }");
+  parse_str_fail("fn test() { try foo() catch {} }");
+  parse_str_fail("fn test() { try {} catch }");
+  parse_str_fail("fn test() { try {} catch a b {} }");
+  parse_str_fail("fn test() { catch {} }");
+  parse_str_fail("fn test() { let a = try {} catch {} }");
+}
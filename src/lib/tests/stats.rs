@@ -0,0 +1,61 @@
+use super::*;
+use crate::stats::stats;
+
+#[test]
+fn test_stats_lines_and_functions() {
+  let res = parse_str("fn a() {}\nfn b() {}\n\nfn c() {}\n");
+  let stats = stats(&res);
+  assert_eq!(stats.lines, 4);
+  assert_eq!(stats.functions.len(), 3);
+}
+
+#[test]
+fn test_stats_simple_function() {
+  let res = parse_str(
+    r#"
+      fn test() {
+        print("a")
+        print("b")
+      }
+    "#,
+  );
+  let function = &stats(&res).functions[0];
+  assert_eq!(function.name, Some(String::from("test")));
+  assert_eq!(function.statements, 2);
+  assert_eq!(function.complexity, 1);
+  assert_eq!(function.max_nesting, 0);
+}
+
+#[test]
+fn test_stats_complexity_and_nesting() {
+  let res = parse_str(
+    r#"
+      fn short() {}
+      fn test(items []int) {
+        for item in items {
+          if item > 1 && item < 10 {
+            print(item)
+          } else if item == 0 {
+            while true {
+              break
+            }
+          } else {
+            print("else")
+          }
+        }
+      }
+    "#,
+  );
+  let stats = stats(&res);
+  let function = &stats.functions[1];
+  // for, if, else if, &&, while
+  assert_eq!(function.complexity, 6);
+  // for > if > while
+  assert_eq!(function.max_nesting, 3);
+  // for, if, print, while, break, print
+  assert_eq!(function.statements, 6);
+  assert_eq!(
+    stats.longest_function().unwrap().name,
+    Some(String::from("test"))
+  );
+}
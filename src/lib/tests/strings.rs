@@ -0,0 +1,126 @@
+use super::*;
+
+fn parse_value(value: &str) -> String_ {
+  let mut res = parse_str(format!("const foo = {}", value));
+  match *res.globals.remove(0).action {
+    Action::StaticString(meta) => meta,
+    action => panic!("expected a string, got {:?}", action),
+  }
+}
+
+#[test]
+fn test_string() {
+  let res = parse_value(r#""foo""#);
+  assert_eq!(res.content, "foo");
+  assert!(!res.raw);
+}
+
+#[test]
+fn test_raw_string() {
+  let res = parse_value(r#"`C:\path\to "file"`"#);
+  assert_eq!(res.content, r#"C:\path\to "file""#);
+  assert!(res.raw);
+  assert_eq!(parse_value("``").content, "");
+}
+
+#[test]
+fn test_raw_string_multi_line() {
+  let res = parse_value("`foo\n  // not a comment\nbar`");
+  assert_eq!(res.content, "foo\n  // not a comment\nbar");
+}
+
+#[test]
+fn test_raw_string_in_function() {
+  parse_str(
+    r#"
+      fn test() {
+        print(`a`, "b")
+        let c = `
+          multi
+          line
+        `
+      }
+    "#,
+  );
+}
+
+#[test]
+fn test_raw_string_invalid() {
+  parse_str_fail("const foo = `foo");
+  parse_str_fail("const foo = foo`bar`");
+}
+
+#[test]
+fn test_string_escapes() {
+  assert_eq!(parse_value(r#""a\"b""#).content, "a\"b");
+  assert_eq!(parse_value(r#""a\\b""#).content, "a\\b");
+  assert_eq!(parse_value(r#""a\nb\tc\rd\0""#).content, "a\nb\tc\rd\0");
+  assert_eq!(parse_value(r#""\x41\x7f""#).content, "A\x7f");
+  assert_eq!(parse_value(r#""\u{41}\u{e9}\u{1F600}""#).content, "Aé😀");
+  assert_eq!(parse_value(r#""\u{10FFFF}""#).content, "\u{10FFFF}");
+}
+
+#[test]
+fn test_raw_string_has_no_escapes() {
+  assert_eq!(parse_value(r#"`a\nb`"#).content, r#"a\nb"#);
+}
+
+#[test]
+fn test_string_invalid_escapes() {
+  let invalid = [
+    r#""\q""#,
+    r#""\x4""#,
+    r#""\xzz""#,
+    r#""\x80""#,
+    r#""\u41""#,
+    r#""\u{}""#,
+    r#""\u{zz}""#,
+    r#""\u{1234567}""#,
+    r#""\u{D800}""#,
+    r#""\u{110000}""#,
+  ];
+  for value in invalid.iter() {
+    match Parser::parse(format!("const foo = {}", value)) {
+      Err(err) => assert_eq!(err.error_type, ParsingErrorType::InvalidEscape, "{}", value),
+      Ok(_) => panic!("expected {} to fail", value),
+    }
+  }
+  parse_str_fail(r#"const foo = "\"#);
+  parse_str_fail(r#"const foo = "\u{41"#);
+}
+
+#[test]
+fn test_string_invalid_utf8() {
+  let mut contents = b"const foo = \"".to_vec();
+  contents.push(0xff);
+  contents.push(b'"');
+  let err = Parser::parse(contents).unwrap_err();
+  assert_eq!(
+    err.error_type,
+    ParsingErrorType::Custom("Invalid utf8 string")
+  );
+}
+
+#[test]
+fn test_string_source() {
+  let res = parse_value(r#""a\tb\u{41}""#);
+  assert_eq!(res.content, "a\tbA");
+  assert_eq!(res.source, r#""a\tb\u{41}""#);
+  assert_eq!(res.span, Span::new(12, 24));
+
+  let res = parse_value(r#"`a\tb`"#);
+  assert_eq!(res.content, r#"a\tb"#);
+  assert_eq!(res.source, r#"`a\tb`"#);
+}
+
+#[test]
+fn test_string_quoted() {
+  let res = parse_value(r#"`C:\path "file"`"#);
+  assert_eq!(res.quoted(), r#""C:\\path \"file\"""#);
+  // The quoted string is valid code with the same content
+  assert_eq!(parse_value(&res.quoted()).content, res.content);
+
+  let res = parse_value(r#""a\n\x01""#);
+  assert_eq!(res.quoted(), r#""a\n\u{1}""#);
+  assert_eq!(res.nul_terminated(), b"a\n\x01\0");
+}
@@ -0,0 +1,208 @@
+use super::*;
+
+#[test]
+fn test_struct_empty() {
+  let res = parse_str(
+    r#"
+      struct Foo {}
+    "#,
+  );
+  assert_eq!(res.structs[0].name, "Foo");
+  assert_eq!(res.structs[0].fields.len(), 0);
+}
+
+#[test]
+fn test_struct_single_field() {
+  let res = parse_str(
+    r#"
+      struct Foo { bar: string }
+    "#,
+  );
+  let fields = &res.structs[0].fields;
+  assert_eq!(fields.len(), 1);
+  assert_eq!(fields[0].0, "bar");
+  assert_eq!(fields[0].1.name, "string");
+}
+
+#[test]
+fn test_struct_multiple_fields() {
+  let res = parse_str(
+    r#"
+      struct Foo {
+        bar: string
+        baz: int,
+        items: []string
+      }
+    "#,
+  );
+  let names: Vec<&str> = res.structs[0]
+    .fields
+    .iter()
+    .map(|(name, type_)| {
+      assert!(!type_.name.is_empty());
+      name.as_str()
+    })
+    .collect();
+  assert_eq!(names, vec!["bar", "baz", "items"]);
+}
+
+#[test]
+fn test_struct_trailing_comma() {
+  let res = parse_str(
+    r#"
+      struct Foo { bar: string, baz: int, }
+    "#,
+  );
+  assert_eq!(res.structs[0].fields.len(), 2);
+}
+
+#[test]
+fn test_structs_with_functions() {
+  let res = parse_str(
+    r#"
+      struct Foo {
+        bar: string,
+      }
+      fn test(foo Foo) {}
+      struct Bar {}
+    "#,
+  );
+  assert_eq!(res.structs.len(), 2);
+  assert_eq!(res.functions.len(), 1);
+}
+
+#[test]
+fn test_struct_no_name() {
+  parse_str_fail(
+    r#"
+      struct {}
+    "#,
+  );
+}
+
+#[test]
+fn test_struct_field_without_type() {
+  parse_str_fail(
+    r#"
+      struct Foo { bar }
+    "#,
+  );
+}
+
+#[test]
+fn test_struct_duplicate_fields() {
+  parse_str_fail(
+    r#"
+      struct Foo { bar: string, bar: int }
+    "#,
+  );
+}
+
+#[test]
+fn test_struct_double_comma() {
+  parse_str_fail(
+    r#"
+      struct Foo { bar: string,, baz: int }
+    "#,
+  );
+}
+
+#[test]
+fn test_struct_unclosed() {
+  parse_str_fail(
+    r#"
+      struct Foo { bar: string
+    "#,
+  );
+}
+
+fn parse_value(value: &str) -> ActionStructInit {
+  let mut res = parse_str(format!("const foo = {}", value));
+  match *res.globals.remove(0).action {
+    Action::StructInit(meta) => meta,
+    action => panic!("expected a inline struct, got {:?}", action),
+  }
+}
+
+#[test]
+fn test_struct_init_empty() {
+  let res = parse_value("Foo{}");
+  assert_eq!(res.name, "Foo");
+  assert_eq!(res.fields.len(), 0);
+}
+
+#[test]
+fn test_struct_init_fields() {
+  let res = parse_value(r#"Foo{bar: "baz", qux: 1 + 2}"#);
+  assert_eq!(res.fields.len(), 2);
+  assert_eq!(res.fields[0].0, "bar");
+  assert_eq!(res.fields[1].0, "qux");
+}
+
+#[test]
+fn test_struct_init_multi_line() {
+  let res = parse_value(
+    r#"Foo {
+      bar: baz,
+      qux: Qux{a: 1},
+    }"#,
+  );
+  assert_eq!(res.fields.len(), 2);
+  match &res.fields[1].1 {
+    Action::StructInit(meta) => assert_eq!(meta.name, "Qux"),
+    action => panic!("expected a inline struct, got {:?}", action),
+  }
+}
+
+#[test]
+fn test_struct_init_as_argument_and_return() {
+  parse_str(
+    r#"
+      fn test() Foo {
+        bar(Foo{a: 1}, Bar{})
+        return Foo{a: 2}
+      }
+    "#,
+  );
+}
+
+#[test]
+fn test_struct_init_not_in_conditions() {
+  let res = parse_str(
+    r#"
+      fn test(items []Foo) {
+        if foo {}
+        while foo {}
+        for item in items {}
+      }
+    "#,
+  );
+  assert_eq!(res.functions[0].body.actions.len(), 3);
+}
+
+#[test]
+fn test_struct_init_missing_value() {
+  parse_str_fail(
+    r#"
+      const foo = Foo{bar}
+    "#,
+  );
+}
+
+#[test]
+fn test_struct_init_duplicate_fields() {
+  parse_str_fail(
+    r#"
+      const foo = Foo{bar: 1, bar: 2}
+    "#,
+  );
+}
+
+#[test]
+fn test_struct_init_unclosed() {
+  parse_str_fail(
+    r#"
+      const foo = Foo{bar: 1
+    "#,
+  );
+}
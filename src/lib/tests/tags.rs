@@ -0,0 +1,58 @@
+use super::*;
+use crate::tags::*;
+
+fn parse_tags(code: &str) -> Vec<Tag> {
+  tags(&parse_str(code), "src/main.tp")
+}
+
+#[test]
+fn test_tags_definitions() {
+  let res = parse_tags(
+    r#"const max = 10
+struct User {
+  name: string
+}
+enum Color {
+  Red,
+}
+
+pub fn main() {
+  let local = 1
+}"#,
+  );
+  let found: Vec<(&str, TagKind, usize)> = res
+    .iter()
+    .map(|tag| (tag.name.as_str(), tag.kind, tag.line))
+    .collect();
+  assert_eq!(
+    found,
+    vec![
+      ("max", TagKind::Global, 1),
+      ("User", TagKind::Struct, 2),
+      ("Color", TagKind::Enum, 5),
+      ("main", TagKind::Function, 9),
+    ]
+  );
+  assert_eq!(res[3].line_text, "pub fn main() {");
+}
+
+#[test]
+fn test_tags_ctags() {
+  let res = parse_tags("fn b() {}\nfn a() {}\n");
+  assert_eq!(
+    ctags(&res),
+    "!_TAG_FILE_FORMAT\t2\t/extended format/\n\
+     !_TAG_FILE_SORTED\t1\t/0=unsorted, 1=sorted/\n\
+     a\tsrc/main.tp\t2;\"\tf\n\
+     b\tsrc/main.tp\t1;\"\tf\n"
+  );
+}
+
+#[test]
+fn test_tags_etags() {
+  let res = parse_tags("fn b() {}\nfn a() {}\n");
+  assert_eq!(
+    etags(&res),
+    "\u{c}\nsrc/main.tp,33\nfn b() {}\u{7f}b\u{1}1,0\nfn a() {}\u{7f}a\u{1}2,10\n"
+  );
+}
@@ -0,0 +1,31 @@
+use super::*;
+
+#[test]
+fn test_trace_function() {
+  let res = parse_str(
+    r#"
+      fn test() {}
+    "#,
+  );
+  let consumed_function = res.trace.iter().any(|event| match event {
+    TraceEvent::Consumed { parser, .. } => *parser == "function",
+    _ => false,
+  });
+  assert!(consumed_function);
+}
+
+#[test]
+fn test_trace_backtracking() {
+  let res = parse_str(
+    r#"
+      fn test() {
+        foo()
+      }
+    "#,
+  );
+  let backtracked = res.trace.iter().any(|event| match event {
+    TraceEvent::Backtracked { from, to } => to < from,
+    _ => false,
+  });
+  assert!(backtracked);
+}
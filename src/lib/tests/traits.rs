@@ -0,0 +1,82 @@
+use super::*;
+
+#[test]
+fn test_trait_empty() {
+  let res = parse_str("trait Foo {}");
+  assert_eq!(res.traits.len(), 1);
+  assert_eq!(res.traits[0].name, "Foo");
+  assert_eq!(res.traits[0].methods.len(), 0);
+}
+
+#[test]
+fn test_trait_methods() {
+  let res = parse_str(
+    r#"
+      trait Shape {
+        fn area(self) int
+        fn scale(self, by int)
+        fn name() -> string
+        fn new(size int) Shape
+      }
+    "#,
+  );
+  let methods = &res.traits[0].methods;
+  assert_eq!(methods.len(), 4);
+  assert_eq!(methods[0].name.as_deref(), Some("area"));
+  assert_eq!(methods[0].args[0].0, "self");
+  assert_eq!(methods[0].args[0].1.name, "Shape");
+  assert_eq!(methods[0].return_type.as_ref().unwrap().name, "int");
+  assert_eq!(methods[1].args.len(), 2);
+  assert!(methods[1].return_type.is_none());
+  assert_eq!(methods[2].return_type.as_ref().unwrap().name, "string");
+  assert_eq!(methods[3].args[0].0, "size");
+}
+
+#[test]
+fn test_trait_single_line() {
+  let res = parse_str("trait Foo { fn a(self), fn b(self) int }");
+  assert_eq!(res.traits[0].methods.len(), 2);
+  assert_eq!(
+    res.traits[0].methods[1].return_type.as_ref().unwrap().name,
+    "int"
+  );
+}
+
+#[test]
+fn test_trait_impl() {
+  let res = parse_str(
+    r#"
+      trait Shape {
+        fn area(self) int
+      }
+
+      struct Square {
+        size: int
+      }
+
+      impl Shape for Square {
+        fn area(self) int {
+          return self.size * self.size
+        }
+      }
+
+      impl Square {}
+    "#,
+  );
+  assert_eq!(res.impls[0].struct_name, "Square");
+  assert_eq!(res.impls[0].trait_name.as_deref(), Some("Shape"));
+  assert_eq!(res.impls[0].methods[0].args[0].1.name, "Square");
+  assert_eq!(res.impls[0].methods[0].body.actions.len(), 1);
+  assert_eq!(res.impls[1].trait_name, None);
+}
+
+#[test]
+fn test_trait_invalid() {
+  parse_str_fail("trait {}");
+  parse_str_fail("trait Foo {");
+  parse_str_fail("trait Foo { fn a() {} }");
+  parse_str_fail("trait Foo { const a = 1 }");
+  parse_str_fail("trait Foo { fn a()\n fn a() }");
+  parse_str_fail("impl Shape for {}");
+  parse_str_fail("impl Shape for Square");
+}
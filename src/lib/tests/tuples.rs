@@ -0,0 +1,173 @@
+use super::*;
+
+fn parse_value(value: &str) -> Action {
+  let mut res = parse_str(format!("const foo = {}", value));
+  *res.globals.remove(0).action
+}
+
+fn parse_type(type_: &str) -> Type {
+  let mut res = parse_str(format!("fn test(foo {}) {{}}", type_));
+  res.functions.remove(0).args.remove(0).1
+}
+
+#[test]
+fn test_tuple_init() {
+  match parse_value(r#"(1, "a", foo + 1)"#) {
+    Action::TupleInit(items) => {
+      assert_eq!(items.len(), 3);
+      match items[2] {
+        Action::BinaryOp(_) => {}
+        ref action => panic!("expected a binary operator, got {:?}", action),
+      }
+    }
+    action => panic!("expected a tuple, got {:?}", action),
+  }
+  match parse_value("(\n  (1, 2),\n  3\n)") {
+    Action::TupleInit(items) => match items[0] {
+      Action::TupleInit(ref nested) => assert_eq!(nested.len(), 2),
+      ref action => panic!("expected a nested tuple, got {:?}", action),
+    },
+    action => panic!("expected a tuple, got {:?}", action),
+  }
+}
+
+#[test]
+fn test_tuple_parentheses_are_not_a_tuple() {
+  match parse_value("(a + b)") {
+    Action::BinaryOp(_) => {}
+    action => panic!("expected a binary operator, got {:?}", action),
+  }
+}
+
+#[test]
+fn test_tuple_index() {
+  match parse_value("foo.1") {
+    Action::TupleIndex(meta) => assert_eq!(meta.index, 1),
+    action => panic!("expected a tuple index, got {:?}", action),
+  }
+  match parse_value("foo.bar.0.1") {
+    Action::TupleIndex(meta) => {
+      assert_eq!(meta.index, 1);
+      match *meta.target {
+        Action::TupleIndex(ref inner) => assert_eq!(inner.index, 0),
+        ref action => panic!("expected a tuple index, got {:?}", action),
+      }
+    }
+    action => panic!("expected a tuple index, got {:?}", action),
+  }
+  match parse_value("(1, 2).0") {
+    Action::TupleIndex(meta) => match *meta.target {
+      Action::TupleInit(_) => {}
+      ref action => panic!("expected a tuple, got {:?}", action),
+    },
+    action => panic!("expected a tuple index, got {:?}", action),
+  }
+}
+
+#[test]
+fn test_tuple_type() {
+  let res = parse_type("(int, string)");
+  assert_eq!(res.name, "tuple");
+  let items = res.tuple.unwrap();
+  assert_eq!(items.len(), 2);
+  assert_eq!(items[0].name, "int");
+  assert_eq!(items[1].name, "string");
+
+  let res = parse_type("( map[string]int , (int, int) )");
+  let items = res.tuple.unwrap();
+  assert_eq!(items[0].name, "map");
+  assert_eq!(items[1].tuple.as_ref().unwrap().len(), 2);
+}
+
+#[test]
+fn test_tuple_type_in_function() {
+  let res = parse_str(
+    r#"
+      fn pair(a int) (int, string) {
+        let res: (int, string) = (a, "a")
+        return res
+      }
+    "#,
+  );
+  let return_type = res.functions[0].return_type.as_ref().unwrap();
+  assert_eq!(return_type.tuple.as_ref().unwrap().len(), 2);
+}
+
+#[test]
+fn test_tuple_invalid() {
+  parse_str_fail("const foo = (1,)");
+  parse_str_fail("const foo = (1, 2");
+  parse_str_fail("const foo = foo.1a");
+  parse_str_fail("const foo = foo.0()");
+  parse_str_fail("fn test(foo (int)) {}");
+  parse_str_fail("fn test(foo (int,)) {}");
+  parse_str_fail("fn test(foo (int, string) {}");
+  parse_str_fail(
+    r#"
+      fn test() {
+        (1, 2)
+      }
+    "#,
+  );
+}
+
+#[test]
+fn test_tuple_multiple_return_values() {
+  let res = parse_str(
+    r#"
+      fn split(value string) (string, string) {
+        return value, "b"
+      }
+      fn single() int {
+        return 1
+      }
+    "#,
+  );
+  match &res.functions[0].body.actions[0] {
+    Action::Return(Some(action)) => match &**action {
+      Action::TupleInit(items) => assert_eq!(items.len(), 2),
+      action => panic!("expected a tuple, got {:?}", action),
+    },
+    action => panic!("expected a return, got {:?}", action),
+  }
+  match &res.functions[1].body.actions[0] {
+    Action::Return(Some(action)) => assert!(matches!(**action, Action::StaticNumber(_))),
+    action => panic!("expected a return, got {:?}", action),
+  }
+}
+
+#[test]
+fn test_tuple_destructure() {
+  let res = parse_str(
+    r#"
+      fn test() {
+        let a, b = split("a")
+        const c ,d,e = (1, 2, 3)
+        let f = a
+      }
+    "#,
+  );
+  let body = &res.functions[0].body.actions;
+  match &body[0] {
+    Action::Destructure(meta) => {
+      assert_eq!(meta.names, vec!["a", "b"]);
+      assert!(matches!(*meta.action, Action::FunctionCall(_)));
+    }
+    action => panic!("expected a destructure, got {:?}", action),
+  }
+  match &body[1] {
+    Action::Destructure(meta) => assert_eq!(meta.names, vec!["c", "d", "e"]),
+    action => panic!("expected a destructure, got {:?}", action),
+  }
+  assert!(matches!(body[2], Action::Variable(_)));
+}
+
+#[test]
+fn test_tuple_multiple_return_values_invalid() {
+  parse_str_fail("fn test() { return a, }");
+  parse_str_fail("fn test() { return , a }");
+  parse_str_fail("fn test() { let a, = foo() }");
+  parse_str_fail("fn test() { let a, b }");
+  parse_str_fail("fn test() { let a, b c = foo() }");
+  parse_str_fail("fn test() { let a, b = }");
+}
@@ -0,0 +1,89 @@
+use super::*;
+use crate::validate::{validate, validate_block};
+
+#[test]
+fn test_validate_parsed_program() {
+  let res = parse_str(
+    r#"
+      const foo = "bar"
+      fn test(items []string) {
+        loop {
+          for item in items {
+            let a = item
+            a = foo(a)
+          }
+        }
+      }
+    "#,
+  );
+  assert_eq!(validate(&res), Vec::<String>::new());
+}
+
+#[test]
+fn test_validate_empty_var_ref() {
+  let mut res = parse_str(
+    r#"
+      fn test() {}
+    "#,
+  );
+  res.functions[0]
+    .body
+    .actions
+    .push(Action::VarRef(String::new()));
+  assert_eq!(validate(&res).len(), 1);
+}
+
+#[test]
+fn test_validate_spans() {
+  let mut res = parse_str(
+    r#"
+      const foo = "bar"
+      fn test() {
+        let a = 1
+        loop {
+          print(a)
+        }
+      }
+    "#,
+  );
+  assert_eq!(validate(&res), Vec::<String>::new());
+
+  let len = res.contents.len();
+  res.functions[0].span = Span::new(0, len + 1);
+  res.globals[0].action_span = Span::new(5, 4);
+  assert_eq!(validate(&res).len(), 2);
+}
+
+#[test]
+fn test_validate_nested_block_spans() {
+  let mut res = parse_str(
+    r#"
+      fn test() {
+        loop {
+          print(a)
+        }
+      }
+    "#,
+  );
+  match &mut res.functions[0].body.actions[0] {
+    Action::Loop(body) => body.action_spans[0] = Span::new(0, 10_000),
+    action => panic!("expected a loop, got {:?}", action),
+  }
+  assert_eq!(validate(&res).len(), 1);
+}
+
+#[test]
+fn test_validate_lazy_body() {
+  let mut res = Parser::parse_with_options(
+    "fn test() { let a = 1 }",
+    ParserOptions {
+      lazy_bodies: true,
+      ..Default::default()
+    },
+  )
+  .unwrap();
+  res.parse_bodies().unwrap();
+  let body = &res.functions[0].body;
+  assert_eq!(validate_block(&res, body), Vec::<String>::new());
+  assert_eq!(body.action_spans.len(), 1);
+}
@@ -37,6 +37,37 @@ fn test_variable_global_let_fails() {
   );
 }
 
+#[test]
+fn test_variable_global_const_expressions() {
+  let res = parse_str(
+    r#"
+                const size = 4 * 1024
+                const names = ["a", "b"]
+                const half = -size / 2 + 0.5
+            "#,
+  );
+  assert_eq!(res.globals.len(), 3);
+  assert!(res.globals.iter().all(|global| global.action.is_constant()));
+}
+
+fn parse_constant_globals(contents: &str) -> Result<Parser, Box<ParsingError>> {
+  let options = ParserOptions {
+    constant_globals: true,
+    ..ParserOptions::default()
+  };
+  Parser::parse_with_options(contents.as_bytes(), options)
+}
+
+#[test]
+fn test_variable_global_const_not_constant() {
+  parse_constant_globals("const foo = [1, 2 + 3]\nconst bar = foo[0]").unwrap();
+  parse_constant_globals("const foo = bar()").unwrap_err();
+  parse_constant_globals("const foo = [1, bar()]").unwrap_err();
+  parse_constant_globals("const foo = |x| x").unwrap_err();
+  // Without the option any value is allowed
+  parse_str("const foo = bar()");
+}
+
 #[test]
 fn test_variable_starts_with_number_fails() {
   // variables should never start with a number
@@ -46,3 +77,185 @@ fn test_variable_starts_with_number_fails() {
             "#,
   );
 }
+
+#[test]
+fn test_variable_assignment() {
+  parse_str(
+    r#"
+                fn test() {
+                  let foo = 1
+                  foo = 2
+                }
+            "#,
+  );
+}
+
+#[test]
+fn test_variable_without_name() {
+  parse_str_fail(
+    r#"
+      fn test() {
+        let = 2
+      }
+    "#,
+  );
+}
+
+#[test]
+fn test_variable_compound_assignment() {
+  let res = parse_str(
+    r#"
+      fn test() {
+        foo += 1
+        foo -= bar * 2
+        foo *= 3
+        foo/=4
+        foo %= 5
+      }
+    "#,
+  );
+  let operators: Vec<&str> = res.functions[0]
+    .body
+    .actions
+    .iter()
+    .map(|action| match action {
+      Action::Assigment(meta) => {
+        assert_eq!(meta.name, "foo");
+        match &*meta.action {
+          Action::BinaryOp(binary_op) => {
+            match &*binary_op.left {
+              Action::VarRef(name) => assert_eq!(name, "foo"),
+              action => panic!("expected a variable reference, got {:?}", action),
+            }
+            binary_op.operator.into()
+          }
+          action => panic!("expected a binary operator, got {:?}", action),
+        }
+      }
+      action => panic!("expected a assignment, got {:?}", action),
+    })
+    .collect();
+  assert_eq!(operators, vec!["+", "-", "*", "/", "%"]);
+}
+
+#[test]
+fn test_variable_compound_assignment_right_side_is_grouped() {
+  let res = parse_str(
+    r#"
+      fn test() {
+        foo -= bar + 1
+      }
+    "#,
+  );
+  match &res.functions[0].body.actions[0] {
+    Action::Assigment(meta) => match &*meta.action {
+      Action::BinaryOp(binary_op) => match &*binary_op.right {
+        Action::BinaryOp(_) => {}
+        action => panic!("expected a binary operator, got {:?}", action),
+      },
+      action => panic!("expected a binary operator, got {:?}", action),
+    },
+    action => panic!("expected a assignment, got {:?}", action),
+  }
+}
+
+#[test]
+fn test_variable_compound_assignment_invalid() {
+  parse_str_fail(
+    r#"
+      fn test() {
+        foo +=
+      }
+    "#,
+  );
+  parse_str_fail(
+    r#"
+      fn test() {
+        _ += 1
+      }
+    "#,
+  );
+  parse_str_fail(
+    r#"
+      fn test() {
+        foo + = 1
+      }
+    "#,
+  );
+}
+
+#[test]
+fn test_variable_data_type() {
+  let res = parse_str(
+    r#"
+      const a = 1
+      const b: int = 2
+      const c int = 3
+      const d:[]string = []
+      const e map[string]int = {}
+    "#,
+  );
+  let data_types: Vec<Option<&str>> = res
+    .globals
+    .iter()
+    .map(|var| var.data_type.as_ref().map(|type_| type_.name.as_str()))
+    .collect();
+  assert_eq!(
+    data_types,
+    vec![
+      None,
+      Some("int"),
+      Some("int"),
+      Some("[]string"),
+      Some("map")
+    ]
+  );
+}
+
+#[test]
+fn test_variable_data_type_in_function() {
+  let res = parse_str(
+    r#"
+      fn test() {
+        let a int = 5
+        let b: string = "foo"
+      }
+    "#,
+  );
+  match &res.functions[0].body.actions[0] {
+    Action::Variable(var) => {
+      assert_eq!(var.name, "a");
+      assert_eq!(var.data_type.as_ref().unwrap().name, "int");
+    }
+    action => panic!("expected a variable, got {:?}", action),
+  }
+}
+
+#[test]
+fn test_variable_data_type_invalid() {
+  parse_str_fail("const a: = 1");
+  parse_str_fail("const a int string = 1");
+  parse_str_fail("const a int");
+}
+
+#[test]
+fn test_variable_spans() {
+  let res = parse_str("const foo: int = 1 + bar\nconst baz=\"a\"\nconst qux = 2 // comment\n");
+  let var = &res.globals[0];
+  assert_eq!(res.span_text(var.name_span), "foo");
+  assert_eq!(res.span_text(var.action_span), "1 + bar");
+  let var = &res.globals[1];
+  assert_eq!(res.span_text(var.name_span), "baz");
+  assert_eq!(res.span_text(var.action_span), "\"a\"");
+  assert_eq!(res.span_text(res.globals[2].action_span), "2");
+
+  let res = parse_str("fn test() {\n  let a = foo()\n}");
+  match &res.functions[0].body.actions[0] {
+    Action::Variable(var) => {
+      assert!(var.var_type.is_mutable());
+      assert_eq!(res.span_text(var.name_span), "a");
+      assert_eq!(res.span_text(var.action_span), "foo()");
+    }
+    action => panic!("expected a variable, got {:?}", action),
+  }
+}
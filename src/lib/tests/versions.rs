@@ -0,0 +1,58 @@
+use super::*;
+
+fn parse_str_with_options(
+  contents: &str,
+  options: ParserOptions,
+) -> Result<Parser, Box<ParsingError>> {
+  Parser::parse_with_options(contents.as_bytes(), options)
+}
+
+#[test]
+fn test_version_default() {
+  let res = parse_str("fn foo() {}");
+  assert_eq!(res.options.version, None);
+  assert_eq!(res.version(), Version::LATEST);
+}
+
+#[test]
+fn test_version_pragma() {
+  let res = parse_str("#version 0.1\nfn foo() {}");
+  assert_eq!(res.options.version, Some(Version { major: 0, minor: 1 }));
+  assert_eq!(res.functions.len(), 1);
+
+  parse_str("#version 0.1");
+  parse_str("// The version of this file\n#version   0.1 // comment\n\nconst foo = 1");
+}
+
+#[test]
+fn test_version_option() {
+  let options = ParserOptions {
+    version: Some(Version { major: 0, minor: 1 }),
+    ..ParserOptions::default()
+  };
+  let res = parse_str_with_options("fn foo() {}", options).unwrap();
+  assert_eq!(res.version(), Version { major: 0, minor: 1 });
+}
+
+#[test]
+fn test_version_pragma_invalid() {
+  // Unsupported versions
+  parse_str_fail("#version 99.0");
+  parse_str_fail("#version 0.1000");
+  // Malformed versions
+  parse_str_fail("#version");
+  parse_str_fail("#version 1");
+  parse_str_fail("#version 0.1.2");
+  parse_str_fail("#version a.b");
+  parse_str_fail("#version0.1");
+  parse_str_fail("#version 0.1 fn foo() {}");
+  parse_str_fail("#foo 0.1");
+  // The pragma must be at the top of the code
+  parse_str_fail("fn foo() {}\n#version 0.1");
+  parse_str_fail("#version 0.1\n#version 0.1");
+}
+
+#[test]
+fn test_version_display() {
+  assert_eq!(Version { major: 0, minor: 2 }.to_string(), "0.2");
+}
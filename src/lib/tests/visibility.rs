@@ -0,0 +1,43 @@
+use super::*;
+
+#[test]
+fn test_visibility_public_items() {
+  let res = parse_str(
+    r#"
+      pub fn foo() {}
+      pub struct Bar {}
+      pub enum Baz {}
+      pub const qux = 1
+    "#,
+  );
+  assert!(res.functions[0].visibility.is_public());
+  assert!(res.structs[0].visibility.is_public());
+  assert!(res.enums[0].visibility.is_public());
+  assert!(res.globals[0].visibility.is_public());
+}
+
+#[test]
+fn test_visibility_private_by_default() {
+  let res = parse_str(
+    r#"
+      fn foo() {
+        let bar = 1
+      }
+      struct Bar {}
+      const qux = 1
+    "#,
+  );
+  assert_eq!(res.functions[0].visibility, Visibility::Private);
+  assert_eq!(res.structs[0].visibility, Visibility::Private);
+  assert_eq!(res.globals[0].visibility, Visibility::Private);
+}
+
+#[test]
+fn test_visibility_invalid() {
+  parse_str_fail("pub");
+  parse_str_fail("pub pub fn foo() {}");
+  parse_str_fail("pubfn foo() {}");
+  parse_str_fail("pub impl Foo {}");
+  parse_str_fail("pub import \"foo\"");
+  parse_str_fail("fn foo() { pub let bar = 1 }");
+}
@@ -0,0 +1,48 @@
+use super::*;
+
+/// A single decision made by the parser
+/// These are only collected when the trace-parse feature is enabled
+#[derive(Debug)]
+pub enum TraceEvent {
+  /// A sub parser consumed the bytes from start until end
+  Consumed {
+    parser: &'static str,
+    start: usize,
+    end: usize,
+  },
+  /// The parser went back from an index to an earlier index
+  Backtracked { from: usize, to: usize },
+}
+
+impl Display for TraceEvent {
+  fn fmt(&self, f: &mut core::fmt::Formatter) -> core::fmt::Result {
+    match self {
+      Self::Consumed { parser, start, end } => {
+        write!(f, "{} consumed {}..{}", parser, start, end)
+      }
+      Self::Backtracked { from, to } => write!(f, "backtracked from {} to {}", from, to),
+    }
+  }
+}
+
+impl Parser {
+  /// Register that a sub parser consumed everything from start until the current index
+  #[allow(unused_variables)]
+  pub fn trace_consumed(&mut self, parser: &'static str, start: usize) {
+    #[cfg(feature = "trace-parse")]
+    self.trace.push(TraceEvent::Consumed {
+      parser,
+      start,
+      end: self.index,
+    });
+  }
+  /// Register that the parser went back to an earlier index
+  #[allow(unused_variables)]
+  pub fn trace_backtracked(&mut self, from: usize) {
+    #[cfg(feature = "trace-parse")]
+    self.trace.push(TraceEvent::Backtracked {
+      from,
+      to: self.index,
+    });
+  }
+}
@@ -0,0 +1,69 @@
+use super::*;
+
+/// A `trait Foo {}` with the method signatures a type needs to implement
+#[derive(Debug)]
+pub struct Trait {
+  pub name: String,
+  /// The required methods, these don't have a body
+  pub methods: Vec<Function>,
+}
+
+pub struct ParseTrait<'a> {
+  p: &'a mut Parser,
+  res: Trait,
+}
+
+impl<'a> ParseTrait<'a> {
+  pub fn start(p: &'a mut Parser) -> Result<Trait, Box<ParsingError>> {
+    let start = p.index;
+    let mut s = Self {
+      p,
+      res: Trait {
+        name: String::new(),
+        methods: vec![],
+      },
+    };
+    s.parse()?;
+    s.p.trace_consumed("trait", start);
+    Ok(s.res)
+  }
+  fn parse(&mut self) -> Result<(), Box<ParsingError>> {
+    let name = self.p.next_ident()?.name;
+    if name.is_empty() {
+      return self.p.error(ParsingErrorType::Custom("Missing trait name"));
+    }
+    self.res.name = name;
+
+    match self.p.next_while(" \t\n") {
+      Some('{') => {}
+      Some(c) => return self.p.unexpected_char(c),
+      None => return self.p.unexpected_eof(),
+    }
+
+    loop {
+      match self.p.next_while(" \t\n,") {
+        Some('}') => return Ok(()),
+        Some(_) => self.p.index -= 1,
+        None => return self.p.unexpected_eof(),
+      }
+
+      if self.p.try_match(&[(Keywords::Fn, " \t\n")]).is_none() {
+        return self.p.error(ParsingErrorType::Custom(
+          "A trait can only contain method signatures",
+        ));
+      }
+      let method = ParseFunction::start_signature(self.p, self.res.name.clone())?;
+      if self
+        .res
+        .methods
+        .iter()
+        .any(|other| other.name == method.name)
+      {
+        return self
+          .p
+          .error(ParsingErrorType::Custom("Duplicate method name"));
+      }
+      self.res.methods.push(method);
+    }
+  }
+}
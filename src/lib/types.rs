@@ -3,60 +3,153 @@ use super::*;
 #[derive(Debug, Clone)]
 pub struct Type {
   pub name: String,
+  /// The key and value type of a map like `map[string]int`, the name of a map is `map`
+  pub map: Option<Box<(Type, Type)>>,
+  /// The item types of a tuple like `(int, string)`, the name of a tuple is `tuple`
+  pub tuple: Option<Vec<Type>>,
+  /// A type that can also be null like `?string`
+  pub optional: bool,
 }
 
 impl Type {
   fn empty() -> Self {
     Self {
       name: String::new(),
+      map: None,
+      tuple: None,
+      optional: false,
     }
   }
-}
-
-struct ParseTypeStateTypeName {
-  name: NameBuilder,
-}
-
-enum ParseTypeState {
-  TypeName(ParseTypeStateTypeName),
+  /// A type that is not written down and needs to be inferred,
+  /// for example the arguments of `|x| x + 1`
+  pub fn inferred() -> Self {
+    Self::empty()
+  }
 }
 
 pub struct ParseType<'a> {
   p: &'a mut Parser,
   res: Type,
-  state: ParseTypeState,
 }
 
 impl<'a> ParseType<'a> {
-  pub fn start(p: &'a mut Parser, go_back_one: bool) -> Result<Type, ParsingError> {
+  pub fn start(p: &'a mut Parser, go_back_one: bool) -> Result<Type, Box<ParsingError>> {
     if go_back_one {
       p.index -= 1;
     }
+    let start = p.index;
     let mut s = Self {
       p,
       res: Type::empty(),
-      state: ParseTypeState::TypeName(ParseTypeStateTypeName {
-        name: NameBuilder::new(),
-      }),
     };
     s.parse()?;
+    if s.res.optional && s.res.name.is_empty() {
+      return s.p.error(ParsingErrorType::Custom("Missing type after ?"));
+    }
+    s.p.trace_consumed("type", start);
     Ok(s.res)
   }
-  fn parse(&mut self) -> Result<(), ParsingError> {
-    while let Some(c) = self.p.next_char() {
-      match &mut self.state {
-        ParseTypeState::TypeName(meta) => match c {
-          '=' | ')' | '}' => {
-            self.p.index -= 1;
-            self.res.name = meta.name.to_string(self.p)?;
-            return Ok(());
-          }
-          _ => {
-            meta.name.push(c);
-          }
-        },
+  fn parse(&mut self) -> Result<(), Box<ParsingError>> {
+    // Whitespace and the `?` of a optional type can come before the name
+    loop {
+      match self.p.next_while(" \t\n") {
+        Some('?') if self.res.optional => {
+          return self
+            .p
+            .error(ParsingErrorType::Custom("A type can only be optional once"))
+        }
+        Some('?') => self.res.optional = true,
+        Some('(') => return self.parse_tuple(),
+        Some(_) => {
+          self.p.index -= 1;
+          break;
+        }
+        None => return Ok(()),
       }
     }
+
+    // The `[]` or `[5]` of a array like `[]string`, these are part of the name
+    let start = self.p.index;
+    while let Some('[') = self.p.seek_next_char() {
+      loop {
+        match self.p.next_char() {
+          Some(']') => break,
+          Some(_) => {}
+          None => return self.p.unexpected_eof(),
+        }
+      }
+    }
+
+    let ident = self.p.next_ident()?;
+    if ident.name == "map" && ident.span.start == start && self.p.eat('[') {
+      self.res.name = ident.name;
+      return self.parse_map();
+    }
+
+    // The generic arguments like the `<int>` in `list<int>` are also part of the name
+    if !ident.name.is_empty() && self.p.eat('<') {
+      let mut depth = 1;
+      while depth > 0 {
+        match self.p.next_char() {
+          Some('<') => depth += 1,
+          Some('>') => depth -= 1,
+          Some(_) => {}
+          None => return self.p.unexpected_eof(),
+        }
+      }
+    }
+    // The type ends at the first char that can't be part of it,
+    // the caller checks if that char is allowed after the type
+    self.res.name = self.p.span_text(Span::new(start, self.p.index));
+    Ok(())
+  }
+  /// Parses the `string]int` part of `map[string]int`
+  fn parse_map(&mut self) -> Result<(), Box<ParsingError>> {
+    let key = ParseType::start(self.p, false)?;
+    if key.name.is_empty() {
+      return self
+        .p
+        .error(ParsingErrorType::Custom("Missing map key type"));
+    }
+    match self.p.next_char() {
+      Some(']') => {}
+      Some(c) => return self.p.unexpected_char(c),
+      None => return self.p.unexpected_eof(),
+    }
+    let value = ParseType::start(self.p, false)?;
+    if value.name.is_empty() {
+      return self
+        .p
+        .error(ParsingErrorType::Custom("Missing map value type"));
+    }
+    self.res.map = Some(Box::new((key, value)));
+    Ok(())
+  }
+  /// Parses the `int, string)` part of `(int, string)`
+  fn parse_tuple(&mut self) -> Result<(), Box<ParsingError>> {
+    let mut items = vec![];
+    loop {
+      let item = ParseType::start(self.p, false)?;
+      if item.name.is_empty() {
+        return self
+          .p
+          .error(ParsingErrorType::Custom("Missing tuple item type"));
+      }
+      items.push(item);
+      match self.p.next_while(" \t\n") {
+        Some(',') => {}
+        Some(')') => break,
+        Some(c) => return self.p.unexpected_char(c),
+        None => return self.p.unexpected_eof(),
+      }
+    }
+    if items.len() < 2 {
+      return self
+        .p
+        .error(ParsingErrorType::Custom("A tuple needs at least two items"));
+    }
+    self.res.name = String::from("tuple");
+    self.res.tuple = Some(items);
     Ok(())
   }
 }
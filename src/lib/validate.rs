@@ -0,0 +1,167 @@
+use super::*;
+
+/// Checks the invariants of the parsed program
+/// A broken invariant is a bug in the parser and not in the parsed code,
+/// so this returns a list of problems instead of a ParsingError
+pub fn validate(p: &Parser) -> Vec<String> {
+  let mut problems = vec![];
+  let len = p.contents.len();
+
+  for var in &p.globals {
+    validate_variable(var, len, &mut problems);
+    var
+      .action
+      .visit(&mut |action| validate_action(action, len, &mut problems));
+  }
+  for function in &p.functions {
+    validate_span(function.span, len, "function", &mut problems);
+    problems.append(&mut validate_function(p, function));
+  }
+  for struct_ in &p.structs {
+    validate_span(struct_.span, len, "struct", &mut problems);
+  }
+  for enum_ in &p.enums {
+    validate_span(enum_.span, len, "enum", &mut problems);
+  }
+  for import in &p.imports {
+    validate_span(import.span, len, "import", &mut problems);
+  }
+  for trait_ in &p.traits {
+    if trait_.methods.iter().any(|method| method.name.is_none()) {
+      problems.push(String::from("trait method without a name"));
+    }
+  }
+  for alias in &p.type_aliases {
+    if alias.name.is_empty() || alias.type_.name.is_empty() {
+      problems.push(String::from("type alias without a name or type"));
+    }
+  }
+  for impl_ in &p.impls {
+    for method in &impl_.methods {
+      if method.name.is_none() {
+        problems.push(String::from("method without a name"));
+      }
+      validate_span(method.span, len, "method", &mut problems);
+      validate_body(&method.body, len, &mut problems);
+    }
+  }
+
+  problems
+}
+
+fn validate_function(p: &Parser, function: &Function) -> Vec<String> {
+  let mut problems = vec![];
+  if function.name.is_none() {
+    problems.push(String::from("function without a name"));
+  }
+  validate_body(&function.body, p.contents.len(), &mut problems);
+  problems
+}
+
+/// Checks the invariants of a single block of the program,
+/// used after a lazy body is parsed so the rest of the program is not checked again
+pub fn validate_block(p: &Parser, block: &Block) -> Vec<String> {
+  let mut problems = vec![];
+  validate_body(block, p.contents.len(), &mut problems);
+  problems
+}
+
+/// Panics if the parsed program breaks an invariant
+/// This runs after parsing in debug builds (and thus also in tests)
+pub fn assert_valid(p: &Parser) {
+  assert_no_problems(validate(p));
+}
+
+/// Panics if the block breaks an invariant, this runs after parsing a lazy body in debug builds
+pub fn assert_valid_block(p: &Parser, block: &Block) {
+  assert_no_problems(validate_block(p, block));
+}
+
+fn assert_no_problems(problems: Vec<String>) {
+  if !problems.is_empty() {
+    panic!("Parsed program is invalid:\n{}", problems.join("\n"));
+  }
+}
+
+/// A span must be inside of the parsed contents
+fn validate_span(span: Span, len: usize, what: &str, problems: &mut Vec<String>) {
+  if span.start > span.end || span.end > len {
+    problems.push(format!(
+      "{} span {}..{} is outside of the contents with a length of {}",
+      what, span.start, span.end, len
+    ));
+  }
+}
+
+fn validate_body(body: &Block, len: usize, problems: &mut Vec<String>) {
+  validate_block_spans(body, len, problems);
+  body.visit(&mut |action| validate_action(action, len, problems));
+}
+
+/// Checks the span of the block and its actions, the actions nested deeper are checked by validate_action
+fn validate_block_spans(block: &Block, len: usize, problems: &mut Vec<String>) {
+  validate_span(block.span, len, "block", problems);
+  for span in &block.action_spans {
+    validate_span(*span, len, "action", problems);
+  }
+}
+
+fn validate_variable(var: &Variable, len: usize, problems: &mut Vec<String>) {
+  if var.name.is_empty() {
+    problems.push(String::from("variable without a name"));
+  }
+  validate_span(var.name_span, len, "variable name", problems);
+  validate_span(var.action_span, len, "variable value", problems);
+}
+
+fn validate_action(action: &Action, len: usize, problems: &mut Vec<String>) {
+  // The actions inside of these blocks are visited on their own,
+  // only the spans of the block itself have to be checked here
+  let blocks: Vec<&Block> = match action {
+    Action::For(meta) => vec![&meta.body],
+    Action::ForClassic(meta) => vec![&meta.body],
+    Action::While(meta) | Action::DoWhile(meta) => vec![&meta.body],
+    Action::Loop(body) => vec![body],
+    Action::Try(meta) => vec![&meta.body, &meta.catch_body],
+    Action::If(meta) => {
+      let mut blocks = vec![&meta.body];
+      blocks.extend(meta.else_ifs.iter().map(|(_, body)| body));
+      blocks.extend(meta.else_body.iter());
+      blocks
+    }
+    Action::Closure(meta) | Action::FunctionDecl(meta) => vec![&meta.body],
+    Action::Match(meta) => meta.arms.iter().map(|arm| &arm.body).collect(),
+    _ => vec![],
+  };
+  for block in blocks {
+    validate_block_spans(block, len, problems);
+  }
+
+  if let Action::FunctionCall(meta) = action {
+    for span in &meta.argument_spans {
+      validate_span(*span, len, "argument", problems);
+    }
+  }
+
+  match action {
+    Action::Variable(var) => validate_variable(var, len, problems),
+    Action::StaticString(meta) => validate_span(meta.span, len, "string", problems),
+    Action::StaticNumber(meta) => validate_span(meta.span, len, "number", problems),
+    Action::Assigment(meta) if meta.name.is_empty() => {
+      problems.push(String::from("assignment without a name"))
+    }
+    Action::FunctionCall(meta) if meta.name.is_empty() => {
+      problems.push(String::from("function call without a name"))
+    }
+    Action::VarRef(name) if name.is_empty() => {
+      problems.push(String::from("variable reference without a name"))
+    }
+    Action::For(meta) if meta.item_name.is_empty() => {
+      problems.push(String::from("for loop without a item name"))
+    }
+    Action::FunctionDecl(meta) if meta.name.is_none() => {
+      problems.push(String::from("function declaration without a name"))
+    }
+    _ => {}
+  }
+}
@@ -6,25 +6,107 @@ pub enum VarType {
   Const,
 }
 
+impl VarType {
+  /// Returns true if the variable can be assigned a new value after it's declared
+  pub fn is_mutable(&self) -> bool {
+    match self {
+      Self::Let => true,
+      Self::Const => false,
+    }
+  }
+}
+
 #[derive(Debug)]
 pub struct Variable {
   pub var_type: VarType,
+  /// The declared type like `int` in `let a: int = 1`
   pub data_type: Option<Type>,
   pub name: String,
+  pub name_span: Span,
+  /// The initializer, the value after the `=`
+  pub action: Box<Action>,
+  pub action_span: Span,
+  /// Only global constants can be public
+  pub visibility: Visibility,
+}
+
+impl From<Variable> for Action {
+  fn from(value: Variable) -> Self {
+    Action::Variable(value)
+  }
+}
+
+/// `let a, b = foo()`
+#[derive(Debug)]
+pub struct ActionDestructure {
+  pub var_type: VarType,
+  pub names: Vec<String>,
   pub action: Box<Action>,
 }
 
-impl Into<Action> for Variable {
-  fn into(self) -> Action {
-    Action::Variable(self)
+impl From<ActionDestructure> for Action {
+  fn from(value: ActionDestructure) -> Self {
+    Action::Destructure(value)
+  }
+}
+
+/// Returns true if the variable that starts here has multiple names like the `a, b` in `let a, b = foo()`
+pub fn is_destructure(p: &mut Parser) -> bool {
+  let start = p.index;
+  let res = match p.next_ident() {
+    Ok(name) if !name.name.is_empty() => p.next_while(" \t") == Some(','),
+    _ => false,
+  };
+  p.index = start;
+  res
+}
+
+/// Parses the `a, b = foo()` part of `let a, b = foo()`
+pub fn parse_destructure(
+  p: &mut Parser,
+  var_type: VarType,
+) -> Result<ActionDestructure, Box<ParsingError>> {
+  let start = p.index;
+  let mut names = vec![];
+  loop {
+    let name = p.next_ident()?;
+    if name.name.is_empty() {
+      return p.error(ParsingErrorType::Custom("Missing variable name"));
+    }
+    names.push(name.name);
+
+    match p.next_while(" \t\n") {
+      Some(',') => {}
+      Some('=') => break,
+      Some(c) => return p.unexpected_char(c),
+      None => return p.unexpected_eof(),
+    }
   }
+
+  let action = ParseAction::start(p, false, ActionToExpect::Assignment(""))?;
+  p.trace_consumed("destructure", start);
+  Ok(ActionDestructure {
+    var_type,
+    names,
+    action: Box::new(action),
+  })
+}
+
+pub fn parse_var(
+  p: &mut Parser,
+  var_type_option: Option<VarType>,
+) -> Result<Variable, Box<ParsingError>> {
+  parse_var_until(p, var_type_option, "")
 }
 
-pub fn parse_var<'a>(
-  p: &'a mut Parser,
+/// Parses a variable of which the value can end with one of the end chars,
+/// like the `;` after `let i = 0` in a for loop
+pub fn parse_var_until(
+  p: &mut Parser,
   var_type_option: Option<VarType>,
-) -> Result<Variable, ParsingError> {
-  let mut name = NameBuilder::new();
+  end: &'static str,
+) -> Result<Variable, Box<ParsingError>> {
+  let start = p.index;
   let mut data_type: Option<Type> = None;
 
   let var_type = if let Some(type_) = var_type_option {
@@ -32,7 +114,7 @@ pub fn parse_var<'a>(
   } else {
     let to_match = &[(Keywords::Const, " \t\n"), (Keywords::Let, " \t\n")];
     let match_result = p.try_match(to_match);
-    if let None = match_result {
+    if match_result.is_none() {
       return p.unexpected_char(*p.contents.get(p.index).unwrap() as char);
     }
 
@@ -44,31 +126,28 @@ pub fn parse_var<'a>(
   };
 
   // Parse name
-  let mut next_char = p.next_while(" \t\n");
-  loop {
-    if let Some(c) = next_char {
-      match c {
-        _ if legal_name_char(c) => name.push(c),
-        ' ' | '\t' | '\n' => break,
-        ':' | '=' => {
-          p.index -= 1;
-          break;
-        }
-        c => return p.unexpected_char(c),
-      }
-    } else {
-      return p.unexpected_eof();
-    }
-    next_char = p.next_char();
+  let name = p.next_ident()?;
+  if name.name.is_empty() {
+    return match p.next_char() {
+      Some(':') | Some('=') => p.error(ParsingErrorType::Custom("Missing variable name")),
+      Some(c) => p.unexpected_char(c),
+      None => p.unexpected_eof(),
+    };
   }
 
-  // Parse the variable type if set
-  next_char = p.next_while(" \t\n");
-  if let None = next_char {
-    return p.unexpected_eof();
-  }
-  if next_char.unwrap() == ':' {
-    data_type = Some(ParseType::start(p, true)?);
+  // Parse the variable type if set, like `let a: int` or `let a int`
+  let mut next_char = p.next_while(" \t\n");
+  let type_ = match next_char {
+    Some(':') => Some(ParseType::start(p, false)?),
+    Some('=') => None,
+    Some(_) => Some(ParseType::start(p, true)?),
+    None => return p.unexpected_eof(),
+  };
+  if let Some(type_) = type_ {
+    if type_.name.is_empty() {
+      return p.error(ParsingErrorType::Custom("Missing variable type"));
+    }
+    data_type = Some(type_);
     next_char = p.next_while(" \t\n");
   }
 
@@ -80,12 +159,17 @@ pub fn parse_var<'a>(
   }
 
   // Parse the action after the action after the =
-  let action = ParseAction::start(p, false, ActionToExpect::Assignment(""))?;
+  let action_start = p.skip_whitespace();
+  let action = ParseAction::start(p, false, ActionToExpect::Assignment(end))?;
+  p.trace_consumed("variable", start);
 
   Ok(Variable {
     var_type,
     data_type,
-    name: name.to_string(p)?,
+    name: name.name,
+    name_span: name.span,
     action: Box::new(action),
+    action_span: p.trimmed_span(action_start, p.index),
+    visibility: Visibility::Private,
   })
 }
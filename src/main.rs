@@ -1,20 +1,260 @@
-mod lib;
-
-use lib::Parser;
+use gpl::{crash, program, reachability, reduce, stats, tags, Parser, ParserOptions};
+use std::env;
 use std::fs::File;
 use std::io::prelude::*;
+use std::path::Path;
 
 fn main() {
-    // the .tp is a temporary file extension (Talpa Language)
-    // the example file should be updated with all working components
-    let mut file = File::open("./src/example.gpl").unwrap();
+    let args: Vec<String> = env::args().skip(1).collect();
+    // Crash reports only contain the source if the user opts in
+    let include_source = args.iter().any(|arg| arg == "--include-source");
+    match args.first().map(|arg| arg.as_str()) {
+        Some("reduce") => match args.get(1) {
+            Some(file_name) => reduce_file(file_name),
+            None => println!("Usage: gpl reduce <file>"),
+        },
+        Some("graph") => match args.get(1) {
+            Some(file_name) => print_graph(file_name, args.iter().any(|arg| arg == "--json")),
+            None => println!("Usage: gpl graph <file> [--json]"),
+        },
+        Some("check") => match args.get(1) {
+            Some(file_name) => {
+                // The function the program starts at, everything that can't be reached from it is reported
+                let entry = match args.iter().position(|arg| arg == "--entry") {
+                    Some(i) => args.get(i + 1).map(|arg| arg.as_str()),
+                    None => Some("main"),
+                };
+                match entry {
+                    Some(entry) => check_program(file_name, entry),
+                    None => println!("Usage: gpl check <file> [--entry <function>]"),
+                }
+            }
+            None => println!("Usage: gpl check <file> [--entry <function>]"),
+        },
+        Some("tags") => {
+            let etags = args.iter().any(|arg| arg == "--etags");
+            let files: Vec<&String> = args[1..]
+                .iter()
+                .filter(|arg| !arg.starts_with("--"))
+                .collect();
+            if files.is_empty() {
+                println!("Usage: gpl tags [--etags] <files...>");
+            } else {
+                write_tags(&files, etags, include_source);
+            }
+        }
+        Some("parse") => match args.get(1) {
+            Some(file_name) => parse_file(
+                file_name,
+                args.iter().any(|arg| arg == "--trace-parse"),
+                include_source,
+            ),
+            None => println!("Usage: gpl parse <file> [--trace-parse]"),
+        },
+        Some("stats") => match args.get(1) {
+            Some(file_name) => print_stats(file_name, include_source),
+            None => println!("Usage: gpl stats <file>"),
+        },
+        // the .tp is a temporary file extension (Talpa Language)
+        // the example file should be updated with all working components
+        _ => parse_file(
+            "./src/example.tp",
+            args.iter().any(|arg| arg == "--trace-parse"),
+            include_source,
+        ),
+    }
+}
+
+fn read_file(file_name: &str) -> Vec<u8> {
+    let mut file = File::open(file_name).unwrap();
     let mut contents = vec![];
     file.read_to_end(&mut contents).unwrap();
-    match Parser::parse(contents) {
-        Err(err) => println!("{}", err),
-        Ok(res) => {
-            println!("Functions: {:?}", res.functions);
-            println!("Globals: {:?}", res.global_vars);
+    contents
+}
+
+/// Parses the contents and prints the error if there is one
+/// If the parser crashes a crash report is written to the current dir
+fn parse(contents: Vec<u8>, include_source: bool) -> Option<Parser> {
+    let input = contents.clone();
+    let parse = || Parser::parse_unguarded(contents, ParserOptions::default());
+    match crash::catch("parse", &input, include_source, parse) {
+        Ok(Ok(res)) => Some(res),
+        Ok(Err(err)) => {
+            println!("{}", err);
+            None
+        }
+        Err(report) => {
+            match report.write_to(Path::new(".")) {
+                Ok(path) => println!(
+                    "gpl crashed: {}\nA crash report was written to {}, it was not send anywhere.\nPlease attach it to a issue, use --include-source to also include the parsed code.",
+                    report.message,
+                    path.display()
+                ),
+                Err(err) => println!("gpl crashed and the crash report could not be written: {}", err),
+            }
+            None
+        }
+    }
+}
+
+/// Parses the file and prints the parsed program
+fn parse_file(file_name: &str, trace_parse: bool, include_source: bool) {
+    if let Some(res) = parse(read_file(file_name), include_source) {
+        if trace_parse {
+            print_trace(&res);
         }
+        println!("Functions: {:?}", res.functions);
+        println!("Globals: {:?}", res.globals);
+        println!("Structs: {:?}", res.structs);
+        println!("Enums: {:?}", res.enums);
     }
 }
+
+fn reduce_file(file_name: &str) {
+    let contents = read_file(file_name);
+    let failure = reduce::failure(&contents);
+    let reduced = reduce::reduce(&contents);
+
+    match (failure, reduced) {
+        (Some(failure), Some(reduced)) => {
+            println!("Reduced input that fails with {:?}:", failure);
+            println!("{}", String::from_utf8_lossy(&reduced));
+        }
+        _ => println!("{} parses without errors, nothing to reduce", file_name),
+    }
+}
+
+fn print_stats(file_name: &str, include_source: bool) {
+    let res = match parse(read_file(file_name), include_source) {
+        Some(res) => res,
+        None => return,
+    };
+    let stats = stats::stats(&res);
+
+    println!("Lines: {}", stats.lines);
+    println!("Functions: {}", stats.functions.len());
+    if let Some(longest) = stats.longest_function() {
+        println!(
+            "Longest function: {} ({} statements)",
+            function_name(&longest.name),
+            longest.statements
+        );
+    }
+    for function in &stats.functions {
+        println!(
+            "  {}: {} statements, complexity {}, max nesting {}",
+            function_name(&function.name),
+            function.statements,
+            function.complexity,
+            function.max_nesting
+        );
+    }
+}
+
+/// Parses the file with the files it imports and warns about the functions, methods and structs
+/// that can't be reached from the entry function
+fn check_program(file_name: &str, entry: &str) {
+    let program = match program::parse_program_from_disk(file_name, ParserOptions::default()) {
+        Ok(program) => program,
+        Err(err) => {
+            println!("{}", err);
+            return;
+        }
+    };
+    let entry_file = &program.files[0].1;
+    if !entry_file
+        .functions
+        .iter()
+        .any(|function| function.name.as_deref() == Some(entry))
+    {
+        println!(
+            "warning: entry function {} not found in {}",
+            entry, file_name
+        );
+        return;
+    }
+    for (path, unreachable) in reachability::unreachable_in_program(&program, entry) {
+        let source_map = program.file(path).unwrap().source_map();
+        let warn = |what: String, start: usize| {
+            println!(
+                "warning: {} is never used, at {}:{}",
+                what,
+                path,
+                source_map.line_of(start) + 1
+            )
+        };
+        for function in unreachable.functions {
+            warn(
+                format!("function {}", function_name(&function.name)),
+                function.span.start,
+            );
+        }
+        for (impl_, method) in unreachable.methods {
+            let name = format!(
+                "method {}.{}",
+                impl_.struct_name,
+                function_name(&method.name)
+            );
+            warn(name, method.span.start);
+        }
+        for struct_ in unreachable.structs {
+            warn(format!("struct {}", struct_.name), struct_.span.start);
+        }
+    }
+}
+
+/// Writes a ctags `tags` file or a etags `TAGS` file to the current dir
+/// Files that fail to parse are skipped so the other files still get tags
+fn write_tags(files: &[&String], etags: bool, include_source: bool) {
+    let mut all_tags = vec![];
+    for file_name in files {
+        if let Some(res) = parse(read_file(file_name), include_source) {
+            all_tags.append(&mut tags::tags(&res, file_name));
+        }
+    }
+    let (output_name, contents) = if etags {
+        ("TAGS", tags::etags(&all_tags))
+    } else {
+        ("tags", tags::ctags(&all_tags))
+    };
+    match File::create(output_name).and_then(|mut file| file.write_all(contents.as_bytes())) {
+        Ok(()) => println!("Wrote {} tags to {}", all_tags.len(), output_name),
+        Err(err) => println!("Could not write {}: {}", output_name, err),
+    }
+}
+
+/// Prints the import graph of the program that starts at the file
+fn print_graph(file_name: &str, json: bool) {
+    let program = match program::parse_program_from_disk(file_name, ParserOptions::default()) {
+        Ok(program) => program,
+        Err(err) => {
+            println!("{}", err);
+            return;
+        }
+    };
+    // The graph is printed to stdout so it can be piped into graphviz
+    if let Some(cycle) = program.import_cycle() {
+        eprintln!("Circular import: {}", cycle.join(" -> "));
+    }
+    if json {
+        println!("{}", program.graph_json());
+    } else {
+        println!("{}", program.graph_dot());
+    }
+}
+
+fn function_name(name: &Option<String>) -> &str {
+    name.as_deref().unwrap_or("<anonymous>")
+}
+
+#[cfg(feature = "trace-parse")]
+fn print_trace(res: &Parser) {
+    for event in &res.trace {
+        println!("{}", event);
+    }
+}
+
+#[cfg(not(feature = "trace-parse"))]
+fn print_trace(_: &Parser) {
+    println!("--trace-parse requires gpl to be build with the trace-parse feature");
+}
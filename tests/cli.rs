@@ -0,0 +1,80 @@
+use std::process::Command;
+
+// Runs the gpl binary from the root of the repo and returns what it printed
+fn gpl(args: &[&str]) -> String {
+  let output = Command::new(env!("CARGO_BIN_EXE_gpl"))
+    .args(args)
+    .current_dir(env!("CARGO_MANIFEST_DIR"))
+    .output()
+    .unwrap();
+  assert!(
+    output.status.success(),
+    "gpl {:?} failed: {:?}",
+    args,
+    output
+  );
+  String::from_utf8(output.stdout).unwrap()
+}
+
+#[test]
+fn test_cli_parse() {
+  let output = gpl(&["parse", "src/example.tp"]);
+  assert!(output.contains("Functions: "), "{}", output);
+  // Without a command the example is parsed
+  assert_eq!(gpl(&[]), output);
+}
+
+#[test]
+fn test_cli_trace_parse() {
+  let output = gpl(&["parse", "src/example.tp", "--trace-parse"]);
+  #[cfg(feature = "trace-parse")]
+  assert!(output.contains("function consumed"), "{}", output);
+  #[cfg(not(feature = "trace-parse"))]
+  assert!(output.contains("requires gpl to be build with the trace-parse feature"));
+  assert!(output.contains("Functions: "), "{}", output);
+}
+
+#[test]
+fn test_cli_check() {
+  let dir = std::env::temp_dir().join(format!("gpl-cli-check-{}", std::process::id()));
+  std::fs::create_dir_all(&dir).unwrap();
+  std::fs::write(
+    dir.join("main.tp"),
+    "import \"utils\"\nfn main() {\n  used()\n}\nfn start() {}\n",
+  )
+  .unwrap();
+  std::fs::write(
+    dir.join("utils.tp"),
+    "pub struct Unused {}\npub fn used() {}\n",
+  )
+  .unwrap();
+  let main = dir.join("main.tp");
+  let main = main.to_str().unwrap();
+
+  let output = gpl(&["check", main]);
+  assert!(
+    output.contains("warning: function start is never used"),
+    "{}",
+    output
+  );
+  assert!(
+    output.contains("warning: struct Unused is never used"),
+    "{}",
+    output
+  );
+  assert!(!output.contains("function used"), "{}", output);
+
+  let output = gpl(&["check", main, "--entry", "start"]);
+  assert!(
+    output.contains("warning: function main is never used"),
+    "{}",
+    output
+  );
+  assert!(
+    output.contains("warning: function used is never used"),
+    "{}",
+    output
+  );
+
+  std::fs::remove_dir_all(&dir).unwrap();
+}